@@ -0,0 +1,342 @@
+//! Normalização de endereços (CEP, UF) e consulta de CEP.
+
+use async_trait::async_trait;
+use sea_orm::{DatabaseConnection, EntityTrait, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::db::entities::cep;
+use crate::db::SharedConnection;
+use crate::error::AppError;
+
+pub const UFS: [&str; 27] = [
+    "AC", "AL", "AM", "AP", "BA", "CE", "DF", "ES", "GO", "MA", "MG", "MS", "MT", "PA", "PB",
+    "PE", "PI", "PR", "RJ", "RN", "RO", "RR", "RS", "SC", "SE", "SP", "TO",
+];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+pub struct CepAddress {
+    pub cep: String,
+    pub street: Option<String>,
+    pub neighborhood: Option<String>,
+    pub city: String,
+    pub state: String,
+}
+
+impl From<cep::Model> for CepAddress {
+    fn from(m: cep::Model) -> Self {
+        CepAddress {
+            cep: m.cep,
+            street: m.street,
+            neighborhood: m.neighborhood,
+            city: m.city,
+            state: m.state,
+        }
+    }
+}
+
+/// Valida o CEP e devolve apenas os 8 dígitos.
+pub fn normalize_cep(value: &str) -> Result<String, AppError> {
+    let trimmed = value.trim();
+    if !trimmed.chars().all(|c| c.is_ascii_digit() || c == '-' || c == '.') {
+        return Err(AppError::validation("CEP deve conter apenas números"));
+    }
+    let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() != 8 {
+        return Err(AppError::validation("CEP deve ter 8 dígitos"));
+    }
+    Ok(digits)
+}
+
+/// Formata o CEP para exibição (00000-000).
+pub fn format_cep(digits: &str) -> String {
+    if digits.len() == 8 {
+        format!("{}-{}", &digits[..5], &digits[5..])
+    } else {
+        digits.to_string()
+    }
+}
+
+/// Valida a sigla da UF e devolve em maiúsculas.
+pub fn normalize_uf(value: &str) -> Result<String, AppError> {
+    let uf = value.trim().to_uppercase();
+    if UFS.contains(&uf.as_str()) {
+        Ok(uf)
+    } else {
+        Err(AppError::validation(format!("UF inválida: {}", value.trim())))
+    }
+}
+
+/// Monta o endereço completo a partir dos campos estruturados,
+/// ex.: "Rua A, 123, Apto 2 - Centro".
+pub fn compose_address(
+    street: Option<&str>,
+    number: Option<&str>,
+    complement: Option<&str>,
+    neighborhood: Option<&str>,
+) -> Option<String> {
+    let street = street.map(str::trim).filter(|s| !s.is_empty())?;
+    let mut address = street.to_string();
+    for part in [number, complement].into_iter().flatten() {
+        let part = part.trim();
+        if !part.is_empty() {
+            address.push_str(", ");
+            address.push_str(part);
+        }
+    }
+    if let Some(n) = neighborhood.map(str::trim).filter(|s| !s.is_empty()) {
+        address.push_str(" - ");
+        address.push_str(n);
+    }
+    Some(address)
+}
+
+#[async_trait]
+pub trait CepResolver: Send + Sync {
+    /// Consulta o CEP (já normalizado); `Ok(None)` quando não encontrado.
+    async fn resolve(&self, cep: &str) -> Result<Option<CepAddress>, AppError>;
+}
+
+/// Consulta a tabela local de CEPs, importada com [`import_cep_table`].
+pub struct LocalCepResolver {
+    db: SharedConnection,
+}
+
+impl LocalCepResolver {
+    pub fn new(db: SharedConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CepResolver for LocalCepResolver {
+    async fn resolve(&self, cep: &str) -> Result<Option<CepAddress>, AppError> {
+        cep::Entity::find_by_id(cep.to_string())
+            .one(&*self.db.read().await)
+            .await
+            .map(|m| m.map(CepAddress::from))
+            .map_err(AppError::from)
+    }
+}
+
+/// Consulta o serviço ViaCEP. A URL base pode ser trocada (ex.: servidor local nos testes).
+#[cfg(feature = "viacep")]
+pub struct ViaCepResolver {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "viacep")]
+impl ViaCepResolver {
+    pub const DEFAULT_URL: &'static str = "https://viacep.com.br/ws";
+
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(feature = "viacep")]
+impl Default for ViaCepResolver {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_URL)
+    }
+}
+
+#[cfg(feature = "viacep")]
+#[derive(Deserialize)]
+struct ViaCepResponse {
+    #[serde(default)]
+    erro: Option<serde_json::Value>,
+    #[serde(default)]
+    logradouro: Option<String>,
+    #[serde(default)]
+    bairro: Option<String>,
+    #[serde(default)]
+    localidade: Option<String>,
+    #[serde(default)]
+    uf: Option<String>,
+}
+
+#[cfg(feature = "viacep")]
+#[async_trait]
+impl CepResolver for ViaCepResolver {
+    async fn resolve(&self, cep: &str) -> Result<Option<CepAddress>, AppError> {
+        let url = format!("{}/{}/json/", self.base_url, cep);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("Erro ao consultar CEP: {}", e)))?;
+
+        // CEP com formato inválido para o serviço
+        if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            return Ok(None);
+        }
+
+        let data: ViaCepResponse = response
+            .error_for_status()
+            .map_err(|e| AppError::internal(format!("Erro ao consultar CEP: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::internal(format!("Resposta inválida do serviço de CEP: {}", e)))?;
+
+        if data.erro.is_some() {
+            return Ok(None);
+        }
+
+        let non_empty = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+        match (non_empty(data.localidade), non_empty(data.uf)) {
+            (Some(city), Some(state)) => Ok(Some(CepAddress {
+                cep: cep.to_string(),
+                street: non_empty(data.logradouro),
+                neighborhood: non_empty(data.bairro),
+                city,
+                state,
+            })),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Tenta cada resolvedor em ordem, devolvendo o primeiro resultado encontrado.
+pub struct ChainResolver {
+    resolvers: Vec<Box<dyn CepResolver>>,
+}
+
+impl ChainResolver {
+    pub fn new(resolvers: Vec<Box<dyn CepResolver>>) -> Self {
+        Self { resolvers }
+    }
+}
+
+#[async_trait]
+impl CepResolver for ChainResolver {
+    async fn resolve(&self, cep: &str) -> Result<Option<CepAddress>, AppError> {
+        let mut last_err = None;
+        for resolver in &self.resolvers {
+            match resolver.resolve(cep).await {
+                Ok(Some(found)) => return Ok(Some(found)),
+                Ok(None) => {}
+                Err(e) => last_err = Some(e),
+            }
+        }
+        match last_err {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Resolvedor usado pelo app: tabela local primeiro e, se habilitado, o ViaCEP.
+pub fn default_resolver(db: SharedConnection) -> Box<dyn CepResolver> {
+    #[allow(unused_mut)]
+    let mut resolvers: Vec<Box<dyn CepResolver>> = vec![Box::new(LocalCepResolver::new(db))];
+    #[cfg(feature = "viacep")]
+    resolvers.push(Box::new(ViaCepResolver::default()));
+    Box::new(ChainResolver::new(resolvers))
+}
+
+/// Valida o CEP e consulta o endereço no resolvedor.
+pub async fn lookup(resolver: &dyn CepResolver, cep: &str) -> Result<CepAddress, AppError> {
+    let cep = normalize_cep(cep).map_err(|e| e.with_field("cep"))?;
+    resolver
+        .resolve(&cep)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("CEP {} não encontrado", format_cep(&cep))))
+}
+
+/// Importa a tabela de CEPs a partir de um arquivo (veja [`import_cep_table`]).
+pub async fn import_cep_file(db: &DatabaseConnection, path: &str) -> Result<usize, AppError> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| AppError::internal(format!("Erro ao abrir arquivo {}: {}", path, e)))?;
+    import_cep_table(db, file).await
+}
+
+/// Importa uma tabela de CEPs em CSV com as colunas
+/// `cep;logradouro;bairro;cidade;uf` (separador `;` ou `,`, cabeçalho opcional).
+/// CEPs já existentes são atualizados. Devolve a quantidade de linhas importadas.
+pub async fn import_cep_table<R: std::io::Read>(
+    db: &DatabaseConnection,
+    mut reader: R,
+) -> Result<usize, AppError> {
+    use sea_orm::sea_query::OnConflict;
+    use sea_orm::Set;
+
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| AppError::internal(format!("Erro ao ler arquivo de CEPs: {}", e)))?;
+
+    // Bases exportadas em Latin-1 também são aceitas
+    let content = match String::from_utf8(bytes) {
+        Ok(text) => text.trim_start_matches('\u{feff}').to_string(),
+        Err(e) => e.into_bytes().iter().map(|&b| b as char).collect(),
+    };
+
+    let first_line = content.lines().next().unwrap_or_default();
+    let delimiter = if first_line.contains(';') { b';' } else { b',' };
+
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut rows = Vec::new();
+    for (index, record) in csv_reader.records().enumerate() {
+        let record = record.map_err(|e| AppError::validation(format!("Linha {}: {}", index + 1, e)))?;
+        let field = |i: usize| record.get(i).map(str::trim).unwrap_or_default().to_string();
+
+        // Cabeçalho opcional na primeira linha
+        if index == 0 && !field(0).chars().any(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if record.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+
+        let cep = normalize_cep(&field(0)).map_err(|e| AppError::validation(format!("Linha {}: {}", index + 1, e)))?;
+        let state = normalize_uf(&field(4)).map_err(|e| AppError::validation(format!("Linha {}: {}", index + 1, e)))?;
+        let city = field(3);
+        if city.is_empty() {
+            return Err(AppError::validation(format!("Linha {}: cidade não informada", index + 1)));
+        }
+        let optional = |v: String| if v.is_empty() { None } else { Some(v) };
+
+        rows.push(cep::ActiveModel {
+            cep: Set(cep),
+            street: Set(optional(field(1))),
+            neighborhood: Set(optional(field(2))),
+            city: Set(city),
+            state: Set(state),
+        });
+    }
+
+    let total = rows.len();
+    let txn = db.begin().await?;
+    for chunk in rows.chunks(500) {
+        cep::Entity::insert_many(chunk.to_vec())
+            .on_conflict(
+                OnConflict::column(cep::Column::Cep)
+                    .update_columns([
+                        cep::Column::Street,
+                        cep::Column::Neighborhood,
+                        cep::Column::City,
+                        cep::Column::State,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    Ok(total)
+}
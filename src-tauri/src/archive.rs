@@ -0,0 +1,345 @@
+//! Pacote de migração entre computadores: um zip com um JSON por tabela e
+//! um `manifest.json` com as versões do pacote e do esquema.
+//!
+//! Diferente do backup (cópia do arquivo SQLite), o pacote não depende do
+//! formato do banco nem da senha do SQLCipher. A importação recria os dados
+//! com os mesmos ids em uma instalação nova, sem clientes, produtos,
+//! fornecedores nem vendas; os cadastros semeados (usuários, categorias, formas de pagamento
+//! e de envio) são substituídos pelos do pacote.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Cursor, Read, Write};
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, PaginatorTrait,
+    TransactionTrait,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use ts_rs::TS;
+use zip::write::SimpleFileOptions;
+
+use crate::db::entities::{
+    audit_log, category, cep, client, client_gallon, payment_method, product, purchase_order, purchase_order_item, sale,
+    setting, shipping_method, stock_movement, supplier, user,
+};
+use crate::audit;
+use crate::db::{self, SCHEMA_VERSION};
+use crate::error::AppError;
+use crate::spreadsheet::ExportedFile;
+use crate::timezone;
+
+/// Identifica o arquivo como pacote de migração do app.
+pub const FORMAT: &str = "aquagas-archive";
+
+/// Versão do formato do pacote (estrutura do zip e do manifesto).
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Ação registrada na auditoria a cada pacote exportado.
+pub const ACTION_EXPORT: &str = "archive_export";
+
+const MANIFEST: &str = "manifest.json";
+
+/// Linhas gravadas por comando na importação, abaixo do limite de
+/// parâmetros do SQLite.
+const INSERT_CHUNK: usize = 200;
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct ArchiveManifest {
+    pub format: String,
+    pub archive_version: u32,
+    /// `db::SCHEMA_VERSION` do app que gerou o pacote.
+    pub schema_version: i32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    /// Quantidade de registros por tabela.
+    pub counts: BTreeMap<String, usize>,
+}
+
+/// Todas as tabelas do pacote. Tabelas ausentes em pacotes antigos chegam
+/// vazias.
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveData {
+    pub users: Vec<user::Model>,
+    pub categories: Vec<category::Model>,
+    pub products: Vec<product::Model>,
+    pub stock_movements: Vec<stock_movement::Model>,
+    pub suppliers: Vec<supplier::Model>,
+    pub purchase_orders: Vec<purchase_order::Model>,
+    pub purchase_order_items: Vec<purchase_order_item::Model>,
+    pub clients: Vec<client::Model>,
+    pub sales: Vec<sale::Model>,
+    pub client_gallons: Vec<client_gallon::Model>,
+    pub payment_methods: Vec<payment_method::Model>,
+    pub shipping_methods: Vec<shipping_method::Model>,
+    pub settings: Vec<setting::Model>,
+    pub ceps: Vec<cep::Model>,
+    pub audit_logs: Vec<audit_log::Model>,
+}
+
+/// Lê todas as tabelas em uma única transação, para o pacote não pegar uma
+/// venda sem as movimentações de estoque dela.
+pub async fn read_data(db: &DatabaseConnection) -> Result<ArchiveData, AppError> {
+    let txn = db.begin().await?;
+    let db = &txn;
+    let data = ArchiveData {
+        users: user::Entity::find().all(db).await?,
+        categories: category::Entity::find().all(db).await?,
+        products: product::Entity::find().all(db).await?,
+        stock_movements: stock_movement::Entity::find().all(db).await?,
+        suppliers: supplier::Entity::find().all(db).await?,
+        purchase_orders: purchase_order::Entity::find().all(db).await?,
+        purchase_order_items: purchase_order_item::Entity::find().all(db).await?,
+        clients: client::Entity::find().all(db).await?,
+        sales: sale::Entity::find().all(db).await?,
+        client_gallons: client_gallon::Entity::find().all(db).await?,
+        payment_methods: payment_method::Entity::find().all(db).await?,
+        shipping_methods: shipping_method::Entity::find().all(db).await?,
+        settings: setting::Entity::find().all(db).await?,
+        ceps: cep::Entity::find().all(db).await?,
+        audit_logs: audit_log::Entity::find().all(db).await?,
+    };
+    txn.commit().await?;
+    Ok(data)
+}
+
+/// Gera o pacote com todos os dados do banco.
+pub async fn export(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<Vec<u8>, AppError> {
+    let data = read_data(db).await?;
+
+    let mut files: Vec<(&str, Vec<u8>)> = Vec::new();
+    let mut counts = BTreeMap::new();
+    let mut table = |name: &'static str, len: usize, json: serde_json::Result<Vec<u8>>| -> Result<(), AppError> {
+        counts.insert(name.to_string(), len);
+        files.push((name, json.map_err(json_error)?));
+        Ok(())
+    };
+    table("users", data.users.len(), serde_json::to_vec(&data.users))?;
+    table("categories", data.categories.len(), serde_json::to_vec(&data.categories))?;
+    table("products", data.products.len(), serde_json::to_vec(&data.products))?;
+    table("stock_movements", data.stock_movements.len(), serde_json::to_vec(&data.stock_movements))?;
+    table("suppliers", data.suppliers.len(), serde_json::to_vec(&data.suppliers))?;
+    table("purchase_orders", data.purchase_orders.len(), serde_json::to_vec(&data.purchase_orders))?;
+    table("purchase_order_items", data.purchase_order_items.len(), serde_json::to_vec(&data.purchase_order_items))?;
+    table("clients", data.clients.len(), serde_json::to_vec(&data.clients))?;
+    table("sales", data.sales.len(), serde_json::to_vec(&data.sales))?;
+    table("client_gallons", data.client_gallons.len(), serde_json::to_vec(&data.client_gallons))?;
+    table("payment_methods", data.payment_methods.len(), serde_json::to_vec(&data.payment_methods))?;
+    table("shipping_methods", data.shipping_methods.len(), serde_json::to_vec(&data.shipping_methods))?;
+    table("settings", data.settings.len(), serde_json::to_vec(&data.settings))?;
+    table("ceps", data.ceps.len(), serde_json::to_vec(&data.ceps))?;
+    table("audit_logs", data.audit_logs.len(), serde_json::to_vec(&data.audit_logs))?;
+
+    let manifest = ArchiveManifest {
+        format: FORMAT.to_string(),
+        archive_version: ARCHIVE_VERSION,
+        schema_version: SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: now,
+        counts,
+    };
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    zip.start_file(MANIFEST, options).map_err(zip_error)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(json_error)?)?;
+    for (name, json) in files {
+        zip.start_file(format!("{}.json", name), options).map_err(zip_error)?;
+        zip.write_all(&json)?;
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Pacote pronto para o frontend salvar, ex.: `aquagas-2026-10-19.zip`.
+/// Gera o pacote para download. A exportação fica registrada na auditoria
+/// (e no próprio pacote) com o usuário responsável.
+pub async fn export_file(db: &DatabaseConnection, username: &str) -> Result<ExportedFile, AppError> {
+    let now = Utc::now();
+    let tz = timezone::business_timezone(db).await;
+    let file_name = format!("aquagas-{}.zip", timezone::local_date(tz, now).format("%Y-%m-%d"));
+    audit::record(db, ACTION_EXPORT, audit::DATABASE, 0, username, Some(json!({ "file_name": file_name }))).await?;
+    let bytes = export(db, now).await?;
+    Ok(ExportedFile {
+        file_name,
+        content: general_purpose::STANDARD.encode(bytes),
+    })
+}
+
+/// Lê e confere o pacote: formato, versões e ligações entre as tabelas.
+pub fn read(bytes: &[u8]) -> Result<(ArchiveManifest, ArchiveData), AppError> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|_| AppError::validation("O arquivo não é um pacote de migração válido"))?;
+
+    let manifest: ArchiveManifest = read_json(&mut zip, MANIFEST)?
+        .ok_or_else(|| AppError::validation("O arquivo não é um pacote de migração válido"))?;
+    if manifest.format != FORMAT {
+        return Err(AppError::validation("O arquivo não é um pacote de migração válido"));
+    }
+    if manifest.archive_version > ARCHIVE_VERSION || manifest.schema_version > SCHEMA_VERSION {
+        return Err(AppError::validation(format!(
+            "O pacote foi gerado por uma versão mais nova do sistema ({}). Atualize o sistema antes de importar",
+            manifest.app_version
+        )));
+    }
+
+    let zip = &mut zip;
+    let data = ArchiveData {
+        users: read_table(zip, "users")?,
+        categories: read_table(zip, "categories")?,
+        products: read_table(zip, "products")?,
+        stock_movements: read_table(zip, "stock_movements")?,
+        suppliers: read_table(zip, "suppliers")?,
+        purchase_orders: read_table(zip, "purchase_orders")?,
+        purchase_order_items: read_table(zip, "purchase_order_items")?,
+        clients: read_table(zip, "clients")?,
+        sales: read_table(zip, "sales")?,
+        client_gallons: read_table(zip, "client_gallons")?,
+        payment_methods: read_table(zip, "payment_methods")?,
+        shipping_methods: read_table(zip, "shipping_methods")?,
+        settings: read_table(zip, "settings")?,
+        ceps: read_table(zip, "ceps")?,
+        audit_logs: read_table(zip, "audit_logs")?,
+    };
+    check_relations(&data)?;
+    Ok((manifest, data))
+}
+
+/// Recria os dados do pacote no banco, em uma única transação. O banco
+/// precisa estar vazio (sem clientes, produtos, fornecedores, vendas nem
+/// galões).
+pub async fn import(db: &DatabaseConnection, bytes: &[u8]) -> Result<ArchiveManifest, AppError> {
+    let (manifest, data) = read(bytes)?;
+
+    let txn = db.begin().await?;
+    let in_use = client::Entity::find().count(&txn).await?
+        + product::Entity::find().count(&txn).await?
+        + sale::Entity::find().count(&txn).await?
+        + client_gallon::Entity::find().count(&txn).await?
+        + supplier::Entity::find().count(&txn).await?;
+    if in_use > 0 {
+        return Err(AppError::conflict(
+            "O banco já tem cadastros. A importação só pode ser feita em uma instalação nova",
+        ));
+    }
+    if !data.users.iter().any(|u| u.role == "admin") {
+        return Err(AppError::validation("O pacote não tem nenhum usuário administrador"));
+    }
+
+    // Sementes da instalação nova, substituídas pelas do pacote
+    user::Entity::delete_many().exec(&txn).await?;
+    category::Entity::delete_many().exec(&txn).await?;
+    payment_method::Entity::delete_many().exec(&txn).await?;
+    shipping_method::Entity::delete_many().exec(&txn).await?;
+    setting::Entity::delete_many().exec(&txn).await?;
+    cep::Entity::delete_many().exec(&txn).await?;
+    audit_log::Entity::delete_many().exec(&txn).await?;
+
+    insert_all(&txn, data.users).await?;
+    insert_all(&txn, data.categories).await?;
+    insert_all(&txn, data.products).await?;
+    insert_all(&txn, data.stock_movements).await?;
+    insert_all(&txn, data.suppliers).await?;
+    insert_all(&txn, data.purchase_orders).await?;
+    insert_all(&txn, data.purchase_order_items).await?;
+    insert_all(&txn, data.clients).await?;
+    insert_all(&txn, data.sales).await?;
+    insert_all(&txn, data.client_gallons).await?;
+    insert_all(&txn, data.payment_methods).await?;
+    insert_all(&txn, data.shipping_methods).await?;
+    insert_all(&txn, data.settings).await?;
+    insert_all(&txn, data.ceps).await?;
+    insert_all(&txn, data.audit_logs).await?;
+    txn.commit().await?;
+
+    // Pacotes de versões anteriores passam pelas migrações de novo
+    if manifest.schema_version < SCHEMA_VERSION {
+        db::setup_schema(db)
+            .await
+            .map_err(|e| AppError::internal(format!("Erro ao atualizar o esquema: {:#}", e)))?;
+    }
+    log::info!("Pacote de migração importado ({:?})", manifest.counts);
+    Ok(manifest)
+}
+
+/// Grava os registros com os ids originais.
+async fn insert_all<C, M, A>(db: &C, models: Vec<M>) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+    M: IntoActiveModel<A>,
+    A: ActiveModelTrait,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    let mut models = models.into_iter().map(IntoActiveModel::into_active_model).peekable();
+    while models.peek().is_some() {
+        let chunk: Vec<A> = models.by_ref().take(INSERT_CHUNK).collect();
+        A::Entity::insert_many(chunk).exec_without_returning(db).await?;
+    }
+    Ok(())
+}
+
+/// Garante que vendas, galões, produtos, movimentações de estoque e pedidos
+/// de compra apontam para registros do pacote.
+fn check_relations(data: &ArchiveData) -> Result<(), AppError> {
+    let clients: HashSet<i32> = data.clients.iter().map(|c| c.id).collect();
+    let categories: HashSet<i32> = data.categories.iter().map(|c| c.id).collect();
+    let broken = |what: String| AppError::validation(format!("Pacote inconsistente: {}", what));
+
+    if let Some(s) = data.sales.iter().find(|s| !clients.contains(&s.client_id)) {
+        return Err(broken(format!("a venda {} é de um cliente que não está no pacote", s.id)));
+    }
+    if let Some(g) = data.client_gallons.iter().find(|g| !clients.contains(&g.client_id)) {
+        return Err(broken(format!("o galão {} é de um cliente que não está no pacote", g.id)));
+    }
+    if let Some(p) = data
+        .products
+        .iter()
+        .find(|p| p.category_id.is_some_and(|id| !categories.contains(&id)))
+    {
+        return Err(broken(format!("o produto {} é de uma categoria que não está no pacote", p.id)));
+    }
+
+    let suppliers: HashSet<i32> = data.suppliers.iter().map(|s| s.id).collect();
+    let orders: HashSet<i32> = data.purchase_orders.iter().map(|o| o.id).collect();
+    let products: HashSet<i32> = data.products.iter().map(|p| p.id).collect();
+    if let Some(m) = data.stock_movements.iter().find(|m| !products.contains(&m.product_id)) {
+        return Err(broken(format!("a movimentação de estoque {} é de um produto que não está no pacote", m.id)));
+    }
+    if let Some(o) = data.purchase_orders.iter().find(|o| !suppliers.contains(&o.supplier_id)) {
+        return Err(broken(format!("o pedido de compra {} é de um fornecedor que não está no pacote", o.id)));
+    }
+    if let Some(i) = data.purchase_order_items.iter().find(|i| !orders.contains(&i.purchase_order_id)) {
+        return Err(broken(format!("o item {} é de um pedido de compra que não está no pacote", i.id)));
+    }
+    if let Some(i) = data.purchase_order_items.iter().find(|i| !products.contains(&i.product_id)) {
+        return Err(broken(format!("o item {} de pedido de compra é de um produto que não está no pacote", i.id)));
+    }
+    Ok(())
+}
+
+fn read_table<T: DeserializeOwned>(zip: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Vec<T>, AppError> {
+    Ok(read_json(zip, &format!("{}.json", name))?.unwrap_or_default())
+}
+
+fn read_json<T: DeserializeOwned>(zip: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Option<T>, AppError> {
+    let mut file = match zip.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(AppError::validation(format!("Não foi possível ler {}: {}", name, e))),
+    };
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| AppError::validation(format!("Conteúdo inválido em {}: {}", name, e)))
+}
+
+fn json_error(e: serde_json::Error) -> AppError {
+    AppError::internal(format!("Erro ao gerar o pacote: {}", e))
+}
+
+fn zip_error(e: zip::result::ZipError) -> AppError {
+    AppError::internal(format!("Erro ao gerar o pacote: {}", e))
+}
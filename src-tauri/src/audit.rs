@@ -0,0 +1,60 @@
+//! Trilha de auditoria das operações sensíveis, como as solicitações de
+//! titulares de dados (LGPD).
+
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+use crate::db::entities::audit_log;
+use crate::error::AppError;
+
+/// Valor de `entity` para registros de clientes.
+pub const CLIENT: &str = "client";
+
+/// Valor de `entity` para pedidos de compra.
+pub const PURCHASE_ORDER: &str = "purchase_order";
+
+/// Valor de `entity` para operações sobre o banco inteiro (`entity_id` 0),
+/// como a exportação do pacote de migração.
+pub const DATABASE: &str = "database";
+
+/// Registra a operação. Aceita uma transação para que o registro só fique
+/// gravado junto com a alteração auditada.
+pub async fn record<C: ConnectionTrait>(
+    db: &C,
+    action: &str,
+    entity: &str,
+    entity_id: i32,
+    username: &str,
+    details: Option<serde_json::Value>,
+) -> Result<audit_log::Model, AppError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(AppError::validation("Usuário responsável não informado").with_field("username"));
+    }
+
+    let entry = audit_log::ActiveModel {
+        action: Set(action.to_string()),
+        entity: Set(entity.to_string()),
+        entity_id: Set(entity_id),
+        username: Set(username.to_string()),
+        details: Set(details.map(|d| d.to_string())),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    };
+    entry.insert(db).await.map_err(AppError::from)
+}
+
+/// Registros de um item, do mais recente para o mais antigo.
+pub async fn list_for<C: ConnectionTrait>(
+    db: &C,
+    entity: &str,
+    entity_id: i32,
+) -> Result<Vec<audit_log::Model>, AppError> {
+    audit_log::Entity::find()
+        .filter(audit_log::Column::Entity.eq(entity))
+        .filter(audit_log::Column::EntityId.eq(entity_id))
+        .order_by_desc(audit_log::Column::Id)
+        .all(db)
+        .await
+        .map_err(AppError::from)
+}
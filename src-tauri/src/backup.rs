@@ -0,0 +1,537 @@
+//! Backups do banco na pasta `backups` dos dados do app.
+//!
+//! As cópias são feitas com `VACUUM INTO` (consistente mesmo com o app em
+//! uso) e conferidas com `PRAGMA integrity_check` antes de receberem o nome
+//! definitivo. Backups automáticos rodam periodicamente e ao fechar o app;
+//! a rotação mantém o mais recente de cada um dos últimos N dias e de cada
+//! uma das últimas M semanas. Backups manuais não são apagados.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, Statement};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use ts_rs::TS;
+
+use crate::db::entities::setting;
+use crate::db::{self, SharedConnection};
+use crate::error::AppError;
+use crate::{startup, timezone};
+
+pub const SETTING_INTERVAL_HOURS: &str = "backup_interval_hours";
+pub const SETTING_KEEP_DAILY: &str = "backup_keep_daily";
+pub const SETTING_KEEP_WEEKLY: &str = "backup_keep_weekly";
+
+/// Frequência com que o agendador confere se já é hora de um novo backup.
+pub const CHECK_EVERY: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+const PREFIX: &str = "aquagas";
+const NAME_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupKind {
+    /// Agendado ou feito ao fechar o app; sujeito à rotação.
+    Auto,
+    /// Pedido pelo usuário; nunca apagado automaticamente.
+    Manual,
+    /// Cópia do banco substituído ao restaurar um backup; fora da rotação.
+    Safety,
+}
+
+impl BackupKind {
+    fn label(self) -> &'static str {
+        match self {
+            BackupKind::Auto => "auto",
+            BackupKind::Manual => "manual",
+            BackupKind::Safety => "safety",
+        }
+    }
+}
+
+/// Arquivo de backup encontrado na pasta.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct BackupFile {
+    pub name: String,
+    /// `null` para arquivos `.db` copiados para a pasta manualmente.
+    pub kind: Option<BackupKind>,
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub size: u64,
+}
+
+/// Backup restaurado por [`restore`].
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct RestoredBackup {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Versão do esquema do backup, antes das migrações aplicadas ao abrir.
+    pub schema_version: i32,
+    /// Cópia do banco que foi substituído.
+    pub safety_backup: BackupFile,
+}
+
+/// Tabelas que um arquivo precisa ter para ser um banco deste app.
+const REQUIRED_TABLES: [&str; 4] = ["clients", "products", "sales", "users"];
+
+/// Quantos backups automáticos manter e de quanto em quanto tempo criá-los.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub interval_hours: u32,
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule {
+            interval_hours: 24,
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+/// Estado gerenciado: pasta dos backups e trava que impede dois backups
+/// simultâneos (agendado, manual e ao fechar).
+pub struct Backups {
+    pub dir: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl Backups {
+    pub fn new(dir: PathBuf) -> Self {
+        Backups {
+            dir,
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub async fn create(&self, db: &SharedConnection, kind: BackupKind) -> Result<BackupFile, AppError> {
+        let session = db.session().await;
+        let _guard = self.lock.lock().await;
+        create_backup(&session.connection, &self.dir, kind, Utc::now(), session.key.as_deref()).await
+    }
+
+    /// Backup automático se o último tiver mais de `interval_hours`, seguido
+    /// da rotação. Devolve o backup criado, se houve.
+    pub async fn run_scheduled(&self, db: &SharedConnection) -> Result<Option<BackupFile>, AppError> {
+        let session = db.session().await;
+        let _guard = self.lock.lock().await;
+        run_scheduled(&session.connection, &self.dir, Utc::now(), session.key.as_deref()).await
+    }
+
+    /// Restaura o backup `name` no lugar do banco `database` (veja
+    /// [`restore`]), com acesso exclusivo à conexão gerenciada.
+    pub async fn restore(
+        &self,
+        db: &SharedConnection,
+        database: &Path,
+        name: &str,
+    ) -> Result<RestoredBackup, AppError> {
+        // Sempre a conexão antes da trava dos backups, como nos demais
+        let mut session = db.write().await;
+        let _guard = self.lock.lock().await;
+        let key = session.key.clone();
+        restore(&mut session.connection, database, &self.dir, name, key.as_deref()).await
+    }
+
+    /// Trava para operações que reescrevem a pasta de backups por fora
+    /// destes métodos (ex.: [`crate::encryption`]).
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    /// Backup automático ao fechar o app, sempre feito.
+    pub async fn on_exit(&self, db: &SharedConnection) -> Result<BackupFile, AppError> {
+        let session = db.session().await;
+        let _guard = self.lock.lock().await;
+        let conn = &session.connection;
+        let file = create_backup(conn, &self.dir, BackupKind::Auto, Utc::now(), session.key.as_deref()).await?;
+        rotate(&self.dir, &load_schedule(conn).await, timezone::business_timezone(conn).await)?;
+        Ok(file)
+    }
+}
+
+fn parse_setting(value: &str, key: &str) -> Result<u32, AppError> {
+    value
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|v| *v > 0)
+        .ok_or_else(|| AppError::validation(format!("Valor inválido para {}: informe um número inteiro positivo", key)))
+}
+
+/// Valida as configurações de backup em `save_setting`.
+pub fn validate_setting(key: &str, value: &str) -> Result<Option<String>, AppError> {
+    match key {
+        SETTING_INTERVAL_HOURS | SETTING_KEEP_DAILY | SETTING_KEEP_WEEKLY => {
+            Ok(Some(parse_setting(value, key)?.to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub async fn load_schedule(db: &DatabaseConnection) -> Schedule {
+    let mut schedule = Schedule::default();
+    let settings = match setting::Entity::find().all(db).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            log::warn!("Erro ao ler configurações de backup: {}", e);
+            return schedule;
+        }
+    };
+    for s in settings {
+        let target = match s.key.as_str() {
+            SETTING_INTERVAL_HOURS => &mut schedule.interval_hours,
+            SETTING_KEEP_DAILY => &mut schedule.keep_daily,
+            SETTING_KEEP_WEEKLY => &mut schedule.keep_weekly,
+            _ => continue,
+        };
+        match parse_setting(&s.value, &s.key) {
+            Ok(v) => *target = v,
+            Err(e) => log::warn!("{}; usando o padrão", e),
+        }
+    }
+    schedule
+}
+
+fn file_name(kind: BackupKind, at: DateTime<Utc>) -> String {
+    format!("{}-{}-{}.db", PREFIX, kind.label(), at.format(NAME_TIME_FORMAT))
+}
+
+/// Tipo e data de um nome gerado por [`file_name`].
+fn parse_name(name: &str) -> Option<(BackupKind, DateTime<Utc>)> {
+    let rest = name.strip_prefix(PREFIX)?.strip_prefix('-')?.strip_suffix(".db")?;
+    let (label, time) = rest.split_once('-')?;
+    let kind = [BackupKind::Auto, BackupKind::Manual, BackupKind::Safety]
+        .into_iter()
+        .find(|k| k.label() == label)?;
+    let at = NaiveDateTime::parse_from_str(time, NAME_TIME_FORMAT).ok()?.and_utc();
+    Some((kind, at))
+}
+
+/// Backups (`*.db`) da pasta, do mais recente para o mais antigo.
+pub fn list_backups(dir: &Path) -> Vec<BackupFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<BackupFile> = entries
+        .filter_map(Result::ok)
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "db"))
+        .filter_map(|e| {
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            let name = e.file_name().to_string_lossy().into_owned();
+            let (kind, created_at) = match parse_name(&name) {
+                Some((kind, at)) => (Some(kind), at),
+                None => (None, meta.modified().ok()?.into()),
+            };
+            Some(BackupFile { name, kind, created_at, size: meta.len() })
+        })
+        .collect();
+    files.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.name.cmp(&a.name)));
+    files
+}
+
+/// `PRAGMA integrity_check` no arquivo, aberto somente para leitura com a
+/// senha `key` se o arquivo for cifrado.
+pub async fn verify(path: &Path, key: Option<&str>) -> Result<(), AppError> {
+    let rows = async {
+        let db = db::connect(path, "ro", key).await?;
+        let rows = db
+            .query_all(Statement::from_string(
+                db.get_database_backend(),
+                "PRAGMA integrity_check".to_string(),
+            ))
+            .await;
+        let _ = db.close().await;
+        rows
+    }
+    .await
+    .map_err(|e| AppError::internal(format!("Não foi possível ler o backup {}: {}", path.display(), e)))?;
+
+    let messages: Vec<String> = rows
+        .iter()
+        .filter_map(|r| r.try_get_by_index::<String>(0).ok())
+        .collect();
+    if messages != ["ok"] {
+        return Err(AppError::internal(format!(
+            "Backup {} falhou na verificação de integridade: {}",
+            path.display(),
+            messages.join("; ")
+        )));
+    }
+    Ok(())
+}
+
+/// Confere se o arquivo pode ser restaurado: íntegro, com as tabelas do app
+/// e de uma versão do esquema que este app sabe abrir. Devolve a versão.
+pub async fn inspect(path: &Path, key: Option<&str>) -> Result<i32, AppError> {
+    verify(path, key).await?;
+
+    let (version, tables) = async {
+        let conn = db::connect(path, "ro", key).await?;
+        let result = async {
+            let version = db::schema_version(&conn).await?;
+            let rows = conn
+                .query_all(Statement::from_string(
+                    conn.get_database_backend(),
+                    "SELECT name FROM sqlite_master WHERE type = 'table'".to_string(),
+                ))
+                .await?;
+            let tables: HashSet<String> = rows
+                .iter()
+                .filter_map(|r| r.try_get_by_index::<String>(0).ok())
+                .collect();
+            Ok::<_, sea_orm::DbErr>((version, tables))
+        }
+        .await;
+        let _ = conn.close().await;
+        result
+    }
+    .await
+    .map_err(|e| AppError::internal(format!("Não foi possível ler o backup {}: {}", path.display(), e)))?;
+
+    let missing: Vec<&str> = REQUIRED_TABLES
+        .into_iter()
+        .filter(|t| !tables.contains(*t))
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::validation(format!(
+            "O arquivo {} não é um backup do sistema (faltam as tabelas {})",
+            path.display(),
+            missing.join(", ")
+        )));
+    }
+    if version > db::SCHEMA_VERSION {
+        return Err(AppError::validation(format!(
+            "O backup é da versão {} do banco, mais nova que a suportada por este sistema ({}). Atualize o sistema antes de restaurar",
+            version,
+            db::SCHEMA_VERSION
+        )));
+    }
+    Ok(version)
+}
+
+/// Confere se o backup `name` (em `path`) pode substituir o banco: cifrado
+/// só com o banco cifrado (`key`), e aprovado por [`inspect`] com a mesma
+/// senha. Devolve a versão do esquema.
+pub async fn check_restore(path: &Path, name: &str, key: Option<&str>) -> Result<i32, AppError> {
+    match (db::is_encrypted(path), key) {
+        (true, None) => {
+            return Err(AppError::validation(format!(
+                "O backup {} é criptografado e o banco atual não",
+                name
+            )))
+        }
+        (false, Some(_)) => {
+            return Err(AppError::validation(format!(
+                "O backup {} não é criptografado; com a criptografia ativa só é possível restaurar backups criptografados",
+                name
+            )))
+        }
+        _ => {}
+    }
+    inspect(path, key).await
+}
+
+/// Copia o banco para a pasta e confere a cópia. Um arquivo temporário só
+/// recebe o nome final depois de verificado. Num banco cifrado a cópia sai
+/// cifrada com a mesma senha `key`.
+pub async fn create_backup(
+    db: &DatabaseConnection,
+    dir: &Path,
+    kind: BackupKind,
+    now: DateTime<Utc>,
+    key: Option<&str>,
+) -> Result<BackupFile, AppError> {
+    fs::create_dir_all(dir)?;
+
+    let name = file_name(kind, now);
+    let target = dir.join(&name);
+    let temp = dir.join(format!("{}.tmp", name));
+    let _ = fs::remove_file(&temp);
+
+    let quoted = temp.display().to_string().replace('\'', "''");
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        format!("VACUUM INTO '{}'", quoted),
+    ))
+    .await?;
+
+    if let Err(e) = verify(&temp, key).await {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    fs::rename(&temp, &target)?;
+
+    log::info!("Backup criado: {}", target.display());
+    Ok(BackupFile {
+        name,
+        kind: Some(kind),
+        created_at: now,
+        size: fs::metadata(&target)?.len(),
+    })
+}
+
+/// Apaga os backups automáticos fora da retenção: fica o mais recente de
+/// cada um dos últimos `keep_daily` dias e de cada uma das últimas
+/// `keep_weekly` semanas (dias e semanas no fuso do negócio). Devolve os
+/// arquivos removidos.
+pub fn rotate(dir: &Path, schedule: &Schedule, tz: Tz) -> Result<Vec<String>, AppError> {
+    // Do mais recente para o mais antigo
+    let auto: Vec<BackupFile> = list_backups(dir)
+        .into_iter()
+        .filter(|f| f.kind == Some(BackupKind::Auto))
+        .collect();
+
+    let mut keep = HashSet::new();
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    for file in &auto {
+        let date = timezone::local_date(tz, file.created_at);
+        let week = date.iso_week();
+        if days.len() < schedule.keep_daily as usize && days.insert(date) {
+            keep.insert(file.name.clone());
+        }
+        if weeks.len() < schedule.keep_weekly as usize && weeks.insert((week.year(), week.week())) {
+            keep.insert(file.name.clone());
+        }
+    }
+
+    let mut removed = Vec::new();
+    for file in auto.into_iter().filter(|f| !keep.contains(&f.name)) {
+        fs::remove_file(dir.join(&file.name))?;
+        removed.push(file.name);
+    }
+    Ok(removed)
+}
+
+/// Cria o backup automático se o último tiver mais de `interval_hours` e
+/// aplica a rotação.
+pub async fn run_scheduled(
+    db: &DatabaseConnection,
+    dir: &Path,
+    now: DateTime<Utc>,
+    key: Option<&str>,
+) -> Result<Option<BackupFile>, AppError> {
+    let schedule = load_schedule(db).await;
+    let last_auto = list_backups(dir)
+        .into_iter()
+        .find(|f| f.kind == Some(BackupKind::Auto))
+        .map(|f| f.created_at);
+
+    let due = match last_auto {
+        Some(at) => now - at >= Duration::hours(schedule.interval_hours as i64),
+        None => true,
+    };
+    if !due {
+        return Ok(None);
+    }
+
+    let file = create_backup(db, dir, BackupKind::Auto, now, key).await?;
+    let removed = rotate(dir, &schedule, timezone::business_timezone(db).await)?;
+    if !removed.is_empty() {
+        log::info!("Backups removidos pela rotação: {}", removed.join(", "));
+    }
+    Ok(Some(file))
+}
+
+/// Troca o banco `database`, aberto em `connection`, pelo backup `name`
+/// da pasta `dir`. Com o banco cifrado (`key`), só aceita backups cifrados
+/// com a mesma senha.
+///
+/// O backup é conferido com [`inspect`] e o banco atual é copiado como
+/// [`BackupKind::Safety`] antes da troca. A conexão é fechada, o arquivo
+/// substituído e o banco reaberto com [`db::open`], que aplica as migrações
+/// se o backup for de uma versão anterior. Se o backup não abrir, a cópia de
+/// segurança volta para o lugar.
+pub async fn restore(
+    connection: &mut DatabaseConnection,
+    database: &Path,
+    dir: &Path,
+    name: &str,
+    key: Option<&str>,
+) -> Result<RestoredBackup, AppError> {
+    let path = startup::backup_file(dir, name)?;
+    let created_at = list_backups(dir)
+        .into_iter()
+        .find(|f| f.name == name)
+        .map(|f| f.created_at)
+        .ok_or_else(|| AppError::not_found(format!("Backup {} não encontrado", name)))?;
+    let schema_version = check_restore(&path, name, key).await?;
+
+    let safety_backup = create_backup(connection, dir, BackupKind::Safety, Utc::now(), key).await?;
+    std::mem::replace(connection, DatabaseConnection::Disconnected).close().await?;
+
+    let reopened = match startup::replace_database_file(database, &path, "substituido") {
+        Ok(_) => open_restored(database, key).await,
+        Err(e) => Err(e),
+    };
+    match reopened {
+        Ok(restored) => *connection = restored,
+        Err(e) => {
+            log::error!("Falha ao abrir o backup {}: {}; voltando ao banco anterior", name, e);
+            startup::replace_database_file(database, &dir.join(&safety_backup.name), "falha")?;
+            *connection = open_restored(database, key).await?;
+            return Err(e);
+        }
+    }
+
+    log::info!(
+        "Backup {} restaurado (esquema versão {}); banco anterior em {}",
+        name,
+        schema_version,
+        safety_backup.name
+    );
+    Ok(RestoredBackup {
+        name: name.to_string(),
+        created_at,
+        schema_version,
+        safety_backup,
+    })
+}
+
+async fn open_restored(database: &Path, key: Option<&str>) -> Result<DatabaseConnection, AppError> {
+    db::open(database, key)
+        .await
+        .map_err(|e| AppError::internal(format!("{:#}", e)))
+}
+
+/// Tarefa em segundo plano que confere a cada [`CHECK_EVERY`] se é hora
+/// de um backup automático. Requer a conexão e [`Backups`] gerenciados.
+pub fn spawn_scheduler(handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(CHECK_EVERY);
+        loop {
+            ticker.tick().await;
+            let (Some(db), Some(backups)) = (
+                handle.try_state::<SharedConnection>(),
+                handle.try_state::<Backups>(),
+            ) else {
+                continue;
+            };
+            if let Err(e) = backups.run_scheduled(&db).await {
+                log::error!("Falha no backup automático: {}", e);
+            }
+        }
+    });
+}
+
+/// Backup ao fechar o app (apenas se o banco chegou a abrir).
+pub fn backup_on_exit(handle: &AppHandle) {
+    let (Some(db), Some(backups)) = (
+        handle.try_state::<SharedConnection>(),
+        handle.try_state::<Backups>(),
+    ) else {
+        return;
+    };
+    if let Err(e) = tauri::async_runtime::block_on(backups.on_exit(&db)) {
+        log::error!("Falha no backup ao fechar: {}", e);
+    }
+}
@@ -0,0 +1,103 @@
+//! Tipos TypeScript das respostas dos comandos, gerados das structs Rust
+//! com ts-rs. O resultado fica versionado em `src/lib/bindings.ts` no
+//! frontend e o teste `tests/bindings.rs` falha se estiver desatualizado.
+//!
+//! Para regenerar: `UPDATE_BINDINGS=1 cargo test --test bindings`.
+
+use ts_rs::TS;
+
+use crate::db::entities::{
+    audit_log, category, client, client_gallon, payment_method, product, purchase_order, purchase_order_item, sale,
+    setting, shipping_method, stock_movement, supplier, user,
+};
+use crate::{address, archive, backup, clients, encryption, error, imports, pagination, pix, privacy, purchases, reports, sales, search, spreadsheet, startup, stock, timezone};
+
+/// Caminho do arquivo gerado, relativo a `src-tauri`.
+pub const FILE: &str = "../src/lib/bindings.ts";
+
+macro_rules! declarations {
+    ($($ty:ty),* $(,)?) => {
+        vec![$(<$ty as TS>::decl()),*]
+    };
+}
+
+/// Conteúdo completo de `bindings.ts`.
+pub fn typescript() -> String {
+    let declarations = declarations![
+        // Tabelas
+        audit_log::Model,
+        category::Model,
+        client::Model,
+        client_gallon::Model,
+        payment_method::Model,
+        product::Model,
+        purchase_order::Model,
+        purchase_order_item::Model,
+        sale::Model,
+        setting::Model,
+        shipping_method::Model,
+        stock_movement::Model,
+        supplier::Model,
+        user::Model,
+        // Respostas dos comandos
+        address::CepAddress,
+        archive::ArchiveManifest,
+        backup::BackupFile,
+        backup::BackupKind,
+        backup::RestoredBackup,
+        clients::ClientDetails,
+        encryption::EncryptedDatabase,
+        encryption::EncryptionStatus,
+        error::ErrorBody,
+        error::ErrorCode,
+        imports::ImportIssue,
+        imports::ImportKind,
+        imports::ImportPreview,
+        imports::ImportResult,
+        imports::ImportRow,
+        pagination::Page<client::Model>,
+        pagination::SortDirection,
+        pix::PixCode,
+        privacy::ClientDataExport,
+        purchases::PurchaseItemInput,
+        purchases::PurchaseOrderDetails,
+        purchases::PurchaseOrderLine,
+        purchases::PurchaseOrderSummary,
+        purchases::PurchaseStatus,
+        purchases::ReceivedItem,
+        reports::DailyRevenue,
+        reports::DashboardStats,
+        reports::ExpiringGallon,
+        reports::Metric,
+        reports::ProfitLine,
+        reports::ProfitabilityReport,
+        reports::ReportSale,
+        reports::ReportSummary,
+        reports::SalesReport,
+        sales::RecentSale,
+        sales::SaleDetails,
+        search::SearchKind,
+        search::SearchResult,
+        spreadsheet::ExportedFile,
+        startup::StartupState,
+        startup::StartupStatus,
+        stock::ReorderSuggestion,
+        stock::StockDiscrepancy,
+        stock::StockReason,
+        timezone::Period,
+        timezone::Range,
+        serde_json::Value,
+    ];
+
+    let mut out = String::from(
+        "// Arquivo gerado por src-tauri/src/bindings.rs. Não edite manualmente.\n\n",
+    );
+    for decl in declarations {
+        out.push_str("export ");
+        out.push_str(&decl);
+        out.push_str("\n\n");
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
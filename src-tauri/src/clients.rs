@@ -0,0 +1,278 @@
+//! Cadastro e consultas de clientes.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::db::entities::{client, client_gallon, sale};
+use crate::error::AppError;
+use crate::{address, document, pagination};
+
+/// Dados do formulário de cliente.
+#[derive(Clone, Debug, Default)]
+pub struct ClientInput {
+    pub name: String,
+    pub phone: Option<String>,
+    pub document: Option<String>,
+    pub cep: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub street: Option<String>,
+    pub number: Option<String>,
+    pub complement: Option<String>,
+    pub neighborhood: Option<String>,
+    pub observations: Option<String>,
+}
+
+/// Filtros e ordenação da listagem paginada.
+#[derive(Clone, Debug, Default)]
+pub struct ClientQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    pub search: Option<String>,
+    pub sort_by: Option<String>,
+    pub sort_dir: Option<pagination::SortDirection>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+}
+
+/// Ficha do cliente com o histórico de vendas e os galões em posse.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ClientDetails {
+    pub client: client::Model,
+    pub sales: Vec<sale::Model>,
+    pub gallons: Vec<client_gallon::Model>,
+    #[ts(type = "number")]
+    pub total_orders: u64,
+}
+
+pub async fn list_clients(db: &DatabaseConnection) -> Result<Vec<client::Model>, AppError> {
+    client::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+pub async fn clients_page(
+    db: &DatabaseConnection,
+    query: ClientQuery,
+) -> Result<pagination::Page<client::Model>, AppError> {
+    use client::Column;
+
+    let mut select = client::Entity::find();
+
+    if let Some(term) = pagination::search_term(query.search) {
+        let pattern = pagination::like_pattern(&term);
+        let mut condition = Condition::any()
+            .add(Column::Name.like(pattern.clone()))
+            .add(Column::Phone.like(pattern.clone()))
+            .add(Column::Document.like(pattern));
+
+        // Documentos são gravados apenas com dígitos
+        let digits = document::normalize(&term);
+        if !digits.is_empty() {
+            condition = condition.add(Column::Document.like(pagination::like_pattern(&digits)));
+        }
+        select = select.filter(condition);
+    }
+    if let Some(city) = pagination::search_term(query.city) {
+        select = select.filter(Column::City.eq(city));
+    }
+    if let Some(state) = pagination::search_term(query.state) {
+        select = select.filter(Column::State.eq(state.to_uppercase()));
+    }
+
+    let select = pagination::apply_sort(
+        select,
+        query.sort_by.as_deref(),
+        query.sort_dir,
+        &[
+            ("name", Column::Name),
+            ("city", Column::City),
+            ("state", Column::State),
+            ("created_at", Column::CreatedAt),
+        ],
+        Column::Id,
+    )?;
+
+    pagination::fetch_page(db, select, query.page, query.page_size).await
+}
+
+/// Valida o CPF/CNPJ informado e garante que nenhum outro cliente o utilize.
+/// Devolve o documento normalizado (apenas dígitos) para ser gravado.
+async fn check_document<C: ConnectionTrait>(
+    db: &C,
+    document: Option<String>,
+    current_id: Option<i32>,
+) -> Result<Option<String>, AppError> {
+    let document = match document {
+        Some(d) if !d.trim().is_empty() => d,
+        _ => return Ok(None),
+    };
+
+    let (kind, normalized) = document::validate(&document).map_err(|e| e.with_field("document"))?;
+
+    let mut query = client::Entity::find().filter(client::Column::Document.eq(normalized.clone()));
+    if let Some(id) = current_id {
+        query = query.filter(client::Column::Id.ne(id));
+    }
+
+    if let Some(other) = query.one(db).await? {
+        let label = match kind {
+            document::DocumentKind::Cpf => "CPF",
+            document::DocumentKind::Cnpj => "CNPJ",
+        };
+        return Err(AppError::conflict(format!(
+            "{} {} já cadastrado para o cliente {}",
+            label,
+            document::format(&normalized),
+            other.name
+        ))
+        .with_field("document"));
+    }
+
+    Ok(Some(normalized))
+}
+
+/// Valida um telefone brasileiro (DDD + 8 ou 9 dígitos, com ou sem o +55)
+/// e devolve no formato do cadastro: `(27) 99999-1234`.
+pub fn normalize_phone(value: &str) -> Result<String, AppError> {
+    let digits = document::normalize(value);
+    let digits = match digits.len() {
+        12 | 13 if digits.starts_with("55") => &digits[2..],
+        _ => digits.as_str(),
+    };
+    if !matches!(digits.len(), 10 | 11) || digits.starts_with('0') || digits[2..].starts_with('0') {
+        return Err(AppError::validation(format!("Telefone inválido: {}", value.trim())).with_field("phone"));
+    }
+    let (ddd, rest) = digits.split_at(2);
+    let split = rest.len() - 4;
+    Ok(format!("({}) {}-{}", ddd, &rest[..split], &rest[split..]))
+}
+
+/// Valida CEP e UF, devolvendo o CEP apenas com dígitos e a UF em maiúsculas.
+fn normalize_location(
+    cep: Option<String>,
+    state: Option<String>,
+) -> Result<(Option<String>, Option<String>), AppError> {
+    let cep = match cep {
+        Some(c) if !c.trim().is_empty() => Some(address::normalize_cep(&c).map_err(|e| e.with_field("cep"))?),
+        _ => None,
+    };
+    let state = match state {
+        Some(s) if !s.trim().is_empty() => Some(address::normalize_uf(&s).map_err(|e| e.with_field("state"))?),
+        _ => None,
+    };
+    Ok((cep, state))
+}
+
+/// Copia o formulário validado para o registro.
+async fn apply_input<C: ConnectionTrait>(
+    db: &C,
+    model: &mut client::ActiveModel,
+    input: ClientInput,
+    current_id: Option<i32>,
+) -> Result<(), AppError> {
+    let document = check_document(db, input.document, current_id).await?;
+    let (cep, state) = normalize_location(input.cep, input.state)?;
+    let address = address::compose_address(
+        input.street.as_deref(),
+        input.number.as_deref(),
+        input.complement.as_deref(),
+        input.neighborhood.as_deref(),
+    );
+
+    model.name = Set(input.name);
+    model.phone = Set(input.phone);
+    model.document = Set(document);
+    model.cep = Set(cep);
+    model.city = Set(input.city);
+    model.state = Set(state);
+    model.street = Set(input.street);
+    model.number = Set(input.number);
+    model.complement = Set(input.complement);
+    model.neighborhood = Set(input.neighborhood);
+    model.address = Set(address);
+    model.observations = Set(input.observations);
+    Ok(())
+}
+
+pub async fn create_client<C: ConnectionTrait>(db: &C, input: ClientInput) -> Result<client::Model, AppError> {
+    let mut model = client::ActiveModel {
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    };
+    apply_input(db, &mut model, input, None).await?;
+    model.insert(db).await.map_err(AppError::from)
+}
+
+pub async fn update_client(
+    db: &DatabaseConnection,
+    id: i32,
+    input: ClientInput,
+) -> Result<client::Model, AppError> {
+    let model = client::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Cliente não encontrado"))?;
+    if model.anonymized_at.is_some() {
+        return Err(AppError::conflict("Cliente anonimizado não pode ser editado"));
+    }
+    let mut model: client::ActiveModel = model.into();
+
+    apply_input(db, &mut model, input, Some(id)).await?;
+    model.update(db).await.map_err(AppError::from)
+}
+
+pub async fn client_details(db: &DatabaseConnection, client_id: i32) -> Result<ClientDetails, AppError> {
+    let client = client::Entity::find_by_id(client_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Cliente não encontrado"))?;
+
+    let sales = sale::Entity::find()
+        .filter(sale::Column::ClientId.eq(client_id))
+        .order_by_desc(sale::Column::Id)
+        .all(db)
+        .await?;
+
+    let gallons = client_gallon::Entity::find()
+        .filter(client_gallon::Column::ClientId.eq(client_id))
+        .all(db)
+        .await?;
+
+    Ok(ClientDetails {
+        client,
+        total_orders: sales.len() as u64,
+        sales,
+        gallons,
+    })
+}
+
+/// Registra um galão em posse do cliente. A validade chega em RFC 3339.
+pub async fn add_gallon(
+    db: &DatabaseConnection,
+    client_id: i32,
+    brand: String,
+    expiration_date: &str,
+) -> Result<client_gallon::Model, AppError> {
+    let expiration = DateTime::parse_from_rfc3339(expiration_date)
+        .map_err(|e| AppError::validation(format!("Data inválida: {}", e)).with_field("expiration_date"))?
+        .with_timezone(&Utc);
+    let client = client::Entity::find_by_id(client_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Cliente não encontrado"))?;
+    if client.anonymized_at.is_some() {
+        return Err(AppError::conflict("Cliente anonimizado não pode receber galões"));
+    }
+
+    let gallon = client_gallon::ActiveModel {
+        client_id: Set(client_id),
+        brand: Set(brand),
+        expiration_date: Set(expiration),
+        ..Default::default()
+    };
+    gallon.insert(db).await.map_err(AppError::from)
+}
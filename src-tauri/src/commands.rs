@@ -0,0 +1,813 @@
+//! Comandos expostos ao frontend. Apenas extraem o estado gerenciado e
+//! repassam os argumentos aos serviços de cada domínio.
+
+use base64::{engine::general_purpose, Engine as _};
+use tauri::{AppHandle, State};
+
+use crate::db::SharedConnection;
+use crate::db::entities::{audit_log, category, client, client_gallon, payment_method, product, purchase_order, sale, setting, shipping_method, stock_movement, supplier, user};
+use crate::error::AppError;
+use crate::{address, archive, audit, backup, clients, encryption, exports, imports, pagination, pdf, pix, privacy, products, purchases, reports, sales, search as search_service, settings, spreadsheet, startup, stock, timezone, users};
+
+#[tauri::command]
+pub async fn get_startup_status(app: AppHandle) -> Result<startup::StartupStatus, AppError> {
+    startup::status(&app)
+}
+
+#[tauri::command]
+pub async fn retry_startup(app: AppHandle) -> Result<startup::StartupStatus, AppError> {
+    if !startup::is_ready(&app) {
+        startup::initialize(&app, None).await;
+    }
+    startup::status(&app)
+}
+
+/// Abre o banco cifrado com a senha digitada na tela de bloqueio.
+#[tauri::command]
+pub async fn unlock_database(app: AppHandle, passphrase: String) -> Result<startup::StartupStatus, AppError> {
+    if !startup::is_ready(&app) {
+        startup::initialize(&app, Some(&passphrase)).await;
+    }
+    startup::status(&app)
+}
+
+/// Recuperação na tela de erro ou de bloqueio: troca o arquivo do banco por
+/// um backup da pasta de dados e tenta abrir novamente. Com o banco cifrado
+/// a senha (`passphrase`) é obrigatória.
+#[tauri::command]
+pub async fn restore_startup_backup(
+    app: AppHandle,
+    name: String,
+    passphrase: Option<String>,
+) -> Result<startup::StartupStatus, AppError> {
+    if startup::is_ready(&app) {
+        return Err(AppError::conflict("O banco de dados já está aberto"));
+    }
+    let data_dir = crate::db::data_dir(&app).map_err(|e| AppError::internal(e.to_string()))?;
+    let key = passphrase.as_deref();
+
+    let database = crate::db::database_path(&data_dir);
+    if let Some(previous) = startup::restore_backup(&database, &crate::db::backup_dir(&data_dir), &name, key).await? {
+        log::warn!("Banco anterior preservado em {}", previous.display());
+    }
+    startup::initialize(&app, key).await;
+    startup::status(&app)
+}
+
+#[tauri::command]
+pub async fn open_data_folder(app: AppHandle) -> Result<(), AppError> {
+    let data_dir = crate::db::data_dir(&app).map_err(|e| AppError::internal(e.to_string()))?;
+    startup::open_folder(&data_dir)
+}
+
+#[tauri::command]
+pub async fn list_backups(backups: State<'_, backup::Backups>) -> Result<Vec<backup::BackupFile>, AppError> {
+    Ok(backup::list_backups(&backups.dir))
+}
+
+#[tauri::command]
+pub async fn create_backup_now(
+    db: State<'_, SharedConnection>,
+    backups: State<'_, backup::Backups>,
+) -> Result<backup::BackupFile, AppError> {
+    backups.create(db.inner(), backup::BackupKind::Manual).await
+}
+
+#[tauri::command]
+pub async fn get_encryption_status(db: State<'_, SharedConnection>) -> Result<encryption::EncryptionStatus, AppError> {
+    Ok(encryption::status(&*db.session().await))
+}
+
+/// Cifra o banco (e os backups) com a senha escolhida pelo administrador.
+#[tauri::command]
+pub async fn encrypt_database(
+    app: AppHandle,
+    db: State<'_, SharedConnection>,
+    backups: State<'_, backup::Backups>,
+    username: String,
+    password: String,
+    passphrase: String,
+) -> Result<encryption::EncryptedDatabase, AppError> {
+    let data_dir = crate::db::data_dir(&app).map_err(|e| AppError::internal(e.to_string()))?;
+    let mut session = db.write().await;
+    users::authorize_admin(&session.connection, &username, &password).await?;
+    let _guard = backups.lock().await;
+    encryption::encrypt_database(&mut session, &crate::db::database_path(&data_dir), &backups.dir, &passphrase).await
+}
+
+/// Restaura um backup da pasta com o app em uso; a conexão é reaberta.
+/// A senha é conferida contra o administrador do banco atual.
+#[tauri::command]
+pub async fn restore_backup(
+    app: AppHandle,
+    db: State<'_, SharedConnection>,
+    backups: State<'_, backup::Backups>,
+    name: String,
+    username: String,
+    password: String,
+) -> Result<backup::RestoredBackup, AppError> {
+    let data_dir = crate::db::data_dir(&app).map_err(|e| AppError::internal(e.to_string()))?;
+    users::authorize_admin(&*db.read().await, &username, &password).await?;
+    backups
+        .restore(db.inner(), &crate::db::database_path(&data_dir), &name)
+        .await
+}
+
+#[tauri::command]
+pub async fn login(db: State<'_, SharedConnection>, username: String, password: String) -> Result<user::Model, AppError> {
+    users::login(&*db.read().await, &username, &password).await
+}
+
+#[tauri::command]
+pub async fn get_clients(db: State<'_, SharedConnection>) -> Result<Vec<client::Model>, AppError> {
+    clients::list_clients(&*db.read().await).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_clients_page(
+    db: State<'_, SharedConnection>,
+    page: Option<u64>,
+    page_size: Option<u64>,
+    search: Option<String>,
+    sort_by: Option<String>,
+    sort_dir: Option<pagination::SortDirection>,
+    city: Option<String>,
+    state: Option<String>,
+) -> Result<pagination::Page<client::Model>, AppError> {
+    let query = clients::ClientQuery { page, page_size, search, sort_by, sort_dir, city, state };
+    clients::clients_page(&*db.read().await, query).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_client(
+    db: State<'_, SharedConnection>,
+    name: String,
+    phone: Option<String>,
+    document: Option<String>,
+    cep: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    street: Option<String>,
+    number: Option<String>,
+    complement: Option<String>,
+    neighborhood: Option<String>,
+    observations: Option<String>,
+) -> Result<client::Model, AppError> {
+    let input = clients::ClientInput {
+        name, phone, document, cep, city, state, street, number, complement, neighborhood, observations,
+    };
+    clients::create_client(&*db.read().await, input).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_client(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    name: String,
+    phone: Option<String>,
+    document: Option<String>,
+    cep: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    street: Option<String>,
+    number: Option<String>,
+    complement: Option<String>,
+    neighborhood: Option<String>,
+    observations: Option<String>,
+) -> Result<client::Model, AppError> {
+    let input = clients::ClientInput {
+        name, phone, document, cep, city, state, street, number, complement, neighborhood, observations,
+    };
+    clients::update_client(&*db.read().await, id, input).await
+}
+
+#[tauri::command]
+pub async fn get_client_details(db: State<'_, SharedConnection>, client_id: i32) -> Result<clients::ClientDetails, AppError> {
+    clients::client_details(&*db.read().await, client_id).await
+}
+
+/// Exportação LGPD em JSON; o frontend salva o arquivo.
+#[tauri::command]
+pub async fn export_client_data(
+    db: State<'_, SharedConnection>,
+    client_id: i32,
+    username: String,
+) -> Result<privacy::ClientDataExport, AppError> {
+    privacy::export_client_data(&*db.read().await, client_id, &username, "json").await
+}
+
+/// Exportação LGPD em PDF (base64).
+#[tauri::command]
+pub async fn export_client_data_pdf(
+    db: State<'_, SharedConnection>,
+    client_id: i32,
+    username: String,
+) -> Result<String, AppError> {
+    let export = privacy::export_client_data(&*db.read().await, client_id, &username, "pdf").await?;
+    let bytes = pdf::client_data(&export)?;
+    Ok(general_purpose::STANDARD.encode(bytes))
+}
+
+/// Anonimização LGPD; exige a senha de um administrador.
+#[tauri::command]
+pub async fn anonymize_client(
+    db: State<'_, SharedConnection>,
+    client_id: i32,
+    username: String,
+    password: String,
+) -> Result<client::Model, AppError> {
+    let db = db.read().await;
+    users::authorize_admin(&db, &username, &password).await?;
+    privacy::anonymize_client(&db, client_id, &username).await
+}
+
+#[tauri::command]
+pub async fn get_client_audit_log(db: State<'_, SharedConnection>, client_id: i32) -> Result<Vec<audit_log::Model>, AppError> {
+    audit::list_for(&*db.read().await, audit::CLIENT, client_id).await
+}
+
+#[tauri::command]
+pub async fn add_client_gallon(
+    db: State<'_, SharedConnection>,
+    client_id: i32,
+    brand: String,
+    expiration_date: String,
+) -> Result<client_gallon::Model, AppError> {
+    clients::add_gallon(&*db.read().await, client_id, brand, &expiration_date).await
+}
+
+#[tauri::command]
+pub async fn lookup_cep(
+    resolver: State<'_, Box<dyn address::CepResolver>>,
+    cep: String,
+) -> Result<address::CepAddress, AppError> {
+    address::lookup(resolver.inner().as_ref(), &cep).await
+}
+
+#[tauri::command]
+pub async fn import_cep_table(db: State<'_, SharedConnection>, path: String) -> Result<usize, AppError> {
+    address::import_cep_file(&*db.read().await, &path).await
+}
+
+#[tauri::command]
+pub async fn search(
+    db: State<'_, SharedConnection>,
+    query: String,
+    limit: Option<u64>,
+) -> Result<Vec<search_service::SearchResult>, AppError> {
+    search_service::search(&*db.read().await, &query, limit.unwrap_or(20).clamp(1, 100)).await
+}
+
+#[tauri::command]
+pub async fn get_dashboard_stats(db: State<'_, SharedConnection>) -> Result<reports::DashboardStats, AppError> {
+    reports::dashboard_stats(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn get_period_range(db: State<'_, SharedConnection>, period: timezone::Period) -> Result<timezone::Range, AppError> {
+    Ok(reports::current_period(&*db.read().await, period).await)
+}
+
+#[tauri::command]
+pub async fn get_popular_products(db: State<'_, SharedConnection>) -> Result<Vec<product::Model>, AppError> {
+    products::popular_products(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn get_expiring_gallons(db: State<'_, SharedConnection>) -> Result<Vec<reports::ExpiringGallon>, AppError> {
+    reports::expiring_gallons(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn get_sales_report(
+    db: State<'_, SharedConnection>,
+    start_iso: String,
+    end_iso: String,
+    payment_method: String,
+) -> Result<reports::SalesReport, AppError> {
+    reports::sales_report(&*db.read().await, &start_iso, &end_iso, &payment_method).await
+}
+
+/// Receita, custo e margem bruta com os mesmos filtros de `get_sales_report`.
+#[tauri::command]
+pub async fn get_profitability_report(
+    db: State<'_, SharedConnection>,
+    start_iso: String,
+    end_iso: String,
+    payment_method: String,
+) -> Result<reports::ProfitabilityReport, AppError> {
+    reports::profitability_report(&*db.read().await, &start_iso, &end_iso, &payment_method).await
+}
+
+/// Planilha das vendas com os mesmos filtros de `get_sales_report`.
+#[tauri::command]
+pub async fn export_sales(
+    db: State<'_, SharedConnection>,
+    start_iso: String,
+    end_iso: String,
+    payment_method: String,
+    format: String,
+) -> Result<spreadsheet::ExportedFile, AppError> {
+    let format = spreadsheet::Format::parse(&format)?;
+    let db = db.read().await;
+    let sheet = exports::sales_sheet(&db, &start_iso, &end_iso, &payment_method).await?;
+    exports::export(&db, &sheet, format).await
+}
+
+#[tauri::command]
+pub async fn export_products(db: State<'_, SharedConnection>, format: String) -> Result<spreadsheet::ExportedFile, AppError> {
+    let format = spreadsheet::Format::parse(&format)?;
+    let db = db.read().await;
+    let sheet = exports::products_sheet(&db).await?;
+    exports::export(&db, &sheet, format).await
+}
+
+#[tauri::command]
+pub async fn export_clients(db: State<'_, SharedConnection>, format: String) -> Result<spreadsheet::ExportedFile, AppError> {
+    let format = spreadsheet::Format::parse(&format)?;
+    let db = db.read().await;
+    let sheet = exports::clients_sheet(&db).await?;
+    exports::export(&db, &sheet, format).await
+}
+
+/// Vendas fiado do período do relatório.
+#[tauri::command]
+pub async fn export_receivables(
+    db: State<'_, SharedConnection>,
+    start_iso: String,
+    end_iso: String,
+    format: String,
+) -> Result<spreadsheet::ExportedFile, AppError> {
+    let format = spreadsheet::Format::parse(&format)?;
+    let db = db.read().await;
+    let sheet = exports::receivables_sheet(&db, &start_iso, &end_iso).await?;
+    exports::export(&db, &sheet, format).await
+}
+
+fn decode_upload(content: &str) -> Result<Vec<u8>, AppError> {
+    general_purpose::STANDARD
+        .decode(content)
+        .map_err(|e| AppError::validation(format!("Arquivo inválido: {}", e)))
+}
+
+/// Lê a planilha (base64) e valida as linhas sem gravar. Sem `mapping`,
+/// as colunas são sugeridas pelos cabeçalhos.
+#[tauri::command]
+pub async fn preview_import(
+    db: State<'_, SharedConnection>,
+    kind: imports::ImportKind,
+    format: String,
+    content: String,
+    mapping: Option<imports::ColumnMapping>,
+) -> Result<imports::ImportPreview, AppError> {
+    let format = spreadsheet::Format::parse(&format)?;
+    let content = decode_upload(&content)?;
+    imports::preview(&*db.read().await, kind, format, &content, mapping).await
+}
+
+#[tauri::command]
+pub async fn commit_import(
+    db: State<'_, SharedConnection>,
+    kind: imports::ImportKind,
+    format: String,
+    content: String,
+    mapping: imports::ColumnMapping,
+) -> Result<imports::ImportResult, AppError> {
+    let format = spreadsheet::Format::parse(&format)?;
+    let content = decode_upload(&content)?;
+    imports::commit(&*db.read().await, kind, format, &content, mapping).await
+}
+
+/// Pacote com todos os dados, para levar a outro computador. Só o
+/// administrador pode gerá-lo; a exportação fica na auditoria.
+#[tauri::command]
+pub async fn export_archive(
+    db: State<'_, SharedConnection>,
+    username: String,
+    password: String,
+) -> Result<spreadsheet::ExportedFile, AppError> {
+    let db = db.read().await;
+    users::authorize_admin(&db, &username, &password).await?;
+    archive::export_file(&db, &username).await
+}
+
+/// Recria os dados do pacote em uma instalação nova. A senha é conferida
+/// contra o administrador da instalação atual, antes da substituição.
+#[tauri::command]
+pub async fn import_archive(
+    db: State<'_, SharedConnection>,
+    content: String,
+    username: String,
+    password: String,
+) -> Result<archive::ArchiveManifest, AppError> {
+    let content = decode_upload(&content)?;
+    let session = db.write().await;
+    users::authorize_admin(&session.connection, &username, &password).await?;
+    archive::import(&session.connection, &content).await
+}
+
+#[tauri::command]
+pub async fn get_recent_sales(db: State<'_, SharedConnection>) -> Result<Vec<sales::RecentSale>, AppError> {
+    sales::recent_sales(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn get_sale_details(db: State<'_, SharedConnection>, id: i32) -> Result<sales::SaleDetails, AppError> {
+    sales::sale_details(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn create_sale(
+    db: State<'_, SharedConnection>,
+    client_id: i32,
+    items: serde_json::Value,
+    total: f64,
+    payment_method: String,
+) -> Result<sale::Model, AppError> {
+    let input = sales::SaleInput { client_id, items, total, payment_method };
+    sales::create_sale(&*db.read().await, input).await
+}
+
+#[tauri::command]
+pub async fn update_sale(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    client_id: i32,
+    items: serde_json::Value,
+    total: f64,
+    payment_method: String,
+) -> Result<sale::Model, AppError> {
+    let input = sales::SaleInput { client_id, items, total, payment_method };
+    sales::update_sale(&*db.read().await, id, input).await
+}
+
+#[tauri::command]
+pub async fn delete_sale(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    sales::delete_sale(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn generate_sale_pdf(
+    db: State<'_, SharedConnection>,
+    client_name: String,
+    items: Vec<serde_json::Value>,
+    total: f64,
+    sale_number: i32,
+) -> Result<String, AppError> {
+    let note = pdf::SaleNote { client_name, items, total, sale_number };
+    let bytes = pdf::sale_note(&*db.read().await, &note).await?;
+    Ok(general_purpose::STANDARD.encode(bytes))
+}
+
+#[tauri::command]
+pub async fn generate_blank_pdf() -> Result<String, AppError> {
+    Ok(general_purpose::STANDARD.encode(pdf::blank_note()?))
+}
+
+#[tauri::command]
+pub async fn generate_pix_code(
+    db: State<'_, SharedConnection>,
+    total: f64,
+    txid: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+) -> Result<pix::PixCode, AppError> {
+    let charge = pix::PixCharge { amount: Some(total), txid, description, location };
+    pix::generate_configured(&*db.read().await, &charge).await
+}
+
+#[tauri::command]
+pub async fn get_products(db: State<'_, SharedConnection>) -> Result<Vec<product::Model>, AppError> {
+    products::list_products(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn get_products_page(
+    db: State<'_, SharedConnection>,
+    page: Option<u64>,
+    page_size: Option<u64>,
+    search: Option<String>,
+    sort_by: Option<String>,
+    sort_dir: Option<pagination::SortDirection>,
+    category_id: Option<i32>,
+) -> Result<pagination::Page<product::Model>, AppError> {
+    let query = products::ProductQuery { page, page_size, search, sort_by, sort_dir, category_id };
+    products::products_page(&*db.read().await, query).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_product(
+    db: State<'_, SharedConnection>,
+    name: String,
+    price: f64,
+    stock_quantity: i32,
+    category: String,
+    category_id: Option<i32>,
+    min_stock: Option<i32>,
+    reorder_qty: Option<i32>,
+    cost_price: Option<f64>,
+) -> Result<product::Model, AppError> {
+    let input = products::ProductInput {
+        name, price, stock_quantity, category, category_id, min_stock, reorder_qty, cost_price,
+    };
+    products::create_product(&*db.read().await, input).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_product(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    name: String,
+    price: f64,
+    category: String,
+    category_id: Option<i32>,
+    min_stock: Option<i32>,
+    reorder_qty: Option<i32>,
+    cost_price: Option<f64>,
+) -> Result<product::Model, AppError> {
+    let input = products::ProductInput {
+        name, price, category, category_id, min_stock, reorder_qty, cost_price, ..Default::default()
+    };
+    products::update_product(&*db.read().await, id, input).await
+}
+
+#[tauri::command]
+pub async fn delete_product(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    products::delete_product(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn get_low_stock_products(db: State<'_, SharedConnection>) -> Result<Vec<product::Model>, AppError> {
+    stock::low_stock_products(&*db.read().await).await
+}
+
+/// Sugestões de compra pela média de vendas dos últimos `days` dias (30 se omitido).
+#[tauri::command]
+pub async fn get_reorder_suggestions(
+    db: State<'_, SharedConnection>,
+    days: Option<u32>,
+) -> Result<Vec<stock::ReorderSuggestion>, AppError> {
+    let days = days.unwrap_or(stock::DEFAULT_SALES_WINDOW_DAYS);
+    stock::reorder_suggestions(&*db.read().await, days, chrono::Utc::now()).await
+}
+
+#[tauri::command]
+pub async fn adjust_stock(
+    db: State<'_, SharedConnection>,
+    product_id: i32,
+    delta: i32,
+    reason: stock::StockReason,
+    note: Option<String>,
+    username: String,
+) -> Result<product::Model, AppError> {
+    stock::adjust_stock(&*db.read().await, product_id, delta, reason, note, &username).await
+}
+
+#[tauri::command]
+pub async fn get_stock_history(
+    db: State<'_, SharedConnection>,
+    product_id: i32,
+) -> Result<Vec<stock_movement::Model>, AppError> {
+    stock::stock_history(&*db.read().await, product_id).await
+}
+
+#[tauri::command]
+pub async fn check_stock_consistency(db: State<'_, SharedConnection>) -> Result<Vec<stock::StockDiscrepancy>, AppError> {
+    stock::check_consistency(&*db.read().await).await
+}
+
+/// Iguala o estoque à soma das movimentações. Restrito a administradores.
+#[tauri::command]
+pub async fn recompute_stock(
+    db: State<'_, SharedConnection>,
+    username: String,
+    password: String,
+) -> Result<Vec<stock::StockDiscrepancy>, AppError> {
+    let db = db.read().await;
+    users::authorize_admin(&db, &username, &password).await?;
+    stock::recompute_stock(&db).await
+}
+
+#[tauri::command]
+pub async fn get_suppliers(db: State<'_, SharedConnection>) -> Result<Vec<supplier::Model>, AppError> {
+    purchases::list_suppliers(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn create_supplier(
+    db: State<'_, SharedConnection>,
+    name: String,
+    document: Option<String>,
+    phone: Option<String>,
+    email: Option<String>,
+    notes: Option<String>,
+) -> Result<supplier::Model, AppError> {
+    let input = purchases::SupplierInput { name, document, phone, email, notes };
+    purchases::create_supplier(&*db.read().await, input).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_supplier(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    name: String,
+    document: Option<String>,
+    phone: Option<String>,
+    email: Option<String>,
+    notes: Option<String>,
+) -> Result<supplier::Model, AppError> {
+    let input = purchases::SupplierInput { name, document, phone, email, notes };
+    purchases::update_supplier(&*db.read().await, id, input).await
+}
+
+#[tauri::command]
+pub async fn delete_supplier(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    purchases::delete_supplier(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn get_purchase_orders(
+    db: State<'_, SharedConnection>,
+    status: Option<purchases::PurchaseStatus>,
+) -> Result<Vec<purchases::PurchaseOrderSummary>, AppError> {
+    purchases::list_purchase_orders(&*db.read().await, status).await
+}
+
+#[tauri::command]
+pub async fn get_purchase_order(
+    db: State<'_, SharedConnection>,
+    id: i32,
+) -> Result<purchases::PurchaseOrderDetails, AppError> {
+    purchases::purchase_order_details(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn create_purchase_order(
+    db: State<'_, SharedConnection>,
+    supplier_id: i32,
+    items: Vec<purchases::PurchaseItemInput>,
+    notes: Option<String>,
+    username: String,
+) -> Result<purchases::PurchaseOrderDetails, AppError> {
+    purchases::create_purchase_order(&*db.read().await, supplier_id, items, notes, &username).await
+}
+
+/// Sem `items`, recebe todo o restante do pedido.
+#[tauri::command]
+pub async fn receive_purchase_order(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    items: Option<Vec<purchases::ReceivedItem>>,
+    username: String,
+) -> Result<purchases::PurchaseOrderDetails, AppError> {
+    purchases::receive_purchase_order(&*db.read().await, id, items, &username).await
+}
+
+#[tauri::command]
+pub async fn cancel_purchase_order(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    username: String,
+) -> Result<purchase_order::Model, AppError> {
+    purchases::cancel_purchase_order(&*db.read().await, id, &username).await
+}
+
+#[tauri::command]
+pub async fn get_categories(db: State<'_, SharedConnection>) -> Result<Vec<category::Model>, AppError> {
+    products::list_categories(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn create_category(
+    db: State<'_, SharedConnection>,
+    name: String,
+    description: Option<String>,
+) -> Result<category::Model, AppError> {
+    products::create_category(&*db.read().await, name, description).await
+}
+
+#[tauri::command]
+pub async fn update_category(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    name: String,
+    description: Option<String>,
+) -> Result<category::Model, AppError> {
+    products::update_category(&*db.read().await, id, name, description).await
+}
+
+#[tauri::command]
+pub async fn delete_category(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    products::delete_category(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn get_users(db: State<'_, SharedConnection>) -> Result<Vec<user::Model>, AppError> {
+    users::list_users(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn create_user(
+    db: State<'_, SharedConnection>,
+    username: String,
+    password_plain: String,
+    name: String,
+    role: String,
+) -> Result<user::Model, AppError> {
+    users::create_user(&*db.read().await, username, &password_plain, name, role).await
+}
+
+#[tauri::command]
+pub async fn update_user(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    username: String,
+    password_plain: Option<String>,
+    name: String,
+    role: String,
+) -> Result<user::Model, AppError> {
+    users::update_user(&*db.read().await, id, username, password_plain.as_deref(), name, role).await
+}
+
+#[tauri::command]
+pub async fn delete_user(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    users::delete_user(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn get_shipping_methods(db: State<'_, SharedConnection>) -> Result<Vec<shipping_method::Model>, AppError> {
+    settings::list_shipping_methods(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn create_shipping_method(
+    db: State<'_, SharedConnection>,
+    name: String,
+    fee: f64,
+) -> Result<shipping_method::Model, AppError> {
+    settings::create_shipping_method(&*db.read().await, name, fee).await
+}
+
+#[tauri::command]
+pub async fn update_shipping_method(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    name: String,
+    fee: f64,
+) -> Result<shipping_method::Model, AppError> {
+    settings::update_shipping_method(&*db.read().await, id, name, fee).await
+}
+
+#[tauri::command]
+pub async fn delete_shipping_method(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    settings::delete_shipping_method(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn get_payment_methods(db: State<'_, SharedConnection>) -> Result<Vec<payment_method::Model>, AppError> {
+    settings::list_payment_methods(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn create_payment_method(db: State<'_, SharedConnection>, name: String) -> Result<payment_method::Model, AppError> {
+    settings::create_payment_method(&*db.read().await, name).await
+}
+
+#[tauri::command]
+pub async fn update_payment_method(
+    db: State<'_, SharedConnection>,
+    id: i32,
+    name: String,
+) -> Result<payment_method::Model, AppError> {
+    settings::update_payment_method(&*db.read().await, id, name).await
+}
+
+#[tauri::command]
+pub async fn delete_payment_method(db: State<'_, SharedConnection>, id: i32) -> Result<(), AppError> {
+    settings::delete_payment_method(&*db.read().await, id).await
+}
+
+#[tauri::command]
+pub async fn get_settings(db: State<'_, SharedConnection>) -> Result<Vec<setting::Model>, AppError> {
+    settings::list_settings(&*db.read().await).await
+}
+
+#[tauri::command]
+pub async fn save_setting(db: State<'_, SharedConnection>, key: String, value: String) -> Result<setting::Model, AppError> {
+    settings::save_setting(&*db.read().await, key, value).await
+}
+
+#[tauri::command]
+pub async fn seed_db(db: State<'_, SharedConnection>) -> Result<String, AppError> {
+    if crate::db::seed_demo_data(&*db.read().await).await? {
+        Ok("Dados iniciais criados com sucesso!".into())
+    } else {
+        Ok("Banco de dados já contém dados.".into())
+    }
+}
@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ceps")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub cep: String, // Apenas dígitos
+    pub street: Option<String>,
+    pub neighborhood: Option<String>,
+    pub city: String,
+    pub state: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,7 +1,9 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[ts(rename = "Client")]
 #[sea_orm(table_name = "clients")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -12,9 +14,15 @@ pub struct Model {
     pub cep: Option<String>,
     pub city: Option<String>,
     pub state: Option<String>,
-    pub address: Option<String>,
+    pub street: Option<String>,
+    pub number: Option<String>,
+    pub complement: Option<String>,
+    pub neighborhood: Option<String>,
+    pub address: Option<String>, // Endereço completo montado a partir dos campos acima
     pub observations: Option<String>,
     pub created_at: DateTimeUtc,
+    /// Quando os dados pessoais foram apagados a pedido do titular (LGPD).
+    pub anonymized_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -6,3 +6,10 @@ pub mod payment_method;
 pub mod shipping_method;
 pub mod client_gallon;
 pub mod category;
+pub mod setting;
+pub mod cep;
+pub mod audit_log;
+pub mod stock_movement;
+pub mod supplier;
+pub mod purchase_order;
+pub mod purchase_order_item;
@@ -1,7 +1,9 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[ts(rename = "Product")]
 #[sea_orm(table_name = "products")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -11,6 +13,18 @@ pub struct Model {
     pub stock_quantity: i32,
     pub category: String, // Mantido por compatibilidade temporária
     pub category_id: Option<i32>,
+    /// Abaixo desta quantidade o produto entra nos alertas de estoque.
+    #[sea_orm(default_value = 10)]
+    #[serde(default = "crate::stock::default_min_stock")]
+    pub min_stock: i32,
+    /// Lote de compra; as sugestões de reposição são múltiplos dele. 0 sem lote.
+    #[sea_orm(default_value = 0)]
+    #[serde(default)]
+    pub reorder_qty: i32,
+    /// Custo médio ponderado, atualizado a cada recebimento de compra.
+    #[sea_orm(default_value = 0.0)]
+    #[serde(default)]
+    pub cost_price: f64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
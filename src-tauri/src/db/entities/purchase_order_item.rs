@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[ts(rename = "PurchaseOrderItem")]
+#[sea_orm(table_name = "purchase_order_items")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub purchase_order_id: i32,
+    pub product_id: i32,
+    pub quantity: i32,
+    pub received_quantity: i32,
+    /// Custo unitário combinado; atualizado com o custo informado no recebimento.
+    pub unit_cost: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::purchase_order::Entity",
+        from = "Column::PurchaseOrderId",
+        to = "super::purchase_order::Column::Id",
+        on_delete = "Cascade"
+    )]
+    PurchaseOrder,
+    #[sea_orm(
+        belongs_to = "super::product::Entity",
+        from = "Column::ProductId",
+        to = "super::product::Column::Id"
+    )]
+    Product,
+}
+
+impl Related<super::purchase_order::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PurchaseOrder.def()
+    }
+}
+
+impl Related<super::product::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Product.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
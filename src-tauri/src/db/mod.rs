@@ -1,30 +1,163 @@
-use sea_orm::{Database, DatabaseConnection, Schema};
+use anyhow::Context;
+use sea_orm::sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sea_orm::{DatabaseConnection, DbErr, Schema, SqlxSqliteConnector};
 use sea_orm::ConnectionTrait;
 use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
 use tauri::AppHandle;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tauri::Manager;
 
 pub mod entities;
 
-pub async fn init_db(app_handle: &AppHandle) -> anyhow::Result<DatabaseConnection> {
-    let app_dir = app_handle.path().app_data_dir()?;
-    
-    if !app_dir.exists() {
-        fs::create_dir_all(&app_dir)?;
+pub const DATABASE_FILE: &str = "aquagas.db";
+pub const BACKUP_DIR: &str = "backups";
+
+/// Versão do esquema gravada em `PRAGMA user_version` por [`setup_schema`].
+/// Aumente ao incluir migrações; bancos de versão maior que esta foram
+/// criados por uma versão mais nova do app. Arquivos anteriores ao controle
+/// de versão têm 0.
+pub const SCHEMA_VERSION: i32 = 6;
+
+/// Cabeçalho de um arquivo SQLite sem criptografia.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Banco aberto e, se cifrado com o SQLCipher, a senha usada para abri-lo
+/// (necessária para conferir os backups, cifrados com a mesma senha).
+pub struct Session {
+    pub connection: DatabaseConnection,
+    pub key: Option<String>,
+}
+
+/// Conexão gerenciada pelo Tauri. Fica atrás de um `RwLock` para que a
+/// restauração de backup possa fechar o banco e abrir o arquivo novo sem
+/// reiniciar o app: os comandos seguram a leitura enquanto usam a conexão.
+#[derive(Clone)]
+pub struct SharedConnection(Arc<RwLock<Session>>);
+
+impl SharedConnection {
+    pub fn new(session: Session) -> Self {
+        SharedConnection(Arc::new(RwLock::new(session)))
     }
-    
-    let db_path = app_dir.join("aquagas.db");
-    let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
-    
-    let db = Database::connect(&db_url).await?;
-    
+
+    pub async fn read(&self) -> RwLockReadGuard<'_, DatabaseConnection> {
+        RwLockReadGuard::map(self.0.read().await, |s| &s.connection)
+    }
+
+    pub async fn session(&self) -> RwLockReadGuard<'_, Session> {
+        self.0.read().await
+    }
+
+    /// Acesso exclusivo, esperando os comandos em andamento terminarem.
+    pub async fn write(&self) -> RwLockWriteGuard<'_, Session> {
+        self.0.write().await
+    }
+}
+
+/// Pasta de dados do app, onde ficam o banco e os backups.
+pub fn data_dir(app_handle: &AppHandle) -> anyhow::Result<PathBuf> {
+    Ok(app_handle.path().app_data_dir()?)
+}
+
+pub fn database_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(DATABASE_FILE)
+}
+
+pub fn backup_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(BACKUP_DIR)
+}
+
+pub async fn init_db(app_handle: &AppHandle, key: Option<&str>) -> anyhow::Result<DatabaseConnection> {
+    open(&database_path(&data_dir(app_handle)?), key).await
+}
+
+/// Se o arquivo existe e não começa com o cabeçalho do SQLite, ou seja,
+/// foi cifrado com o SQLCipher (que grava ali o sal aleatório). Um início
+/// zerado indica arquivo danificado, não criptografia.
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match fs::File::open(path).and_then(|mut f| f.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER && header.iter().any(|&b| b != 0),
+        Err(_) => false,
+    }
+}
+
+/// Opções de conexão ao arquivo (`mode` = `ro`, `rw` ou `rwc`), com a
+/// senha do SQLCipher quando informada.
+pub fn connect_options(path: &Path, mode: &str, key: Option<&str>) -> Result<SqliteConnectOptions, DbErr> {
+    let options = SqliteConnectOptions::from_str(&format!("sqlite:{}?mode={}", path.display(), mode))
+        .map_err(|e| DbErr::Conn(sea_orm::RuntimeErr::Internal(e.to_string())))?;
+    match key {
+        Some(_) if !cfg!(feature = "sqlcipher") => Err(DbErr::Custom(
+            "Este sistema foi compilado sem suporte a banco criptografado".into(),
+        )),
+        Some(key) => Ok(options.pragma("key", quote(key))),
+        None => Ok(options),
+    }
+}
+
+/// Literal SQL entre aspas simples.
+pub fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Conecta ao arquivo sem conferir a integridade nem aplicar o esquema.
+///
+/// Uma conexão só, como no `Database::connect` do SeaORM para SQLite: as
+/// transações leem antes de gravar e, com mais conexões, falhariam com
+/// `SQLITE_BUSY` ao promover o bloqueio.
+pub async fn connect(path: &Path, mode: &str, key: Option<&str>) -> Result<DatabaseConnection, DbErr> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(connect_options(path, mode, key)?)
+        .await
+        .map_err(|e| DbErr::Conn(sea_orm::RuntimeErr::SqlxError(e)))?;
+    Ok(SqlxSqliteConnector::from_sqlx_sqlite_pool(pool))
+}
+
+/// Abre (ou cria) o banco no arquivo indicado, confere a integridade e
+/// aplica o esquema. `key` é a senha de um banco cifrado.
+pub async fn open(path: &Path, key: Option<&str>) -> anyhow::Result<DatabaseConnection> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Não foi possível criar a pasta de dados {}", dir.display()))?;
+    }
+
+    let db = connect(path, "rwc", key)
+        .await
+        .with_context(|| format!("Não foi possível abrir o banco de dados {}", path.display()))?;
+
+    check_integrity(&db).await?;
+
     // Inicialização simples de tabelas caso não existam
     setup_schema(&db).await?;
-    
+
     Ok(db)
 }
 
-async fn setup_schema(db: &DatabaseConnection) -> anyhow::Result<()> {
+/// `PRAGMA quick_check`: falha se o arquivo não for um banco SQLite ou
+/// estiver corrompido.
+pub async fn check_integrity(db: &DatabaseConnection) -> anyhow::Result<()> {
+    let row = db
+        .query_one(sea_orm::Statement::from_string(
+            db.get_database_backend(),
+            "PRAGMA quick_check".to_string(),
+        ))
+        .await
+        .context("Não foi possível ler o banco de dados")?;
+    let result: String = row
+        .and_then(|r| r.try_get_by_index(0).ok())
+        .unwrap_or_default();
+    if result != "ok" {
+        anyhow::bail!("O banco de dados está corrompido: {}", result);
+    }
+    Ok(())
+}
+
+pub async fn setup_schema(db: &DatabaseConnection) -> anyhow::Result<()> {
     use sea_orm::{EntityTrait, PaginatorTrait, ActiveModelTrait, Set};
     let builder = db.get_database_backend();
     let schema = Schema::new(builder);
@@ -38,6 +171,13 @@ async fn setup_schema(db: &DatabaseConnection) -> anyhow::Result<()> {
     let _ = db.execute(builder.build(schema.create_table_from_entity(entities::shipping_method::Entity).if_not_exists())).await;
     let _ = db.execute(builder.build(schema.create_table_from_entity(entities::client_gallon::Entity).if_not_exists())).await;
     let _ = db.execute(builder.build(schema.create_table_from_entity(entities::payment_method::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::setting::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::cep::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::audit_log::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::stock_movement::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::supplier::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::purchase_order::Entity).if_not_exists())).await;
+    let _ = db.execute(builder.build(schema.create_table_from_entity(entities::purchase_order_item::Entity).if_not_exists())).await;
 
     // Migração manual: adicionar colunas se não existirem
     let _ = db.execute(sea_orm::Statement::from_string(
@@ -50,12 +190,60 @@ async fn setup_schema(db: &DatabaseConnection) -> anyhow::Result<()> {
         "ALTER TABLE products ADD COLUMN category_id INTEGER REFERENCES categories(id)".to_string(),
     )).await;
 
+    // Estoque mínimo por produto (antes fixo em 10 para todos)
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE products ADD COLUMN min_stock INTEGER NOT NULL DEFAULT 10".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE products ADD COLUMN reorder_qty INTEGER NOT NULL DEFAULT 0".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE products ADD COLUMN cost_price REAL NOT NULL DEFAULT 0".to_string())).await;
+
     // Migrações de Clientes
     let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN document TEXT".to_string())).await;
     let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN cep TEXT".to_string())).await;
     let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN city TEXT".to_string())).await;
     let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN state TEXT".to_string())).await;
     let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN observations TEXT".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN street TEXT".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN number TEXT".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN complement TEXT".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN neighborhood TEXT".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "ALTER TABLE clients ADD COLUMN anonymized_at TEXT".to_string())).await;
+
+    // Documentos passam a ser gravados apenas com dígitos (só as linhas fora
+    // do padrão)
+    let _ = db.execute(sea_orm::Statement::from_string(
+        builder,
+        "UPDATE clients SET document = NULLIF(REPLACE(REPLACE(REPLACE(REPLACE(TRIM(document), '.', ''), '-', ''), '/', ''), ' ', ''), '') \
+         WHERE document IS NOT NULLIF(REPLACE(REPLACE(REPLACE(REPLACE(TRIM(document), '.', ''), '-', ''), '/', ''), ' ', ''), '')".to_string(),
+    )).await;
+
+    // CEPs gravados apenas com dígitos e UF em maiúsculas. Só toca as linhas
+    // fora do padrão, para não reescrever clientes (e a busca) a cada início
+    let _ = db.execute(sea_orm::Statement::from_string(
+        builder,
+        "UPDATE clients SET cep = NULLIF(REPLACE(REPLACE(TRIM(cep), '-', ''), '.', ''), ''), state = NULLIF(UPPER(TRIM(state)), '') \
+         WHERE cep IS NOT NULLIF(REPLACE(REPLACE(TRIM(cep), '-', ''), '.', ''), '') OR state IS NOT NULLIF(UPPER(TRIM(state)), '')".to_string(),
+    )).await;
+
+    // Endereços antigos (texto livre) passam a ocupar o campo de logradouro
+    let _ = db.execute(sea_orm::Statement::from_string(
+        builder,
+        "UPDATE clients SET street = address WHERE street IS NULL AND address IS NOT NULL".to_string(),
+    )).await;
+
+    // Índices usados pelos relatórios
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "CREATE INDEX IF NOT EXISTS idx_sales_created_at ON sales (created_at)".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "CREATE INDEX IF NOT EXISTS idx_sales_client_id ON sales (client_id)".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "CREATE INDEX IF NOT EXISTS idx_sales_payment_method ON sales (payment_method)".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "CREATE INDEX IF NOT EXISTS idx_audit_logs_entity ON audit_logs (entity, entity_id)".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "CREATE INDEX IF NOT EXISTS idx_stock_movements_product_id ON stock_movements (product_id)".to_string())).await;
+    let _ = db.execute(sea_orm::Statement::from_string(builder, "CREATE INDEX IF NOT EXISTS idx_purchase_order_items_order_id ON purchase_order_items (purchase_order_id)".to_string())).await;
+
+    // Estoque anterior ao histórico de movimentações vira o saldo inicial
+    if let Err(e) = crate::stock::record_opening_balances(db).await {
+        log::error!("Falha ao registrar o saldo inicial do estoque: {}", e);
+    }
+
+    // Índices de busca textual (FTS5)
+    setup_search_index(db).await;
 
     // Sementes de Categorias
     let category_count = entities::category::Entity::find().count(db).await.unwrap_or(0);
@@ -121,6 +309,148 @@ async fn setup_schema(db: &DatabaseConnection) -> anyhow::Result<()> {
             let _ = m.insert(db).await;
         }
     }
-    
+
+    if schema_version(db).await? < SCHEMA_VERSION {
+        db.execute(sea_orm::Statement::from_string(
+            builder,
+            format!("PRAGMA user_version = {}", SCHEMA_VERSION),
+        ))
+        .await?;
+    }
+
     Ok(())
 }
+
+/// Versão do esquema do banco (`PRAGMA user_version`).
+pub async fn schema_version(db: &DatabaseConnection) -> Result<i32, sea_orm::DbErr> {
+    let row = db
+        .query_one(sea_orm::Statement::from_string(
+            db.get_database_backend(),
+            "PRAGMA user_version".to_string(),
+        ))
+        .await?;
+    Ok(row.and_then(|r| r.try_get_by_index(0).ok()).unwrap_or(0))
+}
+
+/// Clientes de demonstração para um banco recém-criado. Devolve `false`
+/// quando já existem clientes cadastrados. O usuário admin é garantido por
+/// [`setup_schema`].
+pub async fn seed_demo_data(db: &DatabaseConnection) -> Result<bool, crate::error::AppError> {
+    use sea_orm::{ActiveModelTrait, EntityTrait, PaginatorTrait, Set};
+
+    if entities::client::Entity::find().count(db).await? > 0 {
+        return Ok(false);
+    }
+
+    let clients = vec![
+        ("Maria Silva", "(11) 98765-4321", "Rua A, 123 - Centro"),
+        ("João Santos", "(11) 91234-5678", "Av. B, 456 - Jardins"),
+        ("Ana Costa", "(11) 99876-5432", "Rua C, 789 - Vila Nova"),
+    ];
+    for (name, phone, address) in clients {
+        let client = entities::client::ActiveModel {
+            name: Set(name.into()),
+            phone: Set(Some(phone.into())),
+            address: Set(Some(address.into())),
+            created_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        };
+        client.insert(db).await?;
+    }
+    Ok(true)
+}
+
+/// Expressão SQL que deixa apenas os dígitos de um telefone formatado.
+const PHONE_DIGITS: &str = "REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(COALESCE({col}, ''), '(', ''), ')', ''), '-', ''), ' ', ''), '+', '')";
+
+/// Cria as tabelas FTS5 de clientes e produtos e os gatilhos que as mantêm
+/// sincronizadas. O tokenizador trigram permite buscar trechos no meio das
+/// palavras (telefones, ruas) e `remove_diacritics` ignora acentos.
+async fn setup_search_index(db: &DatabaseConnection) {
+    let builder = db.get_database_backend();
+    let client_values = |prefix: &str| {
+        format!(
+            "{p}.id, {p}.name, {phone}, COALESCE({p}.document, ''), COALESCE({p}.address, ''), COALESCE({p}.observations, '')",
+            p = prefix,
+            phone = PHONE_DIGITS.replace("{col}", &format!("{}.phone", prefix)),
+        )
+    };
+    let product_values = |prefix: &str| format!("{p}.id, {p}.name, {p}.category", p = prefix);
+
+    let statements = vec![
+        "CREATE VIRTUAL TABLE IF NOT EXISTS clients_fts USING fts5(name, phone, document, address, observations, tokenize = 'trigram remove_diacritics 1')".to_string(),
+        "CREATE VIRTUAL TABLE IF NOT EXISTS products_fts USING fts5(name, category, tokenize = 'trigram remove_diacritics 1')".to_string(),
+        format!(
+            "CREATE TRIGGER IF NOT EXISTS clients_fts_insert AFTER INSERT ON clients BEGIN \
+             INSERT INTO clients_fts(rowid, name, phone, document, address, observations) VALUES ({}); END",
+            client_values("new")
+        ),
+        format!(
+            "CREATE TRIGGER IF NOT EXISTS clients_fts_update AFTER UPDATE ON clients BEGIN \
+             DELETE FROM clients_fts WHERE rowid = old.id; \
+             INSERT INTO clients_fts(rowid, name, phone, document, address, observations) VALUES ({}); END",
+            client_values("new")
+        ),
+        "CREATE TRIGGER IF NOT EXISTS clients_fts_delete AFTER DELETE ON clients BEGIN \
+         DELETE FROM clients_fts WHERE rowid = old.id; END".to_string(),
+        format!(
+            "CREATE TRIGGER IF NOT EXISTS products_fts_insert AFTER INSERT ON products BEGIN \
+             INSERT INTO products_fts(rowid, name, category) VALUES ({}); END",
+            product_values("new")
+        ),
+        format!(
+            "CREATE TRIGGER IF NOT EXISTS products_fts_update AFTER UPDATE ON products BEGIN \
+             DELETE FROM products_fts WHERE rowid = old.id; \
+             INSERT INTO products_fts(rowid, name, category) VALUES ({}); END",
+            product_values("new")
+        ),
+        "CREATE TRIGGER IF NOT EXISTS products_fts_delete AFTER DELETE ON products BEGIN \
+         DELETE FROM products_fts WHERE rowid = old.id; END".to_string(),
+    ];
+    for sql in statements {
+        if let Err(e) = db.execute(sea_orm::Statement::from_string(builder, sql)).await {
+            log::error!("Falha ao criar índice de busca: {}", e);
+        }
+    }
+
+    // Bancos existentes: popular o índice quando estiver fora de sincronia
+    let rebuilds = [
+        (
+            "clients",
+            "clients_fts",
+            format!(
+                "INSERT INTO clients_fts(rowid, name, phone, document, address, observations) SELECT {} FROM clients c",
+                client_values("c")
+            ),
+        ),
+        (
+            "products",
+            "products_fts",
+            format!(
+                "INSERT INTO products_fts(rowid, name, category) SELECT {} FROM products p",
+                product_values("p")
+            ),
+        ),
+    ];
+    for (table, fts, populate) in rebuilds {
+        let counts = db
+            .query_one(sea_orm::Statement::from_string(
+                builder,
+                format!(
+                    "SELECT (SELECT COUNT(*) FROM {}) AS total, (SELECT COUNT(*) FROM {}) AS indexed",
+                    table, fts
+                ),
+            ))
+            .await;
+        let in_sync = match counts {
+            Ok(Some(row)) => {
+                row.try_get::<i64>("", "total").ok() == row.try_get::<i64>("", "indexed").ok()
+            }
+            _ => true,
+        };
+        if !in_sync {
+            let _ = db.execute(sea_orm::Statement::from_string(builder, format!("DELETE FROM {}", fts))).await;
+            let _ = db.execute(sea_orm::Statement::from_string(builder, populate)).await;
+        }
+    }
+}
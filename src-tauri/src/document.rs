@@ -0,0 +1,71 @@
+//! Validação e formatação de CPF/CNPJ.
+
+use crate::error::AppError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentKind {
+    Cpf,
+    Cnpj,
+}
+
+/// Mantém apenas os dígitos do documento.
+pub fn normalize(value: &str) -> String {
+    value.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+fn digits(value: &str) -> Vec<u32> {
+    value.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+fn check_digit(digits: &[u32], weights: &[u32]) -> u32 {
+    let sum: u32 = digits.iter().zip(weights).map(|(d, w)| d * w).sum();
+    let rest = sum % 11;
+    if rest < 2 { 0 } else { 11 - rest }
+}
+
+fn all_equal(digits: &[u32]) -> bool {
+    digits.windows(2).all(|w| w[0] == w[1])
+}
+
+pub fn is_valid_cpf(value: &str) -> bool {
+    let d = digits(value);
+    if d.len() != 11 || all_equal(&d) {
+        return false;
+    }
+    let first = check_digit(&d[..9], &[10, 9, 8, 7, 6, 5, 4, 3, 2]);
+    let second = check_digit(&d[..10], &[11, 10, 9, 8, 7, 6, 5, 4, 3, 2]);
+    d[9] == first && d[10] == second
+}
+
+pub fn is_valid_cnpj(value: &str) -> bool {
+    let d = digits(value);
+    if d.len() != 14 || all_equal(&d) {
+        return false;
+    }
+    let first = check_digit(&d[..12], &[5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]);
+    let second = check_digit(&d[..13], &[6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]);
+    d[12] == first && d[13] == second
+}
+
+/// Valida o documento e devolve o tipo e a versão apenas com dígitos.
+pub fn validate(value: &str) -> Result<(DocumentKind, String), AppError> {
+    let normalized = normalize(value);
+    match normalized.len() {
+        11 if is_valid_cpf(&normalized) => Ok((DocumentKind::Cpf, normalized)),
+        11 => Err(AppError::validation("CPF inválido: dígitos verificadores não conferem")),
+        14 if is_valid_cnpj(&normalized) => Ok((DocumentKind::Cnpj, normalized)),
+        14 => Err(AppError::validation("CNPJ inválido: dígitos verificadores não conferem")),
+        _ => Err(AppError::validation("Documento deve ser um CPF (11 dígitos) ou CNPJ (14 dígitos)")),
+    }
+}
+
+/// Formata um documento para exibição (000.000.000-00 ou 00.000.000/0000-00).
+/// Valores que não são CPF nem CNPJ são devolvidos sem alteração.
+pub fn format(value: &str) -> String {
+    let d = normalize(value);
+    match d.len() {
+        11 => format!("{}.{}.{}-{}", &d[0..3], &d[3..6], &d[6..9], &d[9..11]),
+        14 => format!("{}.{}.{}/{}-{}", &d[0..2], &d[2..5], &d[5..8], &d[8..12], &d[12..14]),
+        _ => value.to_string(),
+    }
+}
@@ -0,0 +1,187 @@
+//! Criptografia do banco com o SQLCipher (feature `sqlcipher`).
+//!
+//! É opcional: o banco nasce sem senha e o administrador pode cifrá-lo com
+//! [`encrypt_database`]. A partir daí o app abre bloqueado e pede a senha
+//! na inicialização (veja [`crate::startup`]). Os backups de um banco
+//! cifrado saem cifrados com a mesma senha.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sea_orm::sqlx::sqlite::SqlitePoolOptions;
+use sea_orm::{ConnectionTrait, DatabaseConnection, SqlxSqliteConnector};
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::backup;
+use crate::db::{self, Session};
+use crate::error::AppError;
+
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct EncryptionStatus {
+    /// Se o app foi compilado com o SQLCipher.
+    pub supported: bool,
+    pub encrypted: bool,
+}
+
+/// Resultado de [`encrypt_database`].
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct EncryptedDatabase {
+    /// Backups sem criptografia que foram cifrados junto com o banco.
+    pub encrypted_backups: Vec<String>,
+    /// Backups que não puderam ser cifrados (ex.: arquivo danificado) e
+    /// continuam legíveis na pasta.
+    pub skipped_backups: Vec<String>,
+}
+
+pub fn status(session: &Session) -> EncryptionStatus {
+    EncryptionStatus {
+        supported: cfg!(feature = "sqlcipher"),
+        encrypted: session.key.is_some(),
+    }
+}
+
+pub fn validate_passphrase(passphrase: &str) -> Result<(), AppError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(AppError::validation(format!(
+            "A senha do banco deve ter pelo menos {} caracteres",
+            MIN_PASSPHRASE_LEN
+        ))
+        .with_field("passphrase"));
+    }
+    Ok(())
+}
+
+/// Cifra o banco `database`, ainda sem senha, com `passphrase` e reabre a
+/// sessão já cifrada. O arquivo original só é substituído depois que a
+/// cópia cifrada é conferida. Os backups sem criptografia da pasta
+/// `backup_dir` também são cifrados, para não deixar cópias legíveis.
+pub async fn encrypt_database(
+    session: &mut Session,
+    database: &Path,
+    backup_dir: &Path,
+    passphrase: &str,
+) -> Result<EncryptedDatabase, AppError> {
+    if !cfg!(feature = "sqlcipher") {
+        return Err(AppError::validation(
+            "Este sistema foi compilado sem suporte a banco criptografado",
+        ));
+    }
+    if session.key.is_some() || db::is_encrypted(database) {
+        return Err(AppError::conflict("O banco de dados já está criptografado"));
+    }
+    validate_passphrase(passphrase)?;
+
+    let temp = sibling(database, "cifrando");
+    std::mem::replace(&mut session.connection, DatabaseConnection::Disconnected)
+        .close()
+        .await?;
+    let swapped = match export_encrypted(database, &temp, passphrase).await {
+        Ok(()) => replace_file(&temp, database),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = swapped {
+        session.connection = reopen(database, None).await?;
+        return Err(e);
+    }
+    session.connection = reopen(database, Some(passphrase)).await?;
+    session.key = Some(passphrase.to_string());
+    log::info!("Banco de dados criptografado");
+
+    let mut result = EncryptedDatabase {
+        encrypted_backups: Vec::new(),
+        skipped_backups: Vec::new(),
+    };
+    for file in backup::list_backups(backup_dir) {
+        let path = backup_dir.join(&file.name);
+        if db::is_encrypted(&path) {
+            continue;
+        }
+        let temp = sibling(&path, "cifrando");
+        let encrypted = match export_encrypted(&path, &temp, passphrase).await {
+            Ok(()) => replace_file(&temp, &path),
+            Err(e) => Err(e),
+        };
+        match encrypted {
+            Ok(()) => result.encrypted_backups.push(file.name),
+            Err(e) => {
+                log::error!("Backup {} não foi criptografado: {}", file.name, e);
+                result.skipped_backups.push(file.name);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Copia o arquivo `source`, sem senha, para `target` cifrado com `key`
+/// (`sqlcipher_export`) e confere a cópia.
+async fn export_encrypted(source: &Path, target: &Path, key: &str) -> Result<(), AppError> {
+    let _ = fs::remove_file(target);
+
+    // Uma única conexão: o ATTACH vale só para a conexão que o executou.
+    // `rwc` porque o modo de abertura também vale para o arquivo anexado.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(db::connect_options(source, "rwc", None)?)
+        .await
+        .map_err(|e| AppError::internal(format!("Não foi possível abrir {}: {}", source.display(), e)))?;
+    let conn = SqlxSqliteConnector::from_sqlx_sqlite_pool(pool);
+
+    let result = async {
+        let version = db::schema_version(&conn).await?;
+        conn.execute_unprepared(&format!(
+            "ATTACH DATABASE {} AS encrypted KEY {}",
+            db::quote(&target.display().to_string()),
+            db::quote(key)
+        ))
+        .await?;
+        conn.execute_unprepared("SELECT sqlcipher_export('encrypted')").await?;
+        conn.execute_unprepared(&format!("PRAGMA encrypted.user_version = {}", version))
+            .await?;
+        conn.execute_unprepared("DETACH DATABASE encrypted").await?;
+        Ok::<_, sea_orm::DbErr>(())
+    }
+    .await;
+    let _ = conn.close().await;
+
+    let checked = match result {
+        Ok(()) => backup::verify(target, Some(key)).await,
+        Err(e) => Err(AppError::internal(format!(
+            "Não foi possível criptografar {}: {}",
+            source.display(),
+            e
+        ))),
+    };
+    if checked.is_err() {
+        let _ = fs::remove_file(target);
+    }
+    checked
+}
+
+/// Coloca `temp` no lugar de `target`, descartando os `-wal`/`-shm` do
+/// arquivo antigo (o conteúdo deles já está na cópia).
+fn replace_file(temp: &Path, target: &Path) -> Result<(), AppError> {
+    for ext in ["wal", "shm"] {
+        let _ = fs::remove_file(sibling_with(target, "-", ext));
+    }
+    fs::rename(temp, target)?;
+    Ok(())
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    sibling_with(path, ".", suffix)
+}
+
+fn sibling_with(path: &Path, separator: &str, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!("{}{}", separator, suffix));
+    PathBuf::from(name)
+}
+
+async fn reopen(database: &Path, key: Option<&str>) -> Result<DatabaseConnection, AppError> {
+    db::open(database, key)
+        .await
+        .map_err(|e| AppError::internal(format!("{:#}", e)))
+}
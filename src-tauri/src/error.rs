@@ -0,0 +1,169 @@
+//! Erro devolvido pelos comandos. Chega ao frontend como
+//! `{ code, message, field }`, com a mensagem pronta para exibição.
+
+use sea_orm::{DbErr, SqlErr};
+use serde::{Serialize, Serializer};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AppError {
+    #[error("{0}")]
+    NotFound(String),
+    /// Dado informado inválido; `field` indica o campo do formulário, se houver.
+    #[error("{message}")]
+    Validation { field: Option<String>, message: String },
+    /// Conflito com dados existentes (ex.: cadastro duplicado).
+    #[error("{message}")]
+    Conflict { field: Option<String>, message: String },
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    Database(String),
+    /// Falhas fora do banco (arquivos, PDF, serviços externos).
+    #[error("{0}")]
+    Internal(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    Validation,
+    Conflict,
+    Unauthorized,
+    Database,
+    Internal,
+}
+
+/// Formato serializado de [`AppError`].
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(rename = "AppError")]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    pub message: String,
+    pub field: Option<String>,
+}
+
+impl AppError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        AppError::NotFound(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        AppError::Validation { field: None, message: message.into() }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        AppError::Conflict { field: None, message: message.into() }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        AppError::Unauthorized(message.into())
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        AppError::Internal(message.into())
+    }
+
+    /// Associa o erro de validação ou conflito ao campo do formulário.
+    pub fn with_field(self, field: &str) -> Self {
+        match self {
+            AppError::Validation { message, .. } => AppError::Validation { field: Some(field.into()), message },
+            AppError::Conflict { message, .. } => AppError::Conflict { field: Some(field.into()), message },
+            other => other,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::Validation { .. } => ErrorCode::Validation,
+            AppError::Conflict { .. } => ErrorCode::Conflict,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::Database(_) => ErrorCode::Database,
+            AppError::Internal(_) => ErrorCode::Internal,
+        }
+    }
+
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            AppError::Validation { field, .. } | AppError::Conflict { field, .. } => field.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn body(&self) -> ErrorBody {
+        ErrorBody {
+            code: self.code(),
+            message: self.to_string(),
+            field: self.field().map(str::to_string),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.body().serialize(serializer)
+    }
+}
+
+/// Coluna da mensagem do SQLite "UNIQUE constraint failed: users.username".
+fn unique_column(message: &str) -> Option<String> {
+    let columns = message.split("constraint failed:").nth(1)?;
+    let first = columns.split(',').next()?.trim();
+    first.rsplit('.').next().map(str::to_string)
+}
+
+fn unique_message(column: Option<&str>) -> String {
+    match column {
+        Some("username") => "Nome de usuário já está em uso".into(),
+        Some("document") => "Documento já cadastrado".into(),
+        Some("name") => "Já existe um cadastro com este nome".into(),
+        Some(c) => format!("Já existe um cadastro com o mesmo valor em {}", c),
+        None => "Já existe um cadastro com os mesmos dados".into(),
+    }
+}
+
+impl From<DbErr> for AppError {
+    fn from(err: DbErr) -> Self {
+        match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(message)) => {
+                let column = unique_column(&message);
+                AppError::Conflict {
+                    message: unique_message(column.as_deref()),
+                    field: column,
+                }
+            }
+            Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+                AppError::conflict("O registro está vinculado a outros dados")
+            }
+            _ => match err {
+                DbErr::RecordNotFound(_) | DbErr::RecordNotUpdated => {
+                    AppError::not_found("Registro não encontrado")
+                }
+                other => {
+                    log::error!("Erro no banco de dados: {}", other);
+                    AppError::Database("Erro ao acessar o banco de dados".into())
+                }
+            },
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        AppError::Internal(format!("Erro ao acessar arquivo: {}", err))
+    }
+}
+
+impl From<genpdf::error::Error> for AppError {
+    fn from(err: genpdf::error::Error) -> Self {
+        AppError::Internal(format!("Erro ao gerar PDF: {}", err))
+    }
+}
+
+impl From<bcrypt::BcryptError> for AppError {
+    fn from(err: bcrypt::BcryptError) -> Self {
+        AppError::Internal(format!("Erro ao processar senha: {}", err))
+    }
+}
@@ -0,0 +1,152 @@
+//! Exportação de vendas, produtos, clientes e contas a receber em planilha
+//! (veja [`crate::spreadsheet`]). Vendas e contas a receber seguem os mesmos
+//! filtros do relatório de vendas.
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::db::entities::{category, client, product, sale};
+use crate::error::AppError;
+use crate::reports;
+use crate::sales::{client_name, RECEIVABLE_PAYMENT_METHOD};
+use crate::spreadsheet::{self, Cell, ExportedFile, Format, Sheet};
+use crate::timezone;
+
+pub async fn sales_sheet(
+    db: &DatabaseConnection,
+    start_iso: &str,
+    end_iso: &str,
+    payment_method: &str,
+) -> Result<Sheet, AppError> {
+    let (start, end) = reports::parse_period(start_iso, end_iso)?;
+    let tz = timezone::business_timezone(db).await;
+    let sales = reports::report_sales(db, reports::period_condition(start, end, payment_method)).await?;
+
+    let mut sheet = Sheet::new("Vendas", &["Nº", "Data", "Cliente", "Itens", "Pagamento", "Total"]);
+    for sale in sales {
+        sheet.rows.push(vec![
+            Cell::Integer(sale.id.into()),
+            local(tz, sale.created_at),
+            sale.client_name.into(),
+            items_summary(&sale.items).into(),
+            sale.payment_method.into(),
+            Cell::Money(sale.total),
+        ]);
+    }
+    Ok(sheet)
+}
+
+pub async fn products_sheet(db: &DatabaseConnection) -> Result<Sheet, AppError> {
+    let products = product::Entity::find()
+        .find_also_related(category::Entity)
+        .order_by_asc(product::Column::Name)
+        .all(db)
+        .await?;
+
+    let mut sheet = Sheet::new("Produtos", &["Código", "Produto", "Categoria", "Preço", "Estoque", "Custo"]);
+    for (product, category) in products {
+        sheet.rows.push(vec![
+            Cell::Integer(product.id.into()),
+            product.name.into(),
+            category.map(|c| c.name).unwrap_or(product.category).into(),
+            Cell::Money(product.price),
+            Cell::Integer(product.stock_quantity.into()),
+            Cell::Money(product.cost_price),
+        ]);
+    }
+    Ok(sheet)
+}
+
+pub async fn clients_sheet(db: &DatabaseConnection) -> Result<Sheet, AppError> {
+    let tz = timezone::business_timezone(db).await;
+    let clients = client::Entity::find()
+        .order_by_asc(client::Column::Name)
+        .all(db)
+        .await?;
+
+    let mut sheet = Sheet::new(
+        "Clientes",
+        &["Código", "Nome", "Telefone", "CPF/CNPJ", "CEP", "Endereço", "Cidade", "UF", "Observações", "Cliente desde"],
+    );
+    for client in clients {
+        sheet.rows.push(vec![
+            Cell::Integer(client.id.into()),
+            client.name.into(),
+            client.phone.into(),
+            client.document.into(),
+            client.cep.into(),
+            client.address.into(),
+            client.city.into(),
+            client.state.into(),
+            client.observations.into(),
+            local(tz, client.created_at),
+        ]);
+    }
+    Ok(sheet)
+}
+
+/// Vendas a prazo (fiado) do período, com o contato do cliente.
+pub async fn receivables_sheet(db: &DatabaseConnection, start_iso: &str, end_iso: &str) -> Result<Sheet, AppError> {
+    let (start, end) = reports::parse_period(start_iso, end_iso)?;
+    let tz = timezone::business_timezone(db).await;
+    let sales = sale::Entity::find()
+        .filter(reports::period_condition(start, end, RECEIVABLE_PAYMENT_METHOD))
+        .find_also_related(client::Entity)
+        .order_by_asc(sale::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut sheet = Sheet::new("Contas a receber", &["Nº", "Data", "Cliente", "Telefone", "CPF/CNPJ", "Valor"]);
+    for (sale, client) in sales {
+        let phone = client.as_ref().and_then(|c| c.phone.clone());
+        let document = client.as_ref().and_then(|c| c.document.clone());
+        sheet.rows.push(vec![
+            Cell::Integer(sale.id.into()),
+            local(tz, sale.created_at),
+            client_name(client).into(),
+            phone.into(),
+            document.into(),
+            Cell::Money(sale.total),
+        ]);
+    }
+    Ok(sheet)
+}
+
+/// Gera o arquivo da planilha; o nome leva a aba e a data de hoje.
+pub async fn export(db: &DatabaseConnection, sheet: &Sheet, format: Format) -> Result<ExportedFile, AppError> {
+    let tz = timezone::business_timezone(db).await;
+    let bytes = spreadsheet::write(sheet, format)?;
+    Ok(ExportedFile {
+        file_name: format!(
+            "{}-{}.{}",
+            sheet.name.to_lowercase().replace(' ', "-"),
+            timezone::local_date(tz, Utc::now()).format("%Y-%m-%d"),
+            format.extension()
+        ),
+        content: general_purpose::STANDARD.encode(bytes),
+    })
+}
+
+fn local(tz: Tz, at: DateTime<Utc>) -> Cell {
+    Cell::DateTime(at.with_timezone(&tz).naive_local())
+}
+
+/// Itens gravados em JSON (`[{ name, qty, price }]`) como texto, ex.:
+/// `Água 20L (x2), Gás P13 (x1)`. Itens em outro formato saem como estão.
+pub fn items_summary(items: &str) -> String {
+    let Ok(serde_json::Value::Array(list)) = serde_json::from_str::<serde_json::Value>(items) else {
+        return items.to_string();
+    };
+    list.iter()
+        .map(|item| {
+            let name = item.get("name").and_then(|n| n.as_str()).unwrap_or("Item");
+            match item.get("qty").and_then(|q| q.as_f64()) {
+                Some(qty) => format!("{} (x{})", name, qty),
+                None => name.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
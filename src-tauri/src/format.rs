@@ -0,0 +1,50 @@
+//! Formatação de números no padrão brasileiro (pt-BR), para PDFs e
+//! exportações. Os comandos devolvem os valores numéricos e o frontend
+//! formata para exibição.
+
+/// Número com separador de milhar `.` e decimal `,`, ex.: `1.234,56`.
+pub fn decimal(value: f64, places: usize) -> String {
+    let formatted = format!("{:.*}", places, value.abs());
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+    for (i, c) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push('.');
+        }
+        grouped.push(c);
+    }
+    if let Some(frac) = frac_part {
+        grouped.push(',');
+        grouped.push_str(frac);
+    }
+
+    // Evita "-0,00" quando o valor arredondado é zero
+    if value < 0.0 && grouped.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        format!("-{}", grouped)
+    } else {
+        grouped
+    }
+}
+
+/// Valor em reais, ex.: `R$ 1.234,56` ou `-R$ 10,00`.
+pub fn currency(value: f64) -> String {
+    let amount = decimal(value, 2);
+    match amount.strip_prefix('-') {
+        Some(abs) => format!("-R$ {}", abs),
+        None => format!("R$ {}", amount),
+    }
+}
+
+/// Variação (0.123 = 12,3%) com sinal, ex.: `+12,3%`.
+pub fn change(ratio: f64, places: usize) -> String {
+    let pct = decimal(ratio * 100.0, places);
+    if pct.starts_with('-') {
+        format!("{}%", pct)
+    } else {
+        format!("+{}%", pct)
+    }
+}
@@ -0,0 +1,485 @@
+//! Importação de clientes e produtos a partir de planilhas (CSV/XLSX).
+//!
+//! São duas etapas com os mesmos argumentos: [`preview`] lê a planilha,
+//! aplica o mapeamento de colunas e valida cada linha sem gravar nada;
+//! [`commit`] refaz a validação dentro de uma transação e grava as linhas
+//! válidas. Linhas com erro ou duplicadas (mesmo documento ou telefone de
+//! um cliente, mesmo nome de um produto) ficam de fora e voltam no
+//! resultado.
+
+use std::collections::{BTreeMap, HashMap};
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::clients::{self, ClientInput};
+use crate::db::entities::{category, client, product};
+use crate::error::AppError;
+use crate::products::{self, ProductInput};
+use crate::spreadsheet::{self, normalize_header, Format};
+use crate::{address, document};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    Clients,
+    Products,
+}
+
+/// Campo de destino -> índice da coluna na planilha.
+pub type ColumnMapping = BTreeMap<String, usize>;
+
+/// Campos de cada tipo e os cabeçalhos (normalizados) reconhecidos
+/// automaticamente para eles.
+const CLIENT_FIELDS: &[(&str, &[&str])] = &[
+    ("name", &["nome", "cliente", "razao social"]),
+    ("phone", &["telefone", "celular", "fone", "whatsapp"]),
+    ("document", &["cpf", "cnpj", "cpf/cnpj", "documento"]),
+    ("cep", &["cep"]),
+    ("city", &["cidade", "municipio"]),
+    ("state", &["uf", "estado"]),
+    ("street", &["rua", "logradouro", "endereco"]),
+    ("number", &["numero", "nº", "n"]),
+    ("complement", &["complemento"]),
+    ("neighborhood", &["bairro"]),
+    ("observations", &["observacoes", "observacao", "obs"]),
+];
+
+const PRODUCT_FIELDS: &[(&str, &[&str])] = &[
+    ("name", &["nome", "produto", "descricao"]),
+    ("price", &["preco", "valor", "preco de venda"]),
+    ("stock_quantity", &["estoque", "quantidade", "qtd"]),
+    ("category", &["categoria"]),
+    ("min_stock", &["estoque minimo", "minimo"]),
+    ("reorder_qty", &["lote", "lote de compra", "reposicao"]),
+    ("cost_price", &["custo", "preco de custo", "custo unitario"]),
+];
+
+impl ImportKind {
+    fn fields(self) -> &'static [(&'static str, &'static [&'static str])] {
+        match self {
+            ImportKind::Clients => CLIENT_FIELDS,
+            ImportKind::Products => PRODUCT_FIELDS,
+        }
+    }
+
+    fn required(self) -> &'static [&'static str] {
+        match self {
+            ImportKind::Clients => &["name"],
+            ImportKind::Products => &["name", "price", "category"],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ImportIssue {
+    /// Campo de destino com problema; `null` quando é a linha toda.
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ImportRow {
+    /// Linha na planilha (o cabeçalho é a linha 1).
+    pub line: usize,
+    /// Valores lidos, por campo de destino.
+    pub values: BTreeMap<String, String>,
+    pub errors: Vec<ImportIssue>,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ImportPreview {
+    pub headers: Vec<String>,
+    /// Mapeamento usado: o informado ou o sugerido pelos cabeçalhos.
+    pub mapping: ColumnMapping,
+    /// Campos obrigatórios sem coluna; enquanto houver, as linhas não são
+    /// validadas e `rows` vem vazio.
+    pub missing_fields: Vec<String>,
+    pub rows: Vec<ImportRow>,
+    pub valid_rows: usize,
+    pub invalid_rows: usize,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ImportResult {
+    pub imported: usize,
+    /// Linhas que não foram gravadas, com os motivos.
+    pub rejected: Vec<ImportRow>,
+}
+
+/// Sugere o mapeamento comparando os cabeçalhos com os nomes conhecidos
+/// de cada campo.
+pub fn suggest_mapping(kind: ImportKind, headers: &[String]) -> ColumnMapping {
+    let headers: Vec<String> = headers.iter().map(|h| normalize_header(h)).collect();
+    kind.fields()
+        .iter()
+        .filter_map(|(field, aliases)| {
+            headers
+                .iter()
+                .position(|h| h == field || aliases.contains(&h.as_str()))
+                .map(|i| (field.to_string(), i))
+        })
+        .collect()
+}
+
+pub async fn preview(
+    db: &DatabaseConnection,
+    kind: ImportKind,
+    format: Format,
+    content: &[u8],
+    mapping: Option<ColumnMapping>,
+) -> Result<ImportPreview, AppError> {
+    let (headers, lines) = parse(content, format)?;
+    let mapping = mapping.unwrap_or_else(|| suggest_mapping(kind, &headers));
+    check_columns(kind, &headers, &mapping)?;
+
+    let missing_fields = missing_fields(kind, &mapping);
+    let rows: Vec<ImportRow> = if missing_fields.is_empty() {
+        validate(db, kind, &lines, &mapping).await?.into_iter().map(|(row, _)| row).collect()
+    } else {
+        Vec::new()
+    };
+    let invalid_rows = rows.iter().filter(|r| !r.errors.is_empty()).count();
+    Ok(ImportPreview {
+        headers,
+        mapping,
+        missing_fields,
+        valid_rows: rows.len() - invalid_rows,
+        invalid_rows,
+        rows,
+    })
+}
+
+pub async fn commit(
+    db: &DatabaseConnection,
+    kind: ImportKind,
+    format: Format,
+    content: &[u8],
+    mapping: ColumnMapping,
+) -> Result<ImportResult, AppError> {
+    let (headers, lines) = parse(content, format)?;
+    check_columns(kind, &headers, &mapping)?;
+    if let Some(missing) = missing_fields(kind, &mapping).first() {
+        return Err(AppError::validation(format!("Selecione a coluna do campo obrigatório \"{}\"", missing))
+            .with_field("mapping"));
+    }
+
+    let txn = db.begin().await?;
+    let mut result = ImportResult { imported: 0, rejected: Vec::new() };
+    for (row, record) in validate(&txn, kind, &lines, &mapping).await? {
+        match record {
+            Record::Client(input) if row.errors.is_empty() => {
+                clients::create_client(&txn, input).await?;
+                result.imported += 1;
+            }
+            Record::Product(input) if row.errors.is_empty() => {
+                products::create_product(&txn, input).await?;
+                result.imported += 1;
+            }
+            _ => result.rejected.push(row),
+        }
+    }
+    txn.commit().await?;
+    log::info!("Importação de {:?}: {} gravados, {} rejeitados", kind, result.imported, result.rejected.len());
+    Ok(result)
+}
+
+/// Linha da planilha com o número dela.
+type Line = (usize, Vec<String>);
+
+/// Cabeçalho e linhas com algum valor.
+fn parse(content: &[u8], format: Format) -> Result<(Vec<String>, Vec<Line>), AppError> {
+    let mut rows = spreadsheet::read(content, format)?.into_iter().enumerate();
+    let headers = rows
+        .next()
+        .map(|(_, headers)| headers)
+        .filter(|headers| headers.iter().any(|h| !h.is_empty()))
+        .ok_or_else(|| AppError::validation("A planilha está vazia"))?;
+    let lines = rows
+        .filter(|(_, cells)| cells.iter().any(|c| !c.is_empty()))
+        .map(|(i, cells)| (i + 1, cells))
+        .collect();
+    Ok((headers, lines))
+}
+
+fn check_columns(kind: ImportKind, headers: &[String], mapping: &ColumnMapping) -> Result<(), AppError> {
+    for (field, column) in mapping {
+        if !kind.fields().iter().any(|(f, _)| f == field) {
+            return Err(AppError::validation(format!("Campo desconhecido: {}", field)).with_field("mapping"));
+        }
+        if *column >= headers.len() {
+            return Err(AppError::validation(format!("Coluna {} não existe na planilha", column + 1))
+                .with_field("mapping"));
+        }
+    }
+    Ok(())
+}
+
+fn missing_fields(kind: ImportKind, mapping: &ColumnMapping) -> Vec<String> {
+    kind.required()
+        .iter()
+        .filter(|f| !mapping.contains_key(**f))
+        .map(|f| f.to_string())
+        .collect()
+}
+
+enum Record {
+    Client(ClientInput),
+    Product(ProductInput),
+}
+
+/// Registros já gravados ou aceitos em linhas anteriores, pela chave de
+/// duplicidade, com a descrição de onde estão.
+type Seen = HashMap<String, String>;
+
+async fn validate<C: ConnectionTrait>(
+    db: &C,
+    kind: ImportKind,
+    lines: &[Line],
+    mapping: &ColumnMapping,
+) -> Result<Vec<(ImportRow, Record)>, AppError> {
+    let mut validated = Vec::with_capacity(lines.len());
+    match kind {
+        ImportKind::Clients => {
+            let (mut documents, mut phones) = (Seen::new(), Seen::new());
+            for c in client::Entity::find().all(db).await? {
+                let owner = format!("o cliente {}", c.name);
+                if let Some(d) = c.document {
+                    documents.insert(d, owner.clone());
+                }
+                if let Some(p) = c.phone.map(|p| document::normalize(&p)).filter(|p| !p.is_empty()) {
+                    phones.insert(p, owner);
+                }
+            }
+            for (line, cells) in lines {
+                let mut row = new_row(*line, cells, mapping);
+                let input = client_input(&mut row, &documents, &phones);
+                if row.errors.is_empty() {
+                    let owner = format!("a linha {}", line);
+                    if let Some(d) = &input.document {
+                        documents.insert(d.clone(), owner.clone());
+                    }
+                    if let Some(p) = &input.phone {
+                        phones.insert(document::normalize(p), owner);
+                    }
+                }
+                validated.push((row, Record::Client(input)));
+            }
+        }
+        ImportKind::Products => {
+            let categories = category::Entity::find().all(db).await?;
+            let mut names: Seen = product::Entity::find()
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|p| (normalize_header(&p.name), "um produto cadastrado".to_string()))
+                .collect();
+            for (line, cells) in lines {
+                let mut row = new_row(*line, cells, mapping);
+                let input = product_input(&mut row, &categories, &names);
+                if row.errors.is_empty() {
+                    names.insert(normalize_header(&input.name), format!("a linha {}", line));
+                }
+                validated.push((row, Record::Product(input)));
+            }
+        }
+    }
+    Ok(validated)
+}
+
+fn new_row(line: usize, cells: &[String], mapping: &ColumnMapping) -> ImportRow {
+    let values = mapping
+        .iter()
+        .filter_map(|(field, column)| {
+            let value = cells.get(*column)?.trim();
+            (!value.is_empty()).then(|| (field.clone(), value.to_string()))
+        })
+        .collect();
+    ImportRow { line, values, errors: Vec::new() }
+}
+
+impl ImportRow {
+    fn value(&self, field: &str) -> Option<String> {
+        self.values.get(field).cloned()
+    }
+
+    fn error(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(ImportIssue { field: Some(field.to_string()), message: message.into() });
+    }
+}
+
+fn client_input(row: &mut ImportRow, documents: &Seen, phones: &Seen) -> ClientInput {
+    let name = row.value("name").unwrap_or_default();
+    if name.is_empty() {
+        row.error("name", "Nome é obrigatório");
+    }
+
+    let document = row.value("document").and_then(|value| match import_document(&value) {
+        Ok(normalized) => {
+            if let Some(owner) = documents.get(&normalized) {
+                row.error("document", format!("{} já cadastrado para {}", document::format(&normalized), owner));
+            }
+            Some(normalized)
+        }
+        Err(e) => {
+            row.error("document", e.to_string());
+            None
+        }
+    });
+
+    let phone = row.value("phone").and_then(|value| match clients::normalize_phone(&value) {
+        Ok(phone) => {
+            if let Some(owner) = phones.get(&document::normalize(&phone)) {
+                row.error("phone", format!("Telefone {} já cadastrado para {}", phone, owner));
+            }
+            Some(phone)
+        }
+        Err(e) => {
+            row.error("phone", e.to_string());
+            None
+        }
+    });
+
+    let cep = row.value("cep");
+    if let Some(Err(e)) = cep.as_deref().map(address::normalize_cep) {
+        row.error("cep", e.to_string());
+    }
+    let state = row.value("state");
+    if let Some(Err(e)) = state.as_deref().map(address::normalize_uf) {
+        row.error("state", e.to_string());
+    }
+
+    ClientInput {
+        name,
+        phone,
+        document,
+        cep,
+        city: row.value("city"),
+        state,
+        street: row.value("street"),
+        number: row.value("number"),
+        complement: row.value("complement"),
+        neighborhood: row.value("neighborhood"),
+        observations: row.value("observations"),
+    }
+}
+
+/// Valida o CPF/CNPJ. Planilhas costumam guardar o documento como número
+/// e perder os zeros à esquerda, que são recolocados aqui.
+fn import_document(value: &str) -> Result<String, AppError> {
+    let digits = document::normalize(value);
+    let padded = match digits.len() {
+        9 | 10 => format!("{:0>11}", digits),
+        12 | 13 => format!("{:0>14}", digits),
+        _ => digits,
+    };
+    document::validate(&padded).map(|(_, normalized)| normalized)
+}
+
+fn product_input(row: &mut ImportRow, categories: &[category::Model], names: &Seen) -> ProductInput {
+    let name = row.value("name").unwrap_or_default();
+    if name.is_empty() {
+        row.error("name", "Nome é obrigatório");
+    } else if let Some(owner) = names.get(&normalize_header(&name)) {
+        row.error("name", format!("Produto \"{}\" já existe em {}", name, owner));
+    }
+
+    let price = match row.value("price").map(|p| parse_decimal(&p)) {
+        Some(Some(price)) if price > 0.0 => (price * 100.0).round() / 100.0,
+        Some(Some(_)) => {
+            row.error("price", "Preço deve ser maior que zero");
+            0.0
+        }
+        Some(None) => {
+            row.error("price", format!("Preço inválido: {}", row.value("price").unwrap_or_default()));
+            0.0
+        }
+        None => {
+            row.error("price", "Preço é obrigatório");
+            0.0
+        }
+    };
+
+    let stock_quantity = quantity(row, "stock_quantity", "Estoque").unwrap_or(0);
+    let min_stock = quantity(row, "min_stock", "Estoque mínimo");
+    let reorder_qty = quantity(row, "reorder_qty", "Lote de compra");
+    let cost_price = match row.value("cost_price").map(|c| parse_decimal(&c)) {
+        None => None,
+        Some(Some(cost)) if cost >= 0.0 => Some((cost * 100.0).round() / 100.0),
+        Some(_) => {
+            row.error("cost_price", format!("Custo inválido: {}", row.value("cost_price").unwrap_or_default()));
+            None
+        }
+    };
+
+    let category_name = row.value("category").unwrap_or_default();
+    let category = categories
+        .iter()
+        .find(|c| normalize_header(&c.name) == normalize_header(&category_name));
+    if category_name.is_empty() {
+        row.error("category", "Categoria é obrigatória");
+    } else if category.is_none() {
+        row.error("category", format!("Categoria \"{}\" não encontrada", category_name));
+    }
+
+    ProductInput {
+        name,
+        price,
+        stock_quantity,
+        category: category.map(|c| c.name.clone()).unwrap_or(category_name),
+        category_id: category.map(|c| c.id),
+        min_stock,
+        reorder_qty,
+        cost_price,
+    }
+}
+
+/// Quantidade inteira não negativa da coluna, se preenchida.
+fn quantity(row: &mut ImportRow, field: &str, label: &str) -> Option<i32> {
+    match row.value(field).map(|q| parse_decimal(&q)) {
+        None => None,
+        Some(Some(q)) if q >= 0.0 && q.fract() == 0.0 && q <= i32::MAX as f64 => Some(q as i32),
+        Some(_) => {
+            row.error(field, format!("{} deve ser um número inteiro não negativo", label));
+            None
+        }
+    }
+}
+
+/// Número em pt-BR (`1.234,56`, `R$ 1.500`, `12,50`). O ponto só separa
+/// milhares: `12.5` é recusado em vez de virar 12,5 ou 125. Os números das
+/// células do XLSX já chegam com vírgula (veja `spreadsheet::read_xlsx`).
+pub fn parse_decimal(value: &str) -> Option<f64> {
+    let cleaned: String = value
+        .trim()
+        .trim_start_matches("R$")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let (integer, fraction) = match cleaned.split_once(',') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (cleaned.as_str(), None),
+    };
+    let (sign, digits) = match integer.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", integer),
+    };
+    let mut groups = digits.split('.');
+    let first = groups.next().unwrap_or_default();
+    let grouped = digits.contains('.');
+    if grouped && !(1..=3).contains(&first.len()) {
+        return None;
+    }
+    let mut normalized = format!("{}{}", sign, first);
+    for group in groups {
+        if group.len() != 3 || !group.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        normalized.push_str(group);
+    }
+    if let Some(fraction) = fraction {
+        normalized = format!("{}.{}", normalized, fraction);
+    }
+    normalized.parse::<f64>().ok().filter(|v| v.is_finite())
+}
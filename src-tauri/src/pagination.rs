@@ -0,0 +1,111 @@
+//! Paginação, busca e ordenação para as listagens.
+
+use sea_orm::sea_query::LikeExpr;
+use sea_orm::{
+    ConnectionTrait, EntityTrait, FromQueryResult, Order, PaginatorTrait, QueryOrder, Select,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::error::AppError;
+
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+pub const MAX_PAGE_SIZE: u64 = 200;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<SortDirection> for Order {
+    fn from(dir: SortDirection) -> Self {
+        match dir {
+            SortDirection::Asc => Order::Asc,
+            SortDirection::Desc => Order::Desc,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    #[ts(type = "number")]
+    pub total: u64,
+    #[ts(type = "number")]
+    pub page: u64,
+    #[ts(type = "number")]
+    pub page_size: u64,
+    #[ts(type = "number")]
+    pub total_pages: u64,
+}
+
+/// Texto de busca já aparado; `None` quando vazio.
+pub fn search_term(search: Option<String>) -> Option<String> {
+    search
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Padrão `LIKE` que encontra o termo em qualquer posição. `%` e `_`
+/// digitados na busca são procurados literalmente (`ESCAPE '\'`).
+pub fn like_pattern(term: &str) -> LikeExpr {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    LikeExpr::new(format!("%{}%", escaped)).escape('\\')
+}
+
+/// Ordena pela coluna solicitada, se ela estiver entre as permitidas.
+/// A chave primária é sempre usada como critério de desempate para que
+/// a paginação seja estável.
+pub fn apply_sort<E, C>(
+    mut select: Select<E>,
+    sort_by: Option<&str>,
+    sort_dir: Option<SortDirection>,
+    allowed: &[(&str, C)],
+    tiebreaker: C,
+) -> Result<Select<E>, AppError>
+where
+    E: EntityTrait,
+    C: sea_orm::ColumnTrait + Copy,
+{
+    let dir: Order = sort_dir.unwrap_or_default().into();
+    if let Some(key) = sort_by.filter(|k| !k.is_empty()) {
+        let column = allowed
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, c)| *c)
+            .ok_or_else(|| AppError::validation(format!("Ordenação inválida: {}", key)).with_field("sort_by"))?;
+        select = select.order_by(column, dir.clone());
+    }
+    Ok(select.order_by(tiebreaker, dir))
+}
+
+/// Executa a consulta paginada (páginas começam em 0).
+pub async fn fetch_page<C, E, M>(
+    db: &C,
+    select: Select<E>,
+    page: Option<u64>,
+    page_size: Option<u64>,
+) -> Result<Page<M>, AppError>
+where
+    C: ConnectionTrait,
+    E: EntityTrait<Model = M>,
+    M: FromQueryResult + Sized + Send + Sync,
+{
+    let page = page.unwrap_or(0);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let paginator = select.paginate(db, page_size);
+    let counts = paginator.num_items_and_pages().await?;
+    let items = paginator.fetch_page(page).await?;
+
+    Ok(Page {
+        items,
+        total: counts.number_of_items,
+        page,
+        page_size,
+        total_pages: counts.number_of_pages,
+    })
+}
@@ -0,0 +1,334 @@
+//! Notas de venda em PDF (duas vias lado a lado) e relatório de dados do
+//! cliente (LGPD).
+
+use genpdf::elements::{Break, FrameCellDecorator, Image, LinearLayout, Paragraph, TableLayout};
+use genpdf::{style, Alignment, Element};
+use sea_orm::DatabaseConnection;
+
+use crate::error::AppError;
+use crate::privacy::ClientDataExport;
+use crate::{format, pix};
+
+const FONT_DIR: &str = "/usr/share/fonts/Adwaita";
+const FONT_FILE: &str = "AdwaitaSans-Regular.ttf";
+
+/// Linhas de itens impressas em cada via.
+const ITEM_ROWS: usize = 12;
+
+/// Dados impressos na nota de uma venda.
+#[derive(Clone, Debug)]
+pub struct SaleNote {
+    pub client_name: String,
+    /// Itens no formato `[{ name, qty, price }]`.
+    pub items: Vec<serde_json::Value>,
+    pub total: f64,
+    pub sale_number: i32,
+}
+
+fn text(value: impl Into<String>, size: u8) -> style::StyledString {
+    style::StyledString::new(value.into(), style::Style::new().with_font_size(size))
+}
+
+fn bold(value: impl Into<String>, size: u8) -> style::StyledString {
+    style::StyledString::new(value.into(), style::Style::new().bold().with_font_size(size))
+}
+
+fn new_document(title: &str) -> Result<genpdf::Document, AppError> {
+    let font_path = std::path::Path::new(FONT_DIR).join(FONT_FILE);
+    let font_data = genpdf::fonts::FontData::load(&font_path, None)
+        .map_err(|e| AppError::internal(format!("Erro ao carregar fonte {}: {}", font_path.display(), e)))?;
+
+    let font_family = genpdf::fonts::FontFamily {
+        regular: font_data.clone(),
+        bold: font_data.clone(),
+        italic: font_data.clone(),
+        bold_italic: font_data,
+    };
+
+    let mut doc = genpdf::Document::new(font_family);
+    doc.set_title(title);
+
+    let mut decorator = genpdf::SimplePageDecorator::new();
+    decorator.set_margins(15);
+    doc.set_page_decorator(decorator);
+    Ok(doc)
+}
+
+fn framed_table(columns: Vec<usize>) -> TableLayout {
+    let mut table = TableLayout::new(columns);
+    table.set_cell_decorator(FrameCellDecorator::new(true, true, false));
+    table
+}
+
+/// Cabeçalho principal: logo e endereço da distribuidora.
+fn header() -> TableLayout {
+    let mut header_table = framed_table(vec![3, 2]);
+
+    let mut logo_box = LinearLayout::vertical();
+    logo_box.push(Paragraph::new(bold("MORAIS", 24)));
+    logo_box.push(Paragraph::new("distribuidora").styled(style::Style::new().italic().with_font_size(16)));
+
+    let mut address_box = LinearLayout::vertical();
+    address_box.push(Paragraph::new(text("Morais Distribuidora de água mineral", 7)));
+    address_box.push(Paragraph::new(text("Av. Tailândia - nº 127", 7)));
+    address_box.push(Paragraph::new(text("Bairro Columbia - Colatina - ES", 7)));
+    address_box.push(Paragraph::new(text("Tel.: (27) 98893-2758 / (27) 99938-1129", 7)));
+
+    header_table.row().element(logo_box).element(address_box).push().unwrap();
+    header_table
+}
+
+/// Grade de cliente e data.
+fn info(client: &str, date: &str) -> TableLayout {
+    let mut info_table = framed_table(vec![1, 5]);
+    info_table.row()
+        .element(Paragraph::new(bold("CLIENTE:", 9)))
+        .element(Paragraph::new(text(client, 9)))
+        .push().unwrap();
+    info_table.row()
+        .element(Paragraph::new(bold("DATA:", 9)))
+        .element(Paragraph::new(text(date, 9)))
+        .push().unwrap();
+    info_table
+}
+
+fn items_table() -> TableLayout {
+    let mut items_table = framed_table(vec![10, 2, 2, 3, 3]);
+    items_table.row()
+        .element(Paragraph::new(bold("Produto", 9)))
+        .element(Paragraph::new(bold(" ", 9)))
+        .element(Paragraph::new(bold("Quant.", 9)))
+        .element(Paragraph::new(bold("Valor Unit.", 9)))
+        .element(Paragraph::new(bold("Valor Total", 9)))
+        .push().unwrap();
+    items_table
+}
+
+/// Linhas em branco para manter o rigor visual.
+fn blank_rows(table: &mut TableLayout, count: usize) {
+    for _ in 0..count {
+        table.row()
+            .element(Paragraph::new(" "))
+            .element(Paragraph::new(" "))
+            .element(Paragraph::new(" "))
+            .element(Paragraph::new(" "))
+            .element(Paragraph::new(" "))
+            .push().unwrap();
+    }
+}
+
+fn totals(quantity: &str, total: &str) -> TableLayout {
+    let mut footer_table = framed_table(vec![12, 2, 6]);
+    footer_table.row()
+        .element(Paragraph::new(bold("TOTAIS", 9)).aligned(Alignment::Center))
+        .element(Paragraph::new(bold(quantity, 9)))
+        .element(Paragraph::new(bold(total, 9)).aligned(Alignment::Right))
+        .push().unwrap();
+    footer_table
+}
+
+fn signature(via: &mut LinearLayout) {
+    via.push(Break::new(1));
+    via.push(Paragraph::new("_______________________________________").aligned(Alignment::Center));
+    via.push(Paragraph::new(bold("ASSINATURA", 7)).aligned(Alignment::Center));
+    via.push(Break::new(0.5));
+    via.push(Paragraph::new("Deus é nossa fonte!").styled(style::Style::new().italic().with_font_size(7)));
+}
+
+fn render(mut doc: genpdf::Document, left: LinearLayout, right: LinearLayout) -> Result<Vec<u8>, AppError> {
+    let mut main_table = TableLayout::new(vec![1, 1]);
+    main_table.row().element(left).element(right).push().unwrap();
+    doc.push(main_table);
+
+    let mut buffer = Vec::new();
+    doc.render(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Nota de controle da venda. A via do cliente leva o QR Code PIX com o
+/// valor da venda quando a chave estiver configurada.
+pub async fn sale_note(db: &DatabaseConnection, note: &SaleNote) -> Result<Vec<u8>, AppError> {
+    let pix_image = match pix::load_config(db).await? {
+        Some(config) => {
+            let charge = pix::PixCharge {
+                amount: Some(note.total),
+                txid: Some(format!("VENDA{:04}", note.sale_number)),
+                ..Default::default()
+            };
+            let payload = pix::build_payload(&config, &charge)?;
+            let qr = pix::qr_image(&payload, 4)?;
+            Some(Image::from_dynamic_image(image::DynamicImage::ImageLuma8(qr))?.with_alignment(Alignment::Center))
+        }
+        None => None,
+    };
+
+    let doc = new_document("Nota de Venda")?;
+    let date = chrono::Local::now().format("%d/%m/%y").to_string();
+
+    let via = |title: &str, with_pix: bool| -> LinearLayout {
+        let mut via = LinearLayout::vertical();
+        via.push(header());
+        via.push(
+            Paragraph::new(text(format!("{} - Nota de controle N.º: {:04}", title, note.sale_number), 9))
+                .aligned(Alignment::Center),
+        );
+        via.push(info(&note.client_name, &date));
+
+        let mut items = items_table();
+        let mut total_qty = 0.0;
+        for item in &note.items {
+            let name = item["name"].as_str().unwrap_or("");
+            let qty = item["qty"].as_f64().unwrap_or(0.0);
+            let price = item["price"].as_f64().unwrap_or(0.0);
+            total_qty += qty;
+
+            items.row()
+                .element(Paragraph::new(text(name, 8)))
+                .element(Paragraph::new(text("-", 8)))
+                .element(Paragraph::new(text(format!("{}", qty), 8)))
+                .element(Paragraph::new(text(format::currency(price), 8)))
+                .element(Paragraph::new(text(format::currency(price * qty), 8)))
+                .push().unwrap();
+        }
+        blank_rows(&mut items, ITEM_ROWS - note.items.len().min(ITEM_ROWS));
+        via.push(items);
+        via.push(totals(&format!("{}", total_qty), &format::currency(note.total)));
+
+        if let (true, Some(qr)) = (with_pix, &pix_image) {
+            via.push(Break::new(0.5));
+            via.push(Paragraph::new(bold("Pague com PIX", 8)).aligned(Alignment::Center));
+            via.push(qr.clone());
+        }
+
+        signature(&mut via);
+        via
+    };
+
+    render(doc, via("1ª Via - Distribuidora", false), via("2ª Via - Cliente", true))
+}
+
+/// Nota em branco para preenchimento à mão.
+pub fn blank_note() -> Result<Vec<u8>, AppError> {
+    let doc = new_document("Nota Branca")?;
+
+    let via = || -> LinearLayout {
+        let mut via = LinearLayout::vertical();
+        via.push(header());
+        via.push(Paragraph::new(text("Nota de controle N.º: ________", 9)).aligned(Alignment::Center));
+        via.push(info("____________________________________________________", "__/__/__"));
+
+        let mut items = items_table();
+        blank_rows(&mut items, ITEM_ROWS);
+        via.push(items);
+        via.push(totals(" ", "R$ ________"));
+
+        signature(&mut via);
+        via
+    };
+
+    render(doc, via(), via())
+}
+
+fn data_table(title: &str, headers: &[&str], columns: Vec<usize>) -> (Paragraph, TableLayout) {
+    let mut table = framed_table(columns);
+    let mut row = table.row();
+    for h in headers {
+        row = row.element(Paragraph::new(bold(*h, 8)));
+    }
+    row.push().unwrap();
+    (Paragraph::new(bold(title, 11)), table)
+}
+
+fn section(doc: &mut genpdf::Document, title: Paragraph, table: TableLayout, empty: bool) {
+    doc.push(Break::new(1));
+    doc.push(title);
+    if empty {
+        doc.push(Paragraph::new(text("Nenhum registro.", 8)));
+    } else {
+        doc.push(table);
+    }
+}
+
+fn local_date_time(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.with_timezone(&chrono::Local).format("%d/%m/%Y %H:%M").to_string()
+}
+
+/// Relatório com todos os dados guardados sobre o cliente, entregue ao
+/// titular que os solicitar.
+pub fn client_data(export: &ClientDataExport) -> Result<Vec<u8>, AppError> {
+    let mut doc = new_document("Dados do Cliente")?;
+    let client = &export.client;
+
+    doc.push(header());
+    doc.push(Break::new(1));
+    doc.push(Paragraph::new(bold("Relatório de dados pessoais (LGPD)", 14)).aligned(Alignment::Center));
+    doc.push(
+        Paragraph::new(text(format!("Gerado em {}", local_date_time(export.generated_at)), 8))
+            .aligned(Alignment::Center),
+    );
+
+    let (title, mut table) = data_table("Cadastro", &["Campo", "Valor"], vec![1, 3]);
+    let fields = [
+        ("Código", Some(client.id.to_string())),
+        ("Nome", Some(client.name.clone())),
+        ("Telefone", client.phone.clone()),
+        ("CPF/CNPJ", client.document.clone()),
+        ("CEP", client.cep.clone()),
+        ("Endereço", client.address.clone()),
+        ("Cidade/UF", match (&client.city, &client.state) {
+            (Some(city), Some(uf)) => Some(format!("{}/{}", city, uf)),
+            (city, uf) => city.clone().or_else(|| uf.clone()),
+        }),
+        ("Observações", client.observations.clone()),
+        ("Cadastrado em", Some(local_date_time(client.created_at))),
+        ("Anonimizado em", client.anonymized_at.map(local_date_time)),
+    ];
+    for (label, value) in fields {
+        table.row()
+            .element(Paragraph::new(bold(label, 8)))
+            .element(Paragraph::new(text(value.unwrap_or_else(|| "-".into()), 8)))
+            .push().unwrap();
+    }
+    section(&mut doc, title, table, false);
+
+    let (title, mut table) = data_table("Compras", &["Nº", "Data", "Pagamento", "Total"], vec![1, 3, 3, 2]);
+    for sale in &export.sales {
+        table.row()
+            .element(Paragraph::new(text(format!("{:04}", sale.id), 8)))
+            .element(Paragraph::new(text(local_date_time(sale.created_at), 8)))
+            .element(Paragraph::new(text(sale.payment_method.clone(), 8)))
+            .element(Paragraph::new(text(format::currency(sale.total), 8)).aligned(Alignment::Right))
+            .push().unwrap();
+    }
+    section(&mut doc, title, table, export.sales.is_empty());
+
+    let receivable: f64 = export.receivables.iter().map(|s| s.total).sum();
+    doc.push(Break::new(0.5));
+    doc.push(Paragraph::new(text(
+        format!("Compras a prazo (fiado): {} • {}", export.receivables.len(), format::currency(receivable)),
+        8,
+    )));
+
+    let (title, mut table) = data_table("Galões", &["Marca", "Validade"], vec![2, 2]);
+    for gallon in &export.gallons {
+        table.row()
+            .element(Paragraph::new(text(gallon.brand.clone(), 8)))
+            .element(Paragraph::new(text(gallon.expiration_date.format("%d/%m/%Y").to_string(), 8)))
+            .push().unwrap();
+    }
+    section(&mut doc, title, table, export.gallons.is_empty());
+
+    let (title, mut table) = data_table("Histórico de solicitações", &["Data", "Operação", "Usuário"], vec![2, 3, 2]);
+    for entry in &export.audit_log {
+        table.row()
+            .element(Paragraph::new(text(local_date_time(entry.created_at), 8)))
+            .element(Paragraph::new(text(entry.action.clone(), 8)))
+            .element(Paragraph::new(text(entry.username.clone(), 8)))
+            .push().unwrap();
+    }
+    section(&mut doc, title, table, export.audit_log.is_empty());
+
+    let mut buffer = Vec::new();
+    doc.render(&mut buffer)?;
+    Ok(buffer)
+}
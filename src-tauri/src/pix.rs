@@ -0,0 +1,226 @@
+//! Geração do BR Code do PIX (padrão EMV MPM do Banco Central).
+
+use base64::{engine::general_purpose, Engine as _};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::db::entities::setting;
+use crate::error::AppError;
+
+/// Dados do recebedor configurados no sistema.
+#[derive(Clone, Debug)]
+pub struct PixConfig {
+    pub key: String,
+    pub merchant_name: String,
+    pub merchant_city: String,
+}
+
+/// Cobrança a ser codificada. Com `location` preenchido o código é dinâmico
+/// (URL fornecida pelo PSP); caso contrário é estático, usando a chave.
+#[derive(Clone, Debug, Default)]
+pub struct PixCharge {
+    pub amount: Option<f64>,
+    pub txid: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct PixCode {
+    pub payload: String,
+    pub png_base64: String,
+    pub svg: String,
+}
+
+pub const SETTING_KEY: &str = "pix_key";
+pub const SETTING_MERCHANT_NAME: &str = "pix_merchant_name";
+pub const SETTING_MERCHANT_CITY: &str = "pix_merchant_city";
+
+const GUI: &str = "br.gov.bcb.pix";
+
+fn field(id: &str, value: &str) -> String {
+    format!("{}{:02}{}", id, value.len(), value)
+}
+
+/// CRC16-CCITT (polinômio 0x1021, valor inicial 0xFFFF), exigido no campo 63.
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Remove acentos e caracteres fora do conjunto aceito pelos bancos.
+fn sanitize(value: &str, max_len: usize) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'É' | 'È' | 'Ê' | 'Ë' => 'E',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+            'ç' => 'c',
+            'Ç' => 'C',
+            c => c,
+        })
+        .filter(|c| c.is_ascii_alphanumeric() || " .-/@:_".contains(*c))
+        .take(max_len)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+pub fn build_payload(config: &PixConfig, charge: &PixCharge) -> Result<String, AppError> {
+    let key = config.key.trim();
+    if key.is_empty() && charge.location.is_none() {
+        return Err(AppError::validation("Chave PIX não configurada"));
+    }
+
+    let merchant_name = sanitize(&config.merchant_name, 25);
+    if merchant_name.is_empty() {
+        return Err(AppError::validation("Nome do recebedor PIX não configurado"));
+    }
+    let merchant_city = sanitize(&config.merchant_city, 15);
+    if merchant_city.is_empty() {
+        return Err(AppError::validation("Cidade do recebedor PIX não configurada"));
+    }
+
+    let txid = match charge.txid.as_deref().map(str::trim) {
+        Some(t) if !t.is_empty() => {
+            if t.len() > 25 || !t.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(AppError::validation("Identificador da transação (txid) deve ter até 25 letras ou números"));
+            }
+            t.to_string()
+        }
+        _ => "***".to_string(),
+    };
+
+    let mut account = field("00", GUI);
+    match &charge.location {
+        Some(url) => account.push_str(&field("25", url.trim_start_matches("https://"))),
+        None => {
+            account.push_str(&field("01", key));
+            if let Some(desc) = charge.description.as_deref().map(|d| sanitize(d, 40)) {
+                if !desc.is_empty() {
+                    account.push_str(&field("02", &desc));
+                }
+            }
+        }
+    }
+    if account.len() > 99 {
+        return Err(AppError::validation("Chave PIX e descrição excedem o tamanho permitido"));
+    }
+
+    let mut payload = field("00", "01");
+    if charge.location.is_some() {
+        payload.push_str(&field("01", "12"));
+    }
+    payload.push_str(&field("26", &account));
+    payload.push_str(&field("52", "0000"));
+    payload.push_str(&field("53", "986"));
+    if let Some(amount) = charge.amount {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(AppError::validation("Valor do PIX deve ser maior que zero"));
+        }
+        payload.push_str(&field("54", &format!("{:.2}", amount)));
+    }
+    payload.push_str(&field("58", "BR"));
+    payload.push_str(&field("59", &merchant_name));
+    payload.push_str(&field("60", &merchant_city));
+    payload.push_str(&field("62", &field("05", &txid)));
+    payload.push_str("6304");
+
+    let crc = crc16(payload.as_bytes());
+    payload.push_str(&format!("{:04X}", crc));
+    Ok(payload)
+}
+
+/// Monta o QR Code do payload como imagem em tons de cinza, com `scale`
+/// pixels por módulo e a zona de silêncio de 4 módulos.
+pub fn qr_image(payload: &str, scale: u32) -> Result<image::GrayImage, AppError> {
+    let code = qrcode::QrCode::with_error_correction_level(payload, qrcode::EcLevel::M)
+        .map_err(|e| AppError::internal(format!("Erro ao gerar QR Code: {}", e)))?;
+    let width = code.width() as u32;
+    let colors = code.to_colors();
+    let quiet = 4;
+    let size = (width + quiet * 2) * scale;
+
+    Ok(image::GrayImage::from_fn(size, size, |x, y| {
+        let (mx, my) = (x / scale, y / scale);
+        let dark = mx >= quiet
+            && my >= quiet
+            && mx < width + quiet
+            && my < width + quiet
+            && colors[((my - quiet) * width + (mx - quiet)) as usize] == qrcode::Color::Dark;
+        image::Luma([if dark { 0 } else { 255 }])
+    }))
+}
+
+pub fn generate(config: &PixConfig, charge: &PixCharge) -> Result<PixCode, AppError> {
+    let payload = build_payload(config, charge)?;
+
+    let code = qrcode::QrCode::with_error_correction_level(&payload, qrcode::EcLevel::M)
+        .map_err(|e| AppError::internal(format!("Erro ao gerar QR Code: {}", e)))?;
+    let svg = code
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(200, 200)
+        .build();
+
+    let img = qr_image(&payload, 8)?;
+    let mut png = Vec::new();
+    image::DynamicImage::ImageLuma8(img)
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .map_err(|e| AppError::internal(format!("Erro ao gerar imagem do QR Code: {}", e)))?;
+
+    Ok(PixCode {
+        payload,
+        png_base64: general_purpose::STANDARD.encode(png),
+        svg,
+    })
+}
+
+/// Lê o recebedor das configurações; `None` quando a chave não foi cadastrada.
+pub async fn load_config(db: &DatabaseConnection) -> Result<Option<PixConfig>, AppError> {
+    let settings = setting::Entity::find()
+        .filter(setting::Column::Key.is_in([SETTING_KEY, SETTING_MERCHANT_NAME, SETTING_MERCHANT_CITY]))
+        .all(db)
+        .await?;
+
+    let get = |key: &str| {
+        settings
+            .iter()
+            .find(|s| s.key == key)
+            .map(|s| s.value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let key = get(SETTING_KEY);
+    if key.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(PixConfig {
+        key,
+        merchant_name: get(SETTING_MERCHANT_NAME),
+        merchant_city: get(SETTING_MERCHANT_CITY),
+    }))
+}
+
+/// Gera a cobrança com o recebedor configurado no sistema.
+pub async fn generate_configured(db: &DatabaseConnection, charge: &PixCharge) -> Result<PixCode, AppError> {
+    let config = load_config(db)
+        .await?
+        .ok_or_else(|| AppError::validation("Chave PIX não configurada"))?;
+    generate(&config, charge)
+}
@@ -0,0 +1,112 @@
+//! Direitos do titular (LGPD) sobre os dados de um cliente: exportação de
+//! tudo o que o sistema guarda sobre ele e anonimização. As duas operações
+//! ficam registradas na trilha de auditoria.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait,
+};
+use serde::Serialize;
+use serde_json::json;
+use ts_rs::TS;
+
+use crate::db::entities::{audit_log, client, client_gallon, sale};
+use crate::error::AppError;
+use crate::sales::RECEIVABLE_PAYMENT_METHOD;
+use crate::{audit, clients};
+
+pub const ACTION_EXPORT: &str = "client_data_export";
+pub const ACTION_ANONYMIZE: &str = "client_anonymize";
+
+/// Pacote com todos os dados de um cliente.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ClientDataExport {
+    pub generated_at: DateTime<Utc>,
+    pub client: client::Model,
+    pub sales: Vec<sale::Model>,
+    pub gallons: Vec<client_gallon::Model>,
+    /// Vendas a prazo (fiado) do cliente, também presentes em `sales`.
+    pub receivables: Vec<sale::Model>,
+    pub audit_log: Vec<audit_log::Model>,
+}
+
+/// Junta os dados do cliente e registra a exportação (`format` é `json` ou
+/// `pdf`). O próprio registro já sai na trilha exportada.
+pub async fn export_client_data(
+    db: &DatabaseConnection,
+    client_id: i32,
+    username: &str,
+    format: &str,
+) -> Result<ClientDataExport, AppError> {
+    let details = clients::client_details(db, client_id).await?;
+    audit::record(db, ACTION_EXPORT, audit::CLIENT, client_id, username, Some(json!({ "format": format }))).await?;
+
+    let receivables = details
+        .sales
+        .iter()
+        .filter(|s| s.payment_method == RECEIVABLE_PAYMENT_METHOD)
+        .cloned()
+        .collect();
+    Ok(ClientDataExport {
+        generated_at: Utc::now(),
+        client: details.client,
+        sales: details.sales,
+        gallons: details.gallons,
+        receivables,
+        audit_log: audit::list_for(db, audit::CLIENT, client_id).await?,
+    })
+}
+
+/// Apaga os dados pessoais do cliente: o nome vira um marcador e contato,
+/// documento, endereço e observações são removidos. As vendas continuam
+/// ligadas ao registro com os mesmos valores, para a contabilidade. Os
+/// galões saem junto, pois só servem para avisar o cliente do vencimento.
+pub async fn anonymize_client(
+    db: &DatabaseConnection,
+    client_id: i32,
+    username: &str,
+) -> Result<client::Model, AppError> {
+    let txn = db.begin().await?;
+
+    let current = client::Entity::find_by_id(client_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::not_found("Cliente não encontrado"))?;
+    if current.anonymized_at.is_some() {
+        return Err(AppError::conflict("Cliente já anonimizado"));
+    }
+
+    let mut model: client::ActiveModel = current.into();
+    model.name = Set(format!("Cliente anonimizado #{}", client_id));
+    model.phone = Set(None);
+    model.document = Set(None);
+    model.cep = Set(None);
+    model.city = Set(None);
+    model.state = Set(None);
+    model.street = Set(None);
+    model.number = Set(None);
+    model.complement = Set(None);
+    model.neighborhood = Set(None);
+    model.address = Set(None);
+    model.observations = Set(None);
+    model.anonymized_at = Set(Some(Utc::now()));
+    let updated = model.update(&txn).await?;
+
+    let gallons = client_gallon::Entity::delete_many()
+        .filter(client_gallon::Column::ClientId.eq(client_id))
+        .exec(&txn)
+        .await?;
+
+    audit::record(
+        &txn,
+        ACTION_ANONYMIZE,
+        audit::CLIENT,
+        client_id,
+        username,
+        Some(json!({ "gallons_removed": gallons.rows_affected })),
+    )
+    .await?;
+
+    txn.commit().await?;
+    Ok(updated)
+}
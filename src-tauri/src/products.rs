@@ -0,0 +1,229 @@
+//! Cadastro de produtos e categorias.
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+
+use crate::db::entities::{category, product, purchase_order_item, stock_movement};
+use crate::error::AppError;
+use crate::stock::{self, StockReason};
+use crate::pagination;
+
+/// Observação da movimentação do estoque informado no cadastro.
+const INITIAL_STOCK_NOTE: &str = "Estoque inicial";
+
+/// Dados do formulário de produto.
+#[derive(Clone, Debug, Default)]
+pub struct ProductInput {
+    pub name: String,
+    pub price: f64,
+    /// Estoque inicial do cadastro. A edição não altera o estoque: as
+    /// mudanças passam por [`stock::adjust_stock`], que registra o usuário.
+    pub stock_quantity: i32,
+    pub category: String,
+    pub category_id: Option<i32>,
+    /// `None` usa [`stock::DEFAULT_MIN_STOCK`] no cadastro e mantém o atual na edição.
+    pub min_stock: Option<i32>,
+    pub reorder_qty: Option<i32>,
+    /// Custo unitário. `None` cadastra sem custo e mantém o atual na edição;
+    /// depois as compras recebidas recalculam o custo médio.
+    pub cost_price: Option<f64>,
+}
+
+impl ProductInput {
+    fn validate(&self) -> Result<(), AppError> {
+        if self.min_stock.is_some_and(|q| q < 0) {
+            return Err(AppError::validation("Estoque mínimo não pode ser negativo").with_field("min_stock"));
+        }
+        if self.reorder_qty.is_some_and(|q| q < 0) {
+            return Err(AppError::validation("Lote de compra não pode ser negativo").with_field("reorder_qty"));
+        }
+        if self.cost_price.is_some_and(|c| !c.is_finite() || c < 0.0) {
+            return Err(AppError::validation("Custo não pode ser negativo").with_field("cost_price"));
+        }
+        Ok(())
+    }
+}
+
+/// Filtros e ordenação da listagem paginada.
+#[derive(Clone, Debug, Default)]
+pub struct ProductQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    pub search: Option<String>,
+    pub sort_by: Option<String>,
+    pub sort_dir: Option<pagination::SortDirection>,
+    pub category_id: Option<i32>,
+}
+
+pub async fn list_products(db: &DatabaseConnection) -> Result<Vec<product::Model>, AppError> {
+    product::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+pub async fn products_page(
+    db: &DatabaseConnection,
+    query: ProductQuery,
+) -> Result<pagination::Page<product::Model>, AppError> {
+    use product::Column;
+
+    let mut select = product::Entity::find();
+
+    if let Some(term) = pagination::search_term(query.search) {
+        let pattern = pagination::like_pattern(&term);
+        select = select.filter(
+            Condition::any()
+                .add(Column::Name.like(pattern.clone()))
+                .add(Column::Category.like(pattern)),
+        );
+    }
+    if let Some(category_id) = query.category_id {
+        select = select.filter(Column::CategoryId.eq(category_id));
+    }
+
+    let select = pagination::apply_sort(
+        select,
+        query.sort_by.as_deref(),
+        query.sort_dir,
+        &[
+            ("name", Column::Name),
+            ("price", Column::Price),
+            ("stock_quantity", Column::StockQuantity),
+            ("category", Column::Category),
+            ("min_stock", Column::MinStock),
+        ],
+        Column::Id,
+    )?;
+
+    pagination::fetch_page(db, select, query.page, query.page_size).await
+}
+
+/// Produtos exibidos no painel: os quatro com maior estoque.
+pub async fn popular_products(db: &DatabaseConnection) -> Result<Vec<product::Model>, AppError> {
+    product::Entity::find()
+        .order_by_desc(product::Column::StockQuantity)
+        .limit(4)
+        .all(db)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Cadastra o produto; o estoque inicial entra no histórico como ajuste.
+pub async fn create_product<C>(db: &C, input: ProductInput) -> Result<product::Model, AppError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    input.validate()?;
+    let txn = db.begin().await?;
+    let product = product::ActiveModel {
+        name: Set(input.name),
+        price: Set(input.price),
+        stock_quantity: Set(input.stock_quantity),
+        category: Set(input.category),
+        category_id: Set(input.category_id),
+        min_stock: Set(input.min_stock.unwrap_or(stock::DEFAULT_MIN_STOCK)),
+        reorder_qty: Set(input.reorder_qty.unwrap_or(0)),
+        cost_price: Set(input.cost_price.unwrap_or(0.0)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    if product.stock_quantity != 0 {
+        let movement = stock::Movement {
+            note: Some(INITIAL_STOCK_NOTE.to_string()),
+            ..stock::Movement::new(product.id, product.stock_quantity, StockReason::Adjustment)
+        };
+        stock::log(&txn, movement).await?;
+    }
+    txn.commit().await?;
+    Ok(product)
+}
+
+pub async fn update_product(
+    db: &DatabaseConnection,
+    id: i32,
+    input: ProductInput,
+) -> Result<product::Model, AppError> {
+    input.validate()?;
+    let current = product::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Produto não encontrado"))?;
+
+    // `stock_quantity` não é tocado: gravar o valor do formulário desfaria
+    // as vendas feitas enquanto ele estava aberto
+    let mut product: product::ActiveModel = current.into();
+    product.name = Set(input.name);
+    product.price = Set(input.price);
+    product.category = Set(input.category);
+    product.category_id = Set(input.category_id);
+    if let Some(min_stock) = input.min_stock {
+        product.min_stock = Set(min_stock);
+    }
+    if let Some(reorder_qty) = input.reorder_qty {
+        product.reorder_qty = Set(reorder_qty);
+    }
+    if let Some(cost_price) = input.cost_price {
+        product.cost_price = Set(cost_price);
+    }
+    product.update(db).await.map_err(AppError::from)
+}
+
+/// Exclui o produto. Produtos com movimentações de estoque ou em pedidos
+/// de compra são mantidos, para não apagar o histórico.
+pub async fn delete_product(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    let movements = stock_movement::Entity::find()
+        .filter(stock_movement::Column::ProductId.eq(id))
+        .count(db)
+        .await?;
+    let purchase_items = purchase_order_item::Entity::find()
+        .filter(purchase_order_item::Column::ProductId.eq(id))
+        .count(db)
+        .await?;
+    if movements > 0 || purchase_items > 0 {
+        return Err(AppError::conflict(
+            "Produto com movimentações de estoque ou pedidos de compra não pode ser excluído",
+        ));
+    }
+    product::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn list_categories(db: &DatabaseConnection) -> Result<Vec<category::Model>, AppError> {
+    category::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+pub async fn create_category(
+    db: &DatabaseConnection,
+    name: String,
+    description: Option<String>,
+) -> Result<category::Model, AppError> {
+    let category = category::ActiveModel {
+        name: Set(name),
+        description: Set(description),
+        ..Default::default()
+    };
+    category.insert(db).await.map_err(AppError::from)
+}
+
+pub async fn update_category(
+    db: &DatabaseConnection,
+    id: i32,
+    name: String,
+    description: Option<String>,
+) -> Result<category::Model, AppError> {
+    let mut category: category::ActiveModel = category::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Categoria não encontrada"))?
+        .into();
+
+    category.name = Set(name);
+    category.description = Set(description);
+    category.update(db).await.map_err(AppError::from)
+}
+
+pub async fn delete_category(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    category::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
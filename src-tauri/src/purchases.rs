@@ -0,0 +1,449 @@
+//! Compras: fornecedores e pedidos de compra. O recebimento, total ou
+//! parcial, dá entrada no estoque pelo histórico de movimentações e
+//! recalcula o custo médio do produto.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::db::entities::{product, purchase_order, purchase_order_item, supplier};
+use crate::error::AppError;
+use crate::stock::{self, StockReason};
+use crate::{audit, clients, document};
+
+/// Ação registrada na auditoria quando um pedido de compra é cancelado.
+pub const ACTION_CANCEL: &str = "purchase_order_cancel";
+
+/// Situação do pedido, gravada em `purchase_orders.status`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum PurchaseStatus {
+    Open,
+    Partial,
+    Received,
+    Cancelled,
+}
+
+impl PurchaseStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PurchaseStatus::Open => "open",
+            PurchaseStatus::Partial => "partial",
+            PurchaseStatus::Received => "received",
+            PurchaseStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn accepts_changes(status: &str) -> bool {
+        status == PurchaseStatus::Open.as_str() || status == PurchaseStatus::Partial.as_str()
+    }
+}
+
+/// Dados do formulário de fornecedor.
+#[derive(Clone, Debug, Default)]
+pub struct SupplierInput {
+    pub name: String,
+    pub document: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Item do pedido enviado pelo frontend.
+#[derive(Clone, Debug, Deserialize, TS)]
+pub struct PurchaseItemInput {
+    pub product_id: i32,
+    pub quantity: i32,
+    pub unit_cost: f64,
+}
+
+/// Quantidade recebida de um item. `unit_cost` substitui o custo combinado
+/// quando a nota vem com outro valor; só é aceito no primeiro recebimento
+/// do item, para não mudar o custo das unidades que já entraram.
+#[derive(Clone, Debug, Deserialize, TS)]
+pub struct ReceivedItem {
+    pub item_id: i32,
+    pub quantity: i32,
+    pub unit_cost: Option<f64>,
+}
+
+/// Pedido na listagem, com o fornecedor e o valor total.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct PurchaseOrderSummary {
+    pub order: purchase_order::Model,
+    pub supplier_name: String,
+    pub total: f64,
+    pub item_count: usize,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct PurchaseOrderLine {
+    pub item: purchase_order_item::Model,
+    pub product_name: String,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct PurchaseOrderDetails {
+    pub order: purchase_order::Model,
+    pub supplier: supplier::Model,
+    pub items: Vec<PurchaseOrderLine>,
+    pub total: f64,
+}
+
+fn optional(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn apply_supplier_input(model: &mut supplier::ActiveModel, input: SupplierInput) -> Result<(), AppError> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::validation("Nome é obrigatório").with_field("name"));
+    }
+    let document = match optional(input.document) {
+        Some(d) => Some(document::validate(&d).map_err(|e| e.with_field("document"))?.1),
+        None => None,
+    };
+    let phone = match optional(input.phone) {
+        Some(p) => Some(clients::normalize_phone(&p)?),
+        None => None,
+    };
+    let email = optional(input.email);
+    if email.as_deref().is_some_and(|e| !e.contains('@')) {
+        return Err(AppError::validation("E-mail inválido").with_field("email"));
+    }
+
+    model.name = Set(name);
+    model.document = Set(document);
+    model.phone = Set(phone);
+    model.email = Set(email);
+    model.notes = Set(optional(input.notes));
+    Ok(())
+}
+
+pub async fn list_suppliers(db: &DatabaseConnection) -> Result<Vec<supplier::Model>, AppError> {
+    supplier::Entity::find()
+        .order_by_asc(supplier::Column::Name)
+        .all(db)
+        .await
+        .map_err(AppError::from)
+}
+
+pub async fn create_supplier(db: &DatabaseConnection, input: SupplierInput) -> Result<supplier::Model, AppError> {
+    let mut model = supplier::ActiveModel {
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    };
+    apply_supplier_input(&mut model, input)?;
+    model.insert(db).await.map_err(AppError::from)
+}
+
+pub async fn update_supplier(
+    db: &DatabaseConnection,
+    id: i32,
+    input: SupplierInput,
+) -> Result<supplier::Model, AppError> {
+    let mut model: supplier::ActiveModel = supplier::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Fornecedor não encontrado"))?
+        .into();
+    apply_supplier_input(&mut model, input)?;
+    model.update(db).await.map_err(AppError::from)
+}
+
+/// Exclui o fornecedor; com pedidos registrados a exclusão é recusada.
+pub async fn delete_supplier(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    supplier::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// Registra um pedido em aberto. Nada entra no estoque até o recebimento.
+pub async fn create_purchase_order(
+    db: &DatabaseConnection,
+    supplier_id: i32,
+    items: Vec<PurchaseItemInput>,
+    notes: Option<String>,
+    username: &str,
+) -> Result<PurchaseOrderDetails, AppError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(AppError::validation("Usuário responsável não informado").with_field("username"));
+    }
+    if supplier::Entity::find_by_id(supplier_id).one(db).await?.is_none() {
+        return Err(AppError::validation("Fornecedor não encontrado").with_field("supplier_id"));
+    }
+    if items.is_empty() {
+        return Err(AppError::validation("Inclua ao menos um produto no pedido").with_field("items"));
+    }
+
+    let product_ids: Vec<i32> = items.iter().map(|i| i.product_id).collect();
+    let known: HashSet<i32> = product::Entity::find()
+        .filter(product::Column::Id.is_in(product_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+    let mut seen = HashSet::new();
+    for item in &items {
+        if !known.contains(&item.product_id) {
+            return Err(AppError::validation(format!("Produto {} não encontrado", item.product_id)).with_field("items"));
+        }
+        if !seen.insert(item.product_id) {
+            return Err(AppError::validation("Cada produto deve aparecer uma única vez no pedido").with_field("items"));
+        }
+        if item.quantity <= 0 {
+            return Err(AppError::validation("A quantidade deve ser maior que zero").with_field("items"));
+        }
+        if !item.unit_cost.is_finite() || item.unit_cost < 0.0 {
+            return Err(AppError::validation("Custo unitário inválido").with_field("items"));
+        }
+    }
+
+    let txn = db.begin().await?;
+    let order = purchase_order::ActiveModel {
+        supplier_id: Set(supplier_id),
+        status: Set(PurchaseStatus::Open.as_str().to_string()),
+        notes: Set(optional(notes)),
+        username: Set(username.to_string()),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    for item in items {
+        purchase_order_item::ActiveModel {
+            purchase_order_id: Set(order.id),
+            product_id: Set(item.product_id),
+            quantity: Set(item.quantity),
+            received_quantity: Set(0),
+            unit_cost: Set((item.unit_cost * 100.0).round() / 100.0),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+    }
+    txn.commit().await?;
+
+    purchase_order_details(db, order.id).await
+}
+
+/// Dá entrada no estoque das quantidades recebidas. Sem `items`, recebe
+/// tudo o que falta. O pedido fica `partial` até todos os itens chegarem.
+pub async fn receive_purchase_order(
+    db: &DatabaseConnection,
+    id: i32,
+    items: Option<Vec<ReceivedItem>>,
+    username: &str,
+) -> Result<PurchaseOrderDetails, AppError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(AppError::validation("Usuário responsável não informado").with_field("username"));
+    }
+
+    let txn = db.begin().await?;
+    let order = purchase_order::Entity::find_by_id(id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::not_found("Pedido de compra não encontrado"))?;
+    if !PurchaseStatus::accepts_changes(&order.status) {
+        return Err(AppError::conflict("Pedido já recebido ou cancelado"));
+    }
+    let lines = purchase_order_item::Entity::find()
+        .filter(purchase_order_item::Column::PurchaseOrderId.eq(id))
+        .all(&txn)
+        .await?;
+
+    let received = match items {
+        Some(items) => items,
+        None => lines
+            .iter()
+            .filter(|l| l.received_quantity < l.quantity)
+            .map(|l| ReceivedItem { item_id: l.id, quantity: l.quantity - l.received_quantity, unit_cost: None })
+            .collect(),
+    };
+    if received.is_empty() {
+        return Err(AppError::validation("Informe as quantidades recebidas").with_field("items"));
+    }
+
+    let mut lines: HashMap<i32, purchase_order_item::Model> = lines.into_iter().map(|l| (l.id, l)).collect();
+    for entry in received {
+        let line = lines
+            .get_mut(&entry.item_id)
+            .ok_or_else(|| AppError::validation("Item não pertence ao pedido").with_field("items"))?;
+        let remaining = line.quantity - line.received_quantity;
+        if entry.quantity <= 0 || entry.quantity > remaining {
+            return Err(AppError::validation(format!(
+                "Quantidade recebida deve estar entre 1 e {} (restante do item)",
+                remaining
+            ))
+            .with_field("items"));
+        }
+        if entry.unit_cost.is_some_and(|c| !c.is_finite() || c < 0.0) {
+            return Err(AppError::validation("Custo unitário inválido").with_field("items"));
+        }
+        let unit_cost = entry.unit_cost.map(|c| (c * 100.0).round() / 100.0);
+        if line.received_quantity > 0 && unit_cost.is_some_and(|c| c != line.unit_cost) {
+            return Err(AppError::validation(
+                "O custo do item só pode mudar no primeiro recebimento; as unidades restantes entram pelo mesmo custo",
+            )
+            .with_field("items"));
+        }
+
+        line.received_quantity += entry.quantity;
+        let mut model: purchase_order_item::ActiveModel = line.clone().into();
+        model.received_quantity = Set(line.received_quantity);
+        if let Some(cost) = unit_cost {
+            line.unit_cost = cost;
+            model.unit_cost = Set(cost);
+        }
+        model.update(&txn).await?;
+
+        let product = product::Entity::find_by_id(line.product_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::not_found("Produto não encontrado"))?;
+        let cost_price = average_cost(product.stock_quantity, product.cost_price, entry.quantity, line.unit_cost);
+        let mut product: product::ActiveModel = product.into();
+        product.cost_price = Set(cost_price);
+        product.update(&txn).await?;
+
+        let movement = stock::Movement {
+            reference_id: Some(id),
+            username: Some(username.to_string()),
+            ..stock::Movement::new(line.product_id, entry.quantity, StockReason::Purchase)
+        };
+        stock::record(&txn, movement).await?;
+    }
+
+    let complete = lines.values().all(|l| l.received_quantity >= l.quantity);
+    let mut order: purchase_order::ActiveModel = order.into();
+    if complete {
+        order.status = Set(PurchaseStatus::Received.as_str().to_string());
+        order.received_at = Set(Some(Utc::now()));
+    } else {
+        order.status = Set(PurchaseStatus::Partial.as_str().to_string());
+    }
+    order.update(&txn).await?;
+    txn.commit().await?;
+
+    purchase_order_details(db, id).await
+}
+
+/// Cancela o que falta receber. O que já chegou continua no estoque. O
+/// cancelamento fica na auditoria com o usuário responsável.
+pub async fn cancel_purchase_order(
+    db: &DatabaseConnection,
+    id: i32,
+    username: &str,
+) -> Result<purchase_order::Model, AppError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(AppError::validation("Usuário responsável não informado").with_field("username"));
+    }
+
+    let txn = db.begin().await?;
+    let order = purchase_order::Entity::find_by_id(id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::not_found("Pedido de compra não encontrado"))?;
+    if !PurchaseStatus::accepts_changes(&order.status) {
+        return Err(AppError::conflict("Pedido já recebido ou cancelado"));
+    }
+    let previous_status = order.status.clone();
+    let mut order: purchase_order::ActiveModel = order.into();
+    order.status = Set(PurchaseStatus::Cancelled.as_str().to_string());
+    order.cancelled_at = Set(Some(Utc::now()));
+    let order = order.update(&txn).await?;
+    let details = serde_json::json!({ "previous_status": previous_status });
+    audit::record(&txn, ACTION_CANCEL, audit::PURCHASE_ORDER, id, username, Some(details)).await?;
+    txn.commit().await?;
+    Ok(order)
+}
+
+/// Pedidos do mais recente para o mais antigo, opcionalmente de uma situação.
+pub async fn list_purchase_orders(
+    db: &DatabaseConnection,
+    status: Option<PurchaseStatus>,
+) -> Result<Vec<PurchaseOrderSummary>, AppError> {
+    let mut select = purchase_order::Entity::find().find_also_related(supplier::Entity);
+    if let Some(status) = status {
+        select = select.filter(purchase_order::Column::Status.eq(status.as_str()));
+    }
+    let orders = select.order_by_desc(purchase_order::Column::Id).all(db).await?;
+
+    let mut items: HashMap<i32, Vec<purchase_order_item::Model>> = HashMap::new();
+    for item in purchase_order_item::Entity::find()
+        .filter(purchase_order_item::Column::PurchaseOrderId.is_in(orders.iter().map(|(o, _)| o.id)))
+        .all(db)
+        .await?
+    {
+        items.entry(item.purchase_order_id).or_default().push(item);
+    }
+
+    Ok(orders
+        .into_iter()
+        .map(|(order, supplier)| {
+            let lines = items.remove(&order.id).unwrap_or_default();
+            PurchaseOrderSummary {
+                supplier_name: supplier.map(|s| s.name).unwrap_or_default(),
+                total: order_total(&lines),
+                item_count: lines.len(),
+                order,
+            }
+        })
+        .collect())
+}
+
+pub async fn purchase_order_details(db: &DatabaseConnection, id: i32) -> Result<PurchaseOrderDetails, AppError> {
+    let (order, supplier) = purchase_order::Entity::find_by_id(id)
+        .find_also_related(supplier::Entity)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Pedido de compra não encontrado"))?;
+    let supplier = supplier.ok_or_else(|| AppError::not_found("Fornecedor não encontrado"))?;
+    let lines = purchase_order_item::Entity::find()
+        .find_also_related(product::Entity)
+        .filter(purchase_order_item::Column::PurchaseOrderId.eq(id))
+        .order_by_asc(purchase_order_item::Column::Id)
+        .all(db)
+        .await?;
+
+    let total = order_total(&lines.iter().map(|(i, _)| i.clone()).collect::<Vec<_>>());
+    Ok(PurchaseOrderDetails {
+        order,
+        supplier,
+        items: lines
+            .into_iter()
+            .map(|(item, product)| PurchaseOrderLine {
+                item,
+                product_name: product.map(|p| p.name).unwrap_or_default(),
+            })
+            .collect(),
+        total,
+    })
+}
+
+/// Custo médio ponderado após a entrada de `quantity` unidades a
+/// `unit_cost`. Sem saldo positivo ou sem custo conhecido (produtos
+/// anteriores ao controle de custo), vale o custo da compra.
+pub fn average_cost(stock_quantity: i32, cost_price: f64, quantity: i32, unit_cost: f64) -> f64 {
+    if stock_quantity <= 0 || cost_price <= 0.0 {
+        return unit_cost;
+    }
+    let stock = f64::from(stock_quantity);
+    let total = stock * cost_price + f64::from(quantity) * unit_cost;
+    // Quatro casas para o arredondamento não se acumular a cada compra
+    (total / (stock + f64::from(quantity)) * 10_000.0).round() / 10_000.0
+}
+
+fn order_total(items: &[purchase_order_item::Model]) -> f64 {
+    let total: f64 = items.iter().map(|i| f64::from(i.quantity) * i.unit_cost).sum();
+    (total * 100.0).round() / 100.0
+}
@@ -0,0 +1,531 @@
+//! Consultas do dashboard e dos relatórios.
+
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, FromQueryResult, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::db::entities::{category, client, client_gallon, product, sale};
+use crate::error::AppError;
+use crate::sales::client_name;
+use crate::timezone;
+
+/// Totais de vendas calculados pelo banco.
+#[derive(Debug, Default, FromQueryResult)]
+pub struct SalesTotals {
+    pub revenue: Option<f64>,
+    pub count: i64,
+    pub clients: i64,
+}
+
+impl SalesTotals {
+    pub fn revenue(&self) -> f64 {
+        self.revenue.unwrap_or(0.0)
+    }
+}
+
+/// Ponto do gráfico de faturamento: dia local (AAAA-MM-DD) e total vendido.
+#[derive(Clone, Debug, PartialEq, Serialize, TS)]
+pub struct DailyRevenue {
+    pub date: String,
+    pub revenue: f64,
+}
+
+/// Indicadores de hoje comparados com ontem. As variações são razões
+/// (0.25 = +25%) e ficam `null` quando ontem não teve movimento.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct DashboardStats {
+    pub revenue: f64,
+    pub revenue_yesterday: f64,
+    pub revenue_change: Option<f64>,
+    #[ts(type = "number")]
+    pub sales_count: i64,
+    #[ts(type = "number")]
+    pub sales_yesterday: i64,
+    /// Diferença absoluta de pedidos em relação a ontem.
+    #[ts(type = "number")]
+    pub sales_change: i64,
+    #[ts(type = "number")]
+    pub client_count: u64,
+    #[ts(type = "number")]
+    pub alerts: u64,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ExpiringGallon {
+    pub id: i32,
+    pub client_name: String,
+    pub brand: String,
+    pub expiration_date: chrono::DateTime<chrono::Utc>,
+}
+
+/// Valor de um indicador no período e no período anterior. `change` é a
+/// variação como razão (0.25 = +25%), `null` quando o anterior é zero.
+#[derive(Clone, Debug, PartialEq, Serialize, TS)]
+pub struct Metric {
+    pub value: f64,
+    pub previous: f64,
+    pub change: Option<f64>,
+}
+
+impl Metric {
+    pub fn new(value: f64, previous: f64) -> Self {
+        Metric { value, previous, change: change_ratio(value, previous) }
+    }
+}
+
+/// Variação relativa entre dois valores; `None` se não há base de comparação.
+pub fn change_ratio(current: f64, previous: f64) -> Option<f64> {
+    if previous == 0.0 {
+        None
+    } else {
+        Some((current - previous) / previous)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ReportSummary {
+    pub revenue: Metric,
+    pub sales_count: Metric,
+    pub average_ticket: Metric,
+    pub unique_clients: Metric,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ReportSale {
+    pub id: i32,
+    pub client_name: String,
+    /// Itens da venda em JSON, como gravados.
+    pub items: String,
+    pub total: f64,
+    pub payment_method: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct SalesReport {
+    pub summary: ReportSummary,
+    pub chart_data: Vec<DailyRevenue>,
+    pub sales_list: Vec<ReportSale>,
+}
+
+/// Receita, custo e lucro bruto de um produto, categoria ou dia.
+#[derive(Clone, Debug, Default, Serialize, TS)]
+pub struct ProfitLine {
+    pub label: String,
+    pub quantity: f64,
+    pub revenue: f64,
+    pub cost: f64,
+    pub gross_profit: f64,
+    /// Lucro bruto sobre a receita; `None` sem receita.
+    pub margin: Option<f64>,
+}
+
+impl ProfitLine {
+    fn add(&mut self, quantity: f64, revenue: f64, cost: f64) {
+        self.quantity += quantity;
+        self.revenue += revenue;
+        self.cost += cost;
+    }
+
+    fn finish(mut self) -> Self {
+        self.revenue = round_money(self.revenue);
+        self.cost = round_money(self.cost);
+        self.gross_profit = round_money(self.revenue - self.cost);
+        self.margin = (self.revenue > 0.0).then(|| self.gross_profit / self.revenue);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ProfitabilityReport {
+    pub summary: ProfitLine,
+    /// Itens vendidos sem custo conhecido, contados com custo zero.
+    pub items_without_cost: u32,
+    pub by_product: Vec<ProfitLine>,
+    pub by_category: Vec<ProfitLine>,
+    /// Por dia local (AAAA-MM-DD), em ordem cronológica.
+    pub by_day: Vec<ProfitLine>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct HourlyRevenue {
+    hour: String,
+    revenue: f64,
+}
+
+/// Itens vendidos de um produto (ou nome, sem produto) em uma hora UTC.
+#[derive(Debug, FromQueryResult)]
+struct ProfitRow {
+    hour: String,
+    product_name: Option<String>,
+    category: Option<String>,
+    item_name: Option<String>,
+    quantity: f64,
+    revenue: f64,
+    cost: f64,
+    without_cost: i64,
+}
+
+/// Itens da venda para o `json_each`; JSON inválido ou que não seja uma
+/// lista não gera linhas.
+const SALE_ITEMS: &str = "CASE WHEN json_valid(sales.items) \
+     THEN CASE json_type(sales.items) WHEN 'array' THEN sales.items END END";
+const ITEM_NAME: &str = "TRIM(json_extract(item.value, '$.name'))";
+const ITEM_QTY: &str = "json_extract(item.value, '$.qty')";
+const ITEM_PRICE: &str = "json_extract(item.value, '$.price')";
+/// Produto do item: pelo id gravado ou, nas vendas antigas, pelo nome.
+const ITEM_PRODUCT: &str = "COALESCE(json_extract(item.value, '$.id'), \
+     (SELECT named.id FROM products named \
+      WHERE LOWER(TRIM(named.name)) = LOWER(TRIM(json_extract(item.value, '$.name'))) LIMIT 1))";
+/// Custo gravado no item ou, sem ele, o custo atual do produto.
+const ITEM_COST: &str = "COALESCE(json_extract(item.value, '$.cost'), p.cost_price, 0)";
+
+/// Soma, quantidade e clientes distintos das vendas que atendem à condição.
+pub async fn sales_totals(db: &DatabaseConnection, condition: Condition) -> Result<SalesTotals, AppError> {
+    sale::Entity::find()
+        .select_only()
+        .column_as(Expr::col(sale::Column::Total).sum(), "revenue")
+        .column_as(Expr::col(sale::Column::Id).count(), "count")
+        .column_as(Expr::col(sale::Column::ClientId).count_distinct(), "clients")
+        .filter(condition)
+        .into_model::<SalesTotals>()
+        .one(db)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(AppError::from)
+}
+
+fn sale_hour() -> SimpleExpr {
+    Func::cust(sea_orm::sea_query::Alias::new("strftime"))
+        .arg("%Y-%m-%d %H")
+        .arg(Expr::col((sale::Entity, sale::Column::CreatedAt)))
+        .into()
+}
+
+/// Faturamento agrupado por dia local (AAAA-MM-DD), em ordem cronológica.
+///
+/// O banco agrupa por hora UTC e as horas são somadas no dia local do fuso
+/// do negócio, o que respeita mudanças de horário de verão. Por isso
+/// [`timezone::parse`] só aceita fusos de horas inteiras.
+pub async fn daily_revenue(
+    db: &DatabaseConnection,
+    condition: Condition,
+    tz: chrono_tz::Tz,
+) -> Result<Vec<DailyRevenue>, AppError> {
+    use std::collections::BTreeMap;
+
+    let hours = sale::Entity::find()
+        .select_only()
+        .column_as(sale_hour(), "hour")
+        .column_as(Expr::col(sale::Column::Total).sum(), "revenue")
+        .filter(condition)
+        .group_by(sale_hour())
+        .order_by_asc(sale_hour())
+        .into_model::<HourlyRevenue>()
+        .all(db)
+        .await?;
+
+    let mut days: BTreeMap<chrono::NaiveDate, f64> = BTreeMap::new();
+    for h in hours {
+        *days.entry(local_day(&h.hour, tz)?).or_insert(0.0) += h.revenue;
+    }
+
+    Ok(days
+        .into_iter()
+        .map(|(date, revenue)| DailyRevenue { date: date.to_string(), revenue })
+        .collect())
+}
+
+/// Dia local de uma hora UTC no formato de [`sale_hour`].
+fn local_day(hour: &str, tz: chrono_tz::Tz) -> Result<chrono::NaiveDate, AppError> {
+    use chrono::{NaiveDateTime, TimeZone, Utc};
+
+    let utc = NaiveDateTime::parse_from_str(&format!("{}:00", hour), "%Y-%m-%d %H:%M")
+        .map_err(|e| AppError::internal(format!("Data de venda inválida ({}): {}", hour, e)))?;
+    Ok(timezone::local_date(tz, Utc.from_utc_datetime(&utc)))
+}
+
+/// Início e fim (RFC 3339) do período dos relatórios, em UTC.
+pub fn parse_period(
+    start_iso: &str,
+    end_iso: &str,
+) -> Result<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>), AppError> {
+    use chrono::{DateTime, Utc};
+
+    let start = DateTime::parse_from_rfc3339(start_iso)
+        .map_err(|e| AppError::validation(format!("Data de início inválida: {}", e)).with_field("start_iso"))?
+        .with_timezone(&Utc);
+
+    let end = DateTime::parse_from_rfc3339(end_iso)
+        .map_err(|e| AppError::validation(format!("Data final inválida: {}", e)).with_field("end_iso"))?
+        .with_timezone(&Utc);
+
+    Ok((start, end))
+}
+
+/// Vendas do período, opcionalmente filtradas pela forma de pagamento.
+pub(crate) fn period_condition<T>(start: T, end: T, payment_method: &str) -> Condition
+where
+    T: Into<sea_orm::Value>,
+{
+    let mut condition = Condition::all().add(sale::Column::CreatedAt.between(start, end));
+    if payment_method != "todos" {
+        condition = condition.add(sale::Column::PaymentMethod.eq(payment_method));
+    }
+    condition
+}
+
+pub async fn dashboard_stats(db: &DatabaseConnection) -> Result<DashboardStats, AppError> {
+    dashboard_stats_at(db, chrono::Utc::now()).await
+}
+
+/// Indicadores do dashboard considerando `now` como o instante atual.
+pub async fn dashboard_stats_at(
+    db: &DatabaseConnection,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<DashboardStats, AppError> {
+    use chrono::Duration;
+
+    // "Hoje" e "ontem" seguem o fuso do negócio, não a meia-noite UTC
+    let tz = timezone::business_timezone(db).await;
+    let today = timezone::local_date(tz, now);
+    let today_start = timezone::day_start(tz, today);
+    let yesterday_start = timezone::day_start(tz, today - Duration::days(1));
+
+    // Vendas hoje
+    let today = sales_totals(db, Condition::all().add(sale::Column::CreatedAt.gte(today_start))).await?;
+    let revenue_today = today.revenue();
+    let count_today = today.count;
+
+    // Vendas ontem
+    let yesterday = sales_totals(
+        db,
+        Condition::all().add(sale::Column::CreatedAt.between(yesterday_start, today_start)),
+    )
+    .await?;
+    let revenue_yesterday = yesterday.revenue();
+    let count_yesterday = yesterday.count;
+
+    let client_count = client::Entity::find()
+        .count(db)
+        .await?;
+
+    // Alertas (Estoque abaixo do mínimo do produto + Galões vencendo em 30 dias)
+    let low_stock_count = product::Entity::find()
+        .filter(Expr::col(product::Column::StockQuantity).lt(Expr::col(product::Column::MinStock)))
+        .count(db)
+        .await?;
+
+    let expiring_gallons_count = client_gallon::Entity::find()
+        .filter(client_gallon::Column::ExpirationDate.between(now, now + Duration::days(30)))
+        .count(db)
+        .await?;
+
+    let total_alerts = low_stock_count + expiring_gallons_count;
+
+    Ok(DashboardStats {
+        revenue: revenue_today,
+        revenue_yesterday,
+        revenue_change: change_ratio(revenue_today, revenue_yesterday),
+        sales_count: count_today,
+        sales_yesterday: count_yesterday,
+        sales_change: count_today - count_yesterday,
+        client_count,
+        alerts: total_alerts,
+    })
+}
+
+/// Galões que vencem nos próximos 30 dias, com o nome do cliente.
+pub async fn expiring_gallons(db: &DatabaseConnection) -> Result<Vec<ExpiringGallon>, AppError> {
+    use chrono::Utc;
+
+    let now = Utc::now();
+    let month_away = now + chrono::Duration::days(30);
+
+    let gallons = client_gallon::Entity::find()
+        .find_also_related(client::Entity)
+        .filter(client_gallon::Column::ExpirationDate.between(now, month_away))
+        .order_by_asc(client_gallon::Column::ExpirationDate)
+        .all(db)
+        .await?;
+
+    Ok(gallons
+        .into_iter()
+        .map(|(g, client)| ExpiringGallon {
+            id: g.id,
+            client_name: client.map(|c| c.name).unwrap_or_else(|| "Cliente desconhecido".to_string()),
+            brand: g.brand,
+            expiration_date: g.expiration_date,
+        })
+        .collect())
+}
+
+/// Vendas que atendem à condição, das mais recentes para as mais antigas,
+/// com o nome do cliente.
+pub async fn report_sales(db: &DatabaseConnection, condition: Condition) -> Result<Vec<ReportSale>, AppError> {
+    let sales = sale::Entity::find()
+        .filter(condition)
+        .find_also_related(client::Entity)
+        .order_by_desc(sale::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(sales
+        .into_iter()
+        .map(|(sale, client)| ReportSale {
+            id: sale.id,
+            client_name: client_name(client),
+            items: sale.items,
+            total: sale.total,
+            payment_method: sale.payment_method,
+            created_at: sale.created_at,
+        })
+        .collect())
+}
+
+pub async fn sales_report(
+    db: &DatabaseConnection,
+    start_iso: &str,
+    end_iso: &str,
+    payment_method: &str,
+) -> Result<SalesReport, AppError> {
+    use chrono::Duration;
+
+    let (start_date, end_date) = parse_period(start_iso, end_iso)?;
+
+    let duration = end_date.signed_duration_since(start_date);
+    let prev_start = start_date - duration - Duration::seconds(1);
+    let prev_end = start_date - Duration::seconds(1);
+
+    let current = period_condition(start_date, end_date, payment_method);
+
+    // 1. Buscar vendas do período atual (já com o cliente de cada venda)
+    let sales_list = report_sales(db, current.clone()).await?;
+
+    // 2. Totais do período atual e do anterior (para comparação)
+    let totals = sales_totals(db, current.clone()).await?;
+    let prev_totals = sales_totals(db, period_condition(prev_start, prev_end, payment_method)).await?;
+
+    // Cálculos Atuais
+    let total_revenue = totals.revenue();
+    let sales_count = totals.count as f64;
+    let avg_ticket = if sales_count > 0.0 { total_revenue / sales_count } else { 0.0 };
+
+    // Cálculos Anteriores
+    let prev_revenue = prev_totals.revenue();
+    let prev_sales_count = prev_totals.count as f64;
+    let prev_avg_ticket = if prev_sales_count > 0.0 { prev_revenue / prev_sales_count } else { 0.0 };
+
+    // Dados para o Gráfico (agrupado por dia no fuso do negócio)
+    let tz = timezone::business_timezone(db).await;
+    let chart_data = daily_revenue(db, current, tz).await?;
+
+    Ok(SalesReport {
+        summary: ReportSummary {
+            revenue: Metric::new(total_revenue, prev_revenue),
+            sales_count: Metric::new(sales_count, prev_sales_count),
+            average_ticket: Metric::new(avg_ticket, prev_avg_ticket),
+            unique_clients: Metric::new(totals.clients as f64, prev_totals.clients as f64),
+        },
+        chart_data,
+        sales_list,
+    })
+}
+
+/// Lucratividade das vendas do período, com os mesmos filtros de
+/// [`sales_report`].
+///
+/// A receita é a soma dos itens (quantidade × preço), sem frete. O custo
+/// é o gravado no item na venda; vendas anteriores ao controle de custo
+/// usam o custo atual do produto, associado pelo id ou pelo nome.
+///
+/// O banco expande os itens com `json_each` e soma por hora UTC e produto;
+/// aqui só são montados os rótulos e os dias locais.
+pub async fn profitability_report(
+    db: &DatabaseConnection,
+    start_iso: &str,
+    end_iso: &str,
+    payment_method: &str,
+) -> Result<ProfitabilityReport, AppError> {
+    use sea_orm::sea_query::{Alias, JoinType, Query};
+    use sea_orm::ConnectionTrait;
+    use std::collections::{BTreeMap, HashMap};
+
+    let (start_date, end_date) = parse_period(start_iso, end_iso)?;
+    let (item, product, category) = (Alias::new("item"), Alias::new("p"), Alias::new("c"));
+    let mut query = Query::select();
+    query
+        .expr_as(sale_hour(), Alias::new("hour"))
+        .expr_as(Expr::cust("p.name"), Alias::new("product_name"))
+        .expr_as(Expr::cust("COALESCE(c.name, p.category)"), Alias::new("category"))
+        .expr_as(Expr::cust(format!("CASE WHEN p.id IS NULL THEN {} END", ITEM_NAME)), Alias::new("item_name"))
+        .expr_as(Expr::cust(format!("TOTAL({})", ITEM_QTY)), Alias::new("quantity"))
+        .expr_as(Expr::cust(format!("TOTAL({} * {})", ITEM_QTY, ITEM_PRICE)), Alias::new("revenue"))
+        .expr_as(Expr::cust(format!("TOTAL({} * {})", ITEM_QTY, ITEM_COST)), Alias::new("cost"))
+        .expr_as(
+            Expr::cust(format!("SUM(CASE WHEN {} <= 0 THEN 1 ELSE 0 END)", ITEM_COST)),
+            Alias::new("without_cost"),
+        )
+        .from(sale::Entity)
+        .from_function(Func::cust(Alias::new("json_each")).arg(Expr::cust(SALE_ITEMS)), item)
+        .join_as(JoinType::LeftJoin, product::Entity, product, Expr::cust(format!("p.id = {}", ITEM_PRODUCT)))
+        .join_as(JoinType::LeftJoin, category::Entity, category, Expr::cust("c.id = p.category_id"))
+        .cond_where(period_condition(start_date, end_date, payment_method))
+        .add_group_by([Expr::cust("hour"), Expr::cust("p.id"), Expr::cust("item_name")]);
+    let rows = ProfitRow::find_by_statement(db.get_database_backend().build(&query))
+        .all(db)
+        .await?;
+    let tz = timezone::business_timezone(db).await;
+
+    let mut summary = ProfitLine { label: "Total".to_string(), ..Default::default() };
+    let mut items_without_cost = 0;
+    let mut by_product: HashMap<String, ProfitLine> = HashMap::new();
+    let mut by_category: HashMap<String, ProfitLine> = HashMap::new();
+    let mut by_day: BTreeMap<chrono::NaiveDate, ProfitLine> = BTreeMap::new();
+    for row in rows {
+        let day = local_day(&row.hour, tz)?;
+        let (label, category) = match row.product_name {
+            Some(name) => (name, row.category.unwrap_or_default()),
+            None => (row.item_name.unwrap_or_default(), "Sem categoria".to_string()),
+        };
+        items_without_cost += row.without_cost as u32;
+        let (quantity, revenue, cost) = (row.quantity, row.revenue, row.cost);
+        summary.add(quantity, revenue, cost);
+        for (map, key) in [(&mut by_product, label), (&mut by_category, category)] {
+            map.entry(key.clone())
+                .or_insert_with(|| ProfitLine { label: key, ..Default::default() })
+                .add(quantity, revenue, cost);
+        }
+        by_day
+            .entry(day)
+            .or_insert_with(|| ProfitLine { label: day.to_string(), ..Default::default() })
+            .add(quantity, revenue, cost);
+    }
+
+    let ranked = |lines: HashMap<String, ProfitLine>| {
+        let mut lines: Vec<ProfitLine> = lines.into_values().map(ProfitLine::finish).collect();
+        lines.sort_by(|a, b| b.gross_profit.total_cmp(&a.gross_profit).then_with(|| a.label.cmp(&b.label)));
+        lines
+    };
+    Ok(ProfitabilityReport {
+        summary: summary.finish(),
+        items_without_cost,
+        by_product: ranked(by_product),
+        by_category: ranked(by_category),
+        by_day: by_day.into_values().map(ProfitLine::finish).collect(),
+    })
+}
+
+fn round_money(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Intervalo (em UTC) do dia, semana ou mês atual no fuso do negócio.
+pub async fn current_period(db: &DatabaseConnection, period: timezone::Period) -> timezone::Range {
+    let tz = timezone::business_timezone(db).await;
+    timezone::period_range(tz, chrono::Utc::now(), period)
+}
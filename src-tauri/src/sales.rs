@@ -0,0 +1,192 @@
+//! Registro e consultas de vendas.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::db::entities::{client, product, sale};
+use crate::error::AppError;
+use crate::stock::{self, StockReason};
+
+/// Nome exibido quando o cliente da venda não existe mais.
+pub const REMOVED_CLIENT: &str = "Cliente removido";
+
+/// Forma de pagamento das vendas a prazo, que ficam a receber do cliente.
+pub const RECEIVABLE_PAYMENT_METHOD: &str = "Fiado";
+
+/// Dados da venda enviados pelo PDV.
+#[derive(Clone, Debug)]
+pub struct SaleInput {
+    pub client_id: i32,
+    /// Itens no formato `[{ id, name, qty, price }]`; o `id` do produto
+    /// baixa o estoque. Ao gravar, cada item recebe o custo unitário do
+    /// produto em `cost`.
+    pub items: serde_json::Value,
+    pub total: f64,
+    pub payment_method: String,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct RecentSale {
+    pub id: i32,
+    pub client_name: String,
+    /// Itens da venda em JSON, como gravados.
+    pub items: String,
+    pub total: f64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct SaleDetails {
+    pub id: i32,
+    pub client_name: String,
+    pub items: serde_json::Value,
+    pub total: f64,
+    pub payment_method: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+pub(crate) fn client_name(client: Option<client::Model>) -> String {
+    client
+        .map(|c| c.name)
+        .unwrap_or_else(|| REMOVED_CLIENT.to_string())
+}
+
+/// Últimas cinco vendas com o nome do cliente, em uma única consulta.
+pub async fn recent_sales(db: &DatabaseConnection) -> Result<Vec<RecentSale>, AppError> {
+    let sales = sale::Entity::find()
+        .find_also_related(client::Entity)
+        .order_by_desc(sale::Column::Id)
+        .limit(5)
+        .all(db)
+        .await?;
+
+    Ok(sales
+        .into_iter()
+        .map(|(sale, client)| RecentSale {
+            id: sale.id,
+            client_name: client_name(client),
+            items: sale.items,
+            total: sale.total,
+            created_at: sale.created_at,
+        })
+        .collect())
+}
+
+pub async fn sale_details(db: &DatabaseConnection, id: i32) -> Result<SaleDetails, AppError> {
+    let (sale, client) = sale::Entity::find_by_id(id)
+        .find_also_related(client::Entity)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Venda não encontrada"))?;
+
+    Ok(SaleDetails {
+        id: sale.id,
+        client_name: client_name(client),
+        items: stored_items(&sale),
+        total: sale.total,
+        payment_method: sale.payment_method,
+        created_at: sale.created_at,
+    })
+}
+
+pub async fn create_sale(db: &DatabaseConnection, input: SaleInput) -> Result<sale::Model, AppError> {
+    let txn = db.begin().await?;
+    let items = with_costs(&txn, &input.items, &serde_json::json!([])).await?;
+    let sale = sale::ActiveModel {
+        client_id: Set(input.client_id),
+        items: Set(items.to_string()),
+        total: Set(input.total),
+        payment_method: Set(input.payment_method),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    stock::record_sale(&txn, sale.id, &serde_json::json!([]), &input.items, StockReason::Sale).await?;
+    txn.commit().await?;
+    Ok(sale)
+}
+
+pub async fn update_sale(db: &DatabaseConnection, id: i32, input: SaleInput) -> Result<sale::Model, AppError> {
+    let txn = db.begin().await?;
+    let current = sale::Entity::find_by_id(id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::not_found("Venda não encontrada"))?;
+    let before = stored_items(&current);
+    let items = with_costs(&txn, &input.items, &before).await?;
+
+    let mut sale: sale::ActiveModel = current.into();
+    sale.client_id = Set(input.client_id);
+    sale.items = Set(items.to_string());
+    sale.total = Set(input.total);
+    sale.payment_method = Set(input.payment_method);
+    let sale = sale.update(&txn).await?;
+    stock::record_sale(&txn, id, &before, &input.items, StockReason::Sale).await?;
+    txn.commit().await?;
+    Ok(sale)
+}
+
+/// Exclui a venda e devolve os itens ao estoque.
+pub async fn delete_sale(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    let txn = db.begin().await?;
+    if let Some(sale) = sale::Entity::find_by_id(id).one(&txn).await? {
+        sale::Entity::delete_by_id(id).exec(&txn).await?;
+        stock::record_sale(&txn, id, &stored_items(&sale), &serde_json::json!([]), StockReason::Return).await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+fn stored_items(sale: &sale::Model) -> serde_json::Value {
+    serde_json::from_str(&sale.items).unwrap_or(serde_json::json!([]))
+}
+
+fn item_product_id(item: &serde_json::Value) -> Option<i32> {
+    item.get("id").and_then(|id| id.as_i64()).and_then(|id| i32::try_from(id).ok())
+}
+
+/// Copia para `cost` de cada item o custo médio atual do produto. Na edição,
+/// produtos que já estavam na venda mantêm o custo da venda original.
+async fn with_costs<C: ConnectionTrait>(
+    db: &C,
+    items: &serde_json::Value,
+    before: &serde_json::Value,
+) -> Result<serde_json::Value, AppError> {
+    let Some(list) = items.as_array() else {
+        return Ok(items.clone());
+    };
+    let previous: HashMap<i32, f64> = before
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| Some((item_product_id(item)?, item.get("cost")?.as_f64()?)))
+        .collect();
+    let ids: Vec<i32> = list.iter().filter_map(item_product_id).collect();
+    let current: HashMap<i32, f64> = product::Entity::find()
+        .filter(product::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.cost_price))
+        .collect();
+
+    let mut items = list.clone();
+    for item in &mut items {
+        let (Some(id), Some(object)) = (item_product_id(item), item.as_object_mut()) else {
+            continue;
+        };
+        match previous.get(&id).or_else(|| current.get(&id)) {
+            Some(cost) => object.insert("cost".to_string(), serde_json::json!(cost)),
+            None => object.remove("cost"),
+        };
+    }
+    Ok(serde_json::Value::Array(items))
+}
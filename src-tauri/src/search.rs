@@ -0,0 +1,209 @@
+//! Busca textual de clientes e produtos sobre os índices FTS5
+//! criados em `db::setup_schema`.
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, FromQueryResult, Statement, Value};
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::error::AppError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Client,
+    Product,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub id: i32,
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Relevância calculada pelo bm25 (quanto menor, mais relevante).
+    pub rank: f64,
+}
+
+#[derive(FromQueryResult)]
+struct Row {
+    id: i32,
+    title: String,
+    subtitle: Option<String>,
+    score: f64,
+}
+
+/// Tabela FTS5 e como ligá-la à tabela de origem.
+struct Index {
+    table: &'static str,
+    columns: &'static [&'static str],
+    select: &'static str,
+    join: &'static str,
+}
+
+const CLIENTS: Index = Index {
+    table: "clients_fts",
+    columns: &["name", "phone", "document", "address", "observations"],
+    select: "c.id AS id, c.name AS title, COALESCE(c.phone, c.address) AS subtitle",
+    join: "JOIN clients c ON c.id = clients_fts.rowid",
+};
+
+const PRODUCTS: Index = Index {
+    table: "products_fts",
+    columns: &["name", "category"],
+    select: "p.id AS id, p.name AS title, p.category AS subtitle",
+    join: "JOIN products p ON p.id = products_fts.rowid",
+};
+
+/// O tokenizador trigram só encontra termos com 3 ou mais caracteres.
+const MIN_TERM_LEN: usize = 3;
+
+/// Letras acentuadas do português e suas versões sem acento, na mesma
+/// ordem. Os termos curtos são filtrados com GLOB, que não ignora acentos
+/// como o índice; por isso cada letra vira uma classe com suas variantes.
+const ACCENTED: &str = "áàâãäéèêëíìîïóòôõöúùûüçñÁÀÂÃÄÉÈÊËÍÌÎÏÓÒÔÕÖÚÙÛÜÇÑ";
+const PLAIN: &str = "aaaaaeeeeiiiiooooouuuucnAAAAAEEEEIIIIOOOOOUUUUCN";
+
+/// Padrão GLOB que encontra o termo em qualquer posição, sem diferenciar
+/// maiúsculas nem acentos.
+fn glob_pattern(term: &str) -> String {
+    let plain = |c: char| {
+        ACCENTED
+            .chars()
+            .zip(PLAIN.chars())
+            .find(|(from, _)| *from == c)
+            .map_or(c, |(_, to)| to)
+            .to_ascii_lowercase()
+    };
+    let class = |c: char| {
+        let base = plain(c);
+        if base.is_ascii_alphabetic() {
+            let variants: String = [base, base.to_ascii_uppercase()]
+                .into_iter()
+                .chain(ACCENTED.chars().filter(|a| plain(*a) == base))
+                .collect();
+            format!("[{}]", variants)
+        } else if "*?[".contains(c) {
+            format!("[{}]", c)
+        } else {
+            c.to_string()
+        }
+    };
+    format!("*{}*", term.chars().map(class).collect::<String>())
+}
+
+/// Separa a busca em termos. Termos que parecem telefone ou documento
+/// (apenas dígitos e pontuação) são reduzidos aos dígitos.
+fn terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|t| {
+            let is_numeric = t.chars().any(|c| c.is_ascii_digit())
+                && t.chars().all(|c| c.is_ascii_digit() || "()-./+".contains(c));
+            if is_numeric {
+                t.chars().filter(|c| c.is_ascii_digit()).collect()
+            } else {
+                t.to_string()
+            }
+        })
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Monta a expressão MATCH (termos entre aspas, combinados com AND) e os
+/// termos curtos, que são filtrados com GLOB.
+fn build_query(query: &str) -> (Option<String>, Vec<String>) {
+    let (long, short): (Vec<_>, Vec<_>) = terms(query)
+        .into_iter()
+        .partition(|t| t.chars().count() >= MIN_TERM_LEN);
+
+    let expr = if long.is_empty() {
+        None
+    } else {
+        Some(
+            long.iter()
+                .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        )
+    };
+    (expr, short)
+}
+
+async fn search_index(
+    db: &DatabaseConnection,
+    index: &Index,
+    match_expr: &Option<String>,
+    short_terms: &[String],
+    limit: u64,
+) -> Result<Vec<Row>, AppError> {
+    let table = index.table;
+    let mut conditions = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(expr) = match_expr {
+        conditions.push(format!("{} MATCH ?", table));
+        values.push(expr.clone().into());
+    }
+    for term in short_terms {
+        let any_column = index
+            .columns
+            .iter()
+            .map(|c| format!("{}.{} GLOB ?", table, c))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        conditions.push(format!("({})", any_column));
+        let pattern = glob_pattern(term);
+        for _ in index.columns {
+            values.push(pattern.clone().into());
+        }
+    }
+    values.push((limit as i64).into());
+
+    let score = if match_expr.is_some() {
+        format!("bm25({})", table)
+    } else {
+        "0.0".to_string()
+    };
+    let sql = format!(
+        "SELECT {select}, {score} AS score FROM {table} {join} WHERE {conditions} ORDER BY score LIMIT ?",
+        select = index.select,
+        score = score,
+        table = table,
+        join = index.join,
+        conditions = conditions.join(" AND "),
+    );
+
+    let backend = db.get_database_backend();
+    Row::find_by_statement(Statement::from_sql_and_values(backend, sql, values))
+        .all(db)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Busca clientes e produtos, devolvendo os resultados ordenados por relevância.
+pub async fn search(db: &DatabaseConnection, query: &str, limit: u64) -> Result<Vec<SearchResult>, AppError> {
+    let (match_expr, short_terms) = build_query(query);
+    if match_expr.is_none() && short_terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clients = search_index(db, &CLIENTS, &match_expr, &short_terms, limit).await?;
+    let products = search_index(db, &PRODUCTS, &match_expr, &short_terms, limit).await?;
+
+    let mut results: Vec<SearchResult> = clients
+        .into_iter()
+        .map(|r| (SearchKind::Client, r))
+        .chain(products.into_iter().map(|r| (SearchKind::Product, r)))
+        .map(|(kind, r)| SearchResult {
+            kind,
+            id: r.id,
+            title: r.title,
+            subtitle: r.subtitle,
+            rank: r.score,
+        })
+        .collect();
+
+    results.sort_by(|a, b| a.rank.partial_cmp(&b.rank).unwrap_or(std::cmp::Ordering::Equal));
+    results.truncate(limit as usize);
+    Ok(results)
+}
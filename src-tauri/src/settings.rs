@@ -0,0 +1,112 @@
+//! Configurações gerais e cadastros auxiliares (formas de envio e pagamento).
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+use crate::db::entities::{payment_method, setting, shipping_method};
+use crate::error::AppError;
+use crate::{backup, timezone};
+
+pub async fn list_settings(db: &DatabaseConnection) -> Result<Vec<setting::Model>, AppError> {
+    setting::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+/// Grava a configuração, validando as chaves com formato conhecido.
+pub async fn save_setting(db: &DatabaseConnection, key: String, value: String) -> Result<setting::Model, AppError> {
+    let value = if key == timezone::SETTING_KEY {
+        timezone::parse(&value)?.name().to_string()
+    } else if let Some(normalized) = backup::validate_setting(&key, &value)? {
+        normalized
+    } else {
+        value
+    };
+
+    let model = setting::ActiveModel {
+        key: Set(key.clone()),
+        value: Set(value),
+    };
+    setting::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(setting::Column::Key)
+                .update_column(setting::Column::Value)
+                .to_owned(),
+        )
+        .exec(db)
+        .await?;
+
+    setting::Entity::find_by_id(key)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Configuração não encontrada"))
+}
+
+pub async fn list_shipping_methods(db: &DatabaseConnection) -> Result<Vec<shipping_method::Model>, AppError> {
+    shipping_method::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+pub async fn create_shipping_method(
+    db: &DatabaseConnection,
+    name: String,
+    fee: f64,
+) -> Result<shipping_method::Model, AppError> {
+    let method = shipping_method::ActiveModel {
+        name: Set(name),
+        fee: Set(fee),
+        ..Default::default()
+    };
+    method.insert(db).await.map_err(AppError::from)
+}
+
+pub async fn update_shipping_method(
+    db: &DatabaseConnection,
+    id: i32,
+    name: String,
+    fee: f64,
+) -> Result<shipping_method::Model, AppError> {
+    let mut method: shipping_method::ActiveModel = shipping_method::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Método de envio não encontrado"))?
+        .into();
+
+    method.name = Set(name);
+    method.fee = Set(fee);
+    method.update(db).await.map_err(AppError::from)
+}
+
+pub async fn delete_shipping_method(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    shipping_method::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn list_payment_methods(db: &DatabaseConnection) -> Result<Vec<payment_method::Model>, AppError> {
+    payment_method::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+pub async fn create_payment_method(db: &DatabaseConnection, name: String) -> Result<payment_method::Model, AppError> {
+    let method = payment_method::ActiveModel {
+        name: Set(name),
+        ..Default::default()
+    };
+    method.insert(db).await.map_err(AppError::from)
+}
+
+pub async fn update_payment_method(
+    db: &DatabaseConnection,
+    id: i32,
+    name: String,
+) -> Result<payment_method::Model, AppError> {
+    let mut method: payment_method::ActiveModel = payment_method::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Método de pagamento não encontrado"))?
+        .into();
+
+    method.name = Set(name);
+    method.update(db).await.map_err(AppError::from)
+}
+
+pub async fn delete_payment_method(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    payment_method::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
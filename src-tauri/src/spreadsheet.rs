@@ -0,0 +1,540 @@
+//! Planilhas para o contador: CSV no padrão do Excel brasileiro (`;`,
+//! vírgula decimal e BOM UTF-8) e XLSX.
+//!
+//! O XLSX é montado à mão (um zip com o XML mínimo do SpreadsheetML), com
+//! uma única aba, textos inline e estilos para cabeçalho, valores e datas.
+//! Na leitura ([`read`]) vale só a primeira aba e as células chegam como
+//! texto, do jeito que estão gravadas.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use ts_rs::TS;
+use zip::write::SimpleFileOptions;
+
+use crate::error::AppError;
+
+/// Marca de ordem de bytes: sem ela o Excel abre o CSV como Latin-1.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Limites de uma planilha do Excel (linhas × colunas).
+const MAX_ROWS: usize = 1_048_576;
+const MAX_COLUMNS: usize = 16_384;
+
+/// Caracteres dos bytes 0x80–0x9F no Windows-1252; os demais bytes
+/// coincidem com o Latin-1. Posições não definidas ficam como controle.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Xlsx,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "xlsx" => Ok(Format::Xlsx),
+            other => Err(AppError::validation(format!("Formato de planilha inválido: {}", other))
+                .with_field("format")),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Integer(i64),
+    /// Valor em reais, com duas casas.
+    Money(f64),
+    /// Data e hora já no fuso do negócio.
+    DateTime(NaiveDateTime),
+    Empty,
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Cell::Text(value)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Cell::Text(value.to_string())
+    }
+}
+
+impl From<Option<String>> for Cell {
+    fn from(value: Option<String>) -> Self {
+        value.map(Cell::Text).unwrap_or(Cell::Empty)
+    }
+}
+
+/// Uma aba: cabeçalho e linhas com o mesmo número de colunas.
+#[derive(Clone, Debug)]
+pub struct Sheet {
+    pub name: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Sheet {
+    pub fn new(name: &str, headers: &[&str]) -> Self {
+        Sheet {
+            name: name.to_string(),
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// Arquivo pronto para o frontend salvar, com o conteúdo em base64.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ExportedFile {
+    pub file_name: String,
+    pub content: String,
+}
+
+pub fn write(sheet: &Sheet, format: Format) -> Result<Vec<u8>, AppError> {
+    match format {
+        Format::Csv => to_csv(sheet),
+        Format::Xlsx => to_xlsx(sheet),
+    }
+}
+
+pub fn to_csv(sheet: &Sheet) -> Result<Vec<u8>, AppError> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_writer(UTF8_BOM.to_vec());
+    writer.write_record(&sheet.headers).map_err(csv_error)?;
+    for row in &sheet.rows {
+        writer.write_record(row.iter().map(csv_field)).map_err(csv_error)?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| AppError::internal(format!("Erro ao gerar CSV: {}", e)))
+}
+
+fn csv_error(e: csv::Error) -> AppError {
+    AppError::internal(format!("Erro ao gerar CSV: {}", e))
+}
+
+fn csv_field(cell: &Cell) -> String {
+    match cell {
+        // Textos que começam como fórmula seriam executados pelo Excel;
+        // telefones e números (`+55 27 99999-1234`, `-5`) saem como estão
+        Cell::Text(text) if text.starts_with(['=', '+', '-', '@', '\t', '\r']) && !is_plain_number(text) => {
+            format!("'{}", text)
+        }
+        Cell::Text(text) => text.clone(),
+        Cell::Integer(value) => value.to_string(),
+        Cell::Money(value) => format!("{:.2}", value).replace('.', ","),
+        Cell::DateTime(at) => at.format("%d/%m/%Y %H:%M").to_string(),
+        Cell::Empty => String::new(),
+    }
+}
+
+/// Telefone ou número: `+` opcional seguido só de dígitos, espaços,
+/// parênteses e hífens.
+fn is_plain_number(text: &str) -> bool {
+    let rest = text.strip_prefix('+').unwrap_or(text);
+    rest.bytes().any(|b| b.is_ascii_digit())
+        && rest.bytes().all(|b| b.is_ascii_digit() || matches!(b, b' ' | b'(' | b')' | b'-'))
+}
+
+// Índices em `cellXfs` de STYLES
+const STYLE_HEADER: u8 = 1;
+const STYLE_MONEY: u8 = 2;
+const STYLE_DATE_TIME: u8 = 3;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><numFmts count="1"><numFmt numFmtId="164" formatCode="dd/mm/yyyy hh:mm"/></numFmts><fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="4"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/><xf numFmtId="4" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="164" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/></cellXfs></styleSheet>"#;
+
+pub fn to_xlsx(sheet: &Sheet) -> Result<Vec<u8>, AppError> {
+    let workbook = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        escape(&sheet_name(&sheet.name))
+    );
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", ROOT_RELS.to_string()),
+        ("xl/workbook.xml", workbook),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.to_string()),
+        ("xl/styles.xml", STYLES.to_string()),
+        ("xl/worksheets/sheet1.xml", worksheet(sheet)),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options).map_err(zip_error)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+fn zip_error(e: zip::result::ZipError) -> AppError {
+    AppError::internal(format!("Erro ao gerar XLSX: {}", e))
+}
+
+fn worksheet(sheet: &Sheet) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    let header = sheet.headers.iter().map(|h| Cell::Text(h.clone())).collect::<Vec<_>>();
+    push_row(&mut xml, 1, &header, Some(STYLE_HEADER));
+    for (i, row) in sheet.rows.iter().enumerate() {
+        push_row(&mut xml, i + 2, row, None);
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn push_row(xml: &mut String, number: usize, cells: &[Cell], style: Option<u8>) {
+    xml.push_str(&format!(r#"<row r="{}">"#, number));
+    for (i, cell) in cells.iter().enumerate() {
+        let reference = format!("{}{}", column_name(i), number);
+        let style = |default: u8| style.unwrap_or(default);
+        match cell {
+            Cell::Text(text) => xml.push_str(&format!(
+                r#"<c r="{}" t="inlineStr" s="{}"><is><t xml:space="preserve">{}</t></is></c>"#,
+                reference,
+                style(0),
+                escape(text)
+            )),
+            Cell::Integer(value) => {
+                xml.push_str(&format!(r#"<c r="{}" s="{}"><v>{}</v></c>"#, reference, style(0), value))
+            }
+            Cell::Money(value) => xml.push_str(&format!(
+                r#"<c r="{}" s="{}"><v>{}</v></c>"#,
+                reference,
+                style(STYLE_MONEY),
+                value
+            )),
+            Cell::DateTime(at) => xml.push_str(&format!(
+                r#"<c r="{}" s="{}"><v>{}</v></c>"#,
+                reference,
+                style(STYLE_DATE_TIME),
+                serial_date(*at)
+            )),
+            Cell::Empty => {}
+        }
+    }
+    xml.push_str("</row>");
+}
+
+/// Letra da coluna no estilo do Excel: 0 → A, 25 → Z, 26 → AA.
+fn column_name(index: usize) -> String {
+    let mut name = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        name.push(b'A' + rem as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Data no formato numérico do Excel: dias desde 30/12/1899.
+fn serial_date(at: NaiveDateTime) -> f64 {
+    let epoch = chrono::NaiveDate::from_ymd_opt(1899, 12, 30)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .unwrap_or_default();
+    (at - epoch).num_seconds() as f64 / 86_400.0
+}
+
+/// O Excel limita o nome da aba a 31 caracteres, sem `[]:*?/\`.
+fn sheet_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !"[]:*?/\\".contains(*c))
+        .take(31)
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // Caracteres de controle são inválidos em XML
+            c if c.is_control() && !matches!(c, '\n' | '\t' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Linhas da planilha como texto; a linha `i` do vetor é a linha `i + 1`
+/// do arquivo (linhas vazias do XLSX chegam como vetores vazios).
+pub fn read(bytes: &[u8], format: Format) -> Result<Vec<Vec<String>>, AppError> {
+    match format {
+        Format::Csv => read_csv(bytes),
+        Format::Xlsx => read_xlsx(bytes),
+    }
+}
+
+/// Aceita `;`, `,` ou tabulação como separador e arquivos salvos pelo
+/// Excel em Windows-1252, além de UTF-8.
+pub fn read_csv(bytes: &[u8]) -> Result<Vec<Vec<String>>, AppError> {
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode_windows_1252(bytes),
+    };
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = [b';', b',', b'\t']
+        .into_iter()
+        .max_by_key(|d| first_line.matches(*d as char).count())
+        .unwrap_or(b';');
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| AppError::validation(format!("CSV inválido: {}", e)))?;
+        rows.push(record.iter().map(|f| f.trim().to_string()).collect());
+    }
+    Ok(rows)
+}
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+            _ => b as char,
+        })
+        .collect()
+}
+
+pub fn read_xlsx(bytes: &[u8]) -> Result<Vec<Vec<String>>, AppError> {
+    let invalid = |e: &dyn std::fmt::Display| AppError::validation(format!("Arquivo XLSX inválido: {}", e));
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| invalid(&e))?;
+    let mut part = |name: &str| -> Result<Option<String>, AppError> {
+        let mut file = match zip.by_name(name) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(invalid(&e)),
+        };
+        let mut content = String::new();
+        file.read_to_string(&mut content).map_err(|e| invalid(&e))?;
+        Ok(Some(content))
+    };
+
+    let sheet_path = match (part("xl/workbook.xml")?, part("xl/_rels/workbook.xml.rels")?) {
+        (Some(workbook), Some(rels)) => first_sheet_path(&workbook, &rels).map_err(|e| invalid(&e))?,
+        _ => None,
+    }
+    .unwrap_or_else(|| "xl/worksheets/sheet1.xml".to_string());
+    let shared = match part("xl/sharedStrings.xml")? {
+        Some(xml) => shared_strings(&xml).map_err(|e| invalid(&e))?,
+        None => Vec::new(),
+    };
+    let sheet = part(&sheet_path)?.ok_or_else(|| invalid(&"planilha não encontrada"))?;
+    sheet_rows(&sheet, &shared).map_err(|e| invalid(&e))
+}
+
+type XmlResult<T> = Result<T, quick_xml::Error>;
+
+fn attribute(e: &quick_xml::events::BytesStart, name: &str) -> XmlResult<Option<String>> {
+    match e.try_get_attribute(name)? {
+        Some(a) => Ok(Some(a.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Texto de uma referência (`&amp;`, `&#233;`) dentro de um nó de texto.
+fn reference(r: &quick_xml::events::BytesRef) -> XmlResult<String> {
+    if let Some(c) = r.resolve_char_ref()? {
+        return Ok(c.to_string());
+    }
+    let name = r.decode()?;
+    Ok(quick_xml::escape::resolve_predefined_entity(&name)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("&{};", name)))
+}
+
+/// Caminho no zip da primeira aba do `workbook.xml`.
+fn first_sheet_path(workbook: &str, rels: &str) -> XmlResult<Option<String>> {
+    use quick_xml::events::Event;
+
+    let mut id = None;
+    let mut reader = quick_xml::Reader::from_str(workbook);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"sheet" => {
+                id = attribute(&e, "r:id")?;
+                break;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let Some(id) = id else { return Ok(None) };
+
+    let mut reader = quick_xml::Reader::from_str(rels);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == b"Relationship" && attribute(&e, "Id")?.as_deref() == Some(id.as_str()) =>
+            {
+                return Ok(attribute(&e, "Target")?.map(|target| match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{}", target),
+                }));
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+fn shared_strings(xml: &str) -> XmlResult<Vec<String>> {
+    use quick_xml::events::Event;
+
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"si" => current.clear(),
+            Event::End(e) if e.local_name().as_ref() == b"si" => strings.push(std::mem::take(&mut current)),
+            Event::Start(e) if e.local_name().as_ref() == b"t" => in_text = true,
+            Event::End(e) if e.local_name().as_ref() == b"t" => in_text = false,
+            Event::Text(t) if in_text => current.push_str(&t.decode()?),
+            Event::GeneralRef(r) if in_text => current.push_str(&reference(&r)?),
+            Event::Eof => return Ok(strings),
+            _ => {}
+        }
+    }
+}
+
+/// Linhas da aba. Referências fora dos limites do Excel são ignoradas,
+/// para que um arquivo malformado não aloque linhas ou colunas sem fim.
+fn sheet_rows(xml: &str, shared: &[String]) -> XmlResult<Vec<Vec<String>>> {
+    use quick_xml::events::Event;
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut current: Option<usize> = None;
+    let mut cell: Option<(usize, Option<String>)> = None;
+    let mut value = String::new();
+    let mut in_value = false;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"row" => {
+                let number = attribute(&e, "r")?
+                    .and_then(|r| r.parse::<usize>().ok())
+                    .filter(|n| (1..=MAX_ROWS).contains(n));
+                let index = number.map(|n| n - 1).unwrap_or(rows.len());
+                current = (index < MAX_ROWS).then_some(index);
+                if let Some(index) = current {
+                    while rows.len() <= index {
+                        rows.push(Vec::new());
+                    }
+                }
+            }
+            Event::Start(e) if e.local_name().as_ref() == b"c" => {
+                let next = current.and_then(|r| rows.get(r)).map(Vec::len).unwrap_or(0);
+                let column = match attribute(&e, "r")? {
+                    Some(reference) => column_index(&reference),
+                    None => Some(next),
+                };
+                let kind = attribute(&e, "t")?;
+                cell = column.map(|column| (column, kind));
+                value.clear();
+            }
+            Event::End(e) if e.local_name().as_ref() == b"c" => {
+                let cell = cell.take().filter(|(column, _)| *column < MAX_COLUMNS);
+                if let (Some((column, kind)), Some(row)) = (cell, current.and_then(|r| rows.get_mut(r))) {
+                    let text = match kind.as_deref() {
+                        Some("s") => value.trim().parse::<usize>().ok().and_then(|i| shared.get(i)).cloned().unwrap_or_default(),
+                        None | Some("n") => number_text(&value),
+                        _ => value.clone(),
+                    };
+                    while row.len() <= column {
+                        row.push(String::new());
+                    }
+                    row[column] = text.trim().to_string();
+                }
+            }
+            Event::Start(e) if matches!(e.local_name().as_ref(), b"v" | b"t") && cell.is_some() => in_value = true,
+            Event::End(e) if matches!(e.local_name().as_ref(), b"v" | b"t") => in_value = false,
+            Event::Text(t) if in_value => value.push_str(&t.decode()?),
+            Event::GeneralRef(r) if in_value => value.push_str(&reference(&r)?),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+/// Número de uma célula numérica, gravado pelo Excel como `12.5`, com a
+/// vírgula decimal do pt-BR, como a planilha mostra (`12,5`).
+fn number_text(raw: &str) -> String {
+    match raw.trim().parse::<f64>() {
+        Ok(number) if number.is_finite() => number.to_string().replace('.', ","),
+        _ => raw.to_string(),
+    }
+}
+
+/// Índice da coluna de uma referência como `AB12` (A = 0).
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: String = reference.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if letters.is_empty() {
+        return None;
+    }
+    letters
+        .to_ascii_uppercase()
+        .bytes()
+        .try_fold(0usize, |acc, b| acc.checked_mul(26)?.checked_add((b - b'A') as usize + 1))
+        .filter(|n| *n <= MAX_COLUMNS)
+        .map(|n| n - 1)
+}
+
+/// Cabeçalho normalizado para comparação: minúsculas, sem acentos e sem
+/// espaços nas pontas.
+pub fn normalize_header(header: &str) -> String {
+    let replacements: HashMap<char, char> = "áàâãäéèêëíìîïóòôõöúùûüçñ"
+        .chars()
+        .zip("aaaaaeeeeiiiiooooouuuucn".chars())
+        .collect();
+    header
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| *replacements.get(&c).unwrap_or(&c))
+        .collect()
+}
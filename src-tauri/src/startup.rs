@@ -0,0 +1,204 @@
+//! Abertura do banco na inicialização e ações de recuperação.
+//!
+//! Se o banco não abrir, o app sobe numa tela de erro (veja
+//! `get_startup_status`) em vez de funcionar sem a conexão gerenciada.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use ts_rs::TS;
+
+use crate::error::AppError;
+use crate::{address, backup, db};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupState {
+    Ready,
+    /// Banco cifrado esperando a senha (`unlock_database`).
+    Locked,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct StartupStatus {
+    pub state: StartupState,
+    /// Motivo da falha ao abrir o banco (ou senha incorreta).
+    pub error: Option<String>,
+    pub data_dir: String,
+    pub backups: Vec<backup::BackupFile>,
+}
+
+/// Último erro de abertura do banco, gerenciado pelo Tauri.
+#[derive(Default)]
+pub struct Startup(Mutex<Option<String>>);
+
+impl Startup {
+    fn set_error(&self, error: Option<String>) {
+        *self.0.lock().unwrap() = error;
+    }
+
+    fn error(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Abre o banco e passa a gerenciar a conexão. Em caso de falha o erro fica
+/// registrado para a tela de recuperação. Um banco cifrado só é aberto com
+/// a senha `key`; sem ela o app fica bloqueado, sem erro.
+pub async fn initialize(handle: &AppHandle, key: Option<&str>) -> bool {
+    let encrypted = db::data_dir(handle).is_ok_and(|dir| awaits_key(&db::database_path(&dir)));
+    if encrypted && key.is_none() {
+        if let Some(startup) = handle.try_state::<Startup>() {
+            startup.set_error(None);
+        }
+        return false;
+    }
+
+    let result = db::init_db(handle, key).await;
+    let error = match result {
+        Ok(connection) => {
+            let connection = db::SharedConnection::new(db::Session {
+                connection,
+                key: key.map(str::to_string),
+            });
+            handle.manage(address::default_resolver(connection.clone()));
+            handle.manage(connection);
+            if let Ok(data_dir) = db::data_dir(handle) {
+                handle.manage(backup::Backups::new(db::backup_dir(&data_dir)));
+                backup::spawn_scheduler(handle.clone());
+            }
+            log::info!("Database initialized and managed.");
+            None
+        }
+        Err(e) if encrypted => {
+            log::error!("Failed to unlock database: {:#}", e);
+            Some("Senha incorreta ou banco de dados corrompido".to_string())
+        }
+        Err(e) => {
+            log::error!("Failed to initialize database: {:#}", e);
+            Some(format!("{:#}", e))
+        }
+    };
+
+    let ready = error.is_none();
+    if let Some(startup) = handle.try_state::<Startup>() {
+        startup.set_error(error);
+    }
+    ready
+}
+
+/// Se a abertura depende da senha do banco. Sem o SQLCipher compilado um
+/// arquivo que não parece SQLite nunca é tratado como bloqueado: ele cai
+/// na tela de recuperação com o erro de abertura.
+fn awaits_key(database: &Path) -> bool {
+    cfg!(feature = "sqlcipher") && db::is_encrypted(database)
+}
+
+pub fn is_ready(handle: &AppHandle) -> bool {
+    handle.try_state::<db::SharedConnection>().is_some()
+}
+
+pub fn status(handle: &AppHandle) -> Result<StartupStatus, AppError> {
+    let data_dir = db::data_dir(handle).map_err(|e| AppError::internal(e.to_string()))?;
+    let state = if is_ready(handle) {
+        StartupState::Ready
+    } else if awaits_key(&db::database_path(&data_dir)) {
+        StartupState::Locked
+    } else {
+        StartupState::Failed
+    };
+    let error = match state {
+        StartupState::Ready => None,
+        StartupState::Locked => handle.try_state::<Startup>().and_then(|s| s.error()),
+        StartupState::Failed => handle
+            .try_state::<Startup>()
+            .and_then(|s| s.error())
+            .or_else(|| Some("Banco de dados não inicializado".into())),
+    };
+
+    Ok(StartupStatus {
+        state,
+        error,
+        backups: backup::list_backups(&db::backup_dir(&data_dir)),
+        data_dir: data_dir.display().to_string(),
+    })
+}
+
+/// Resolve o nome de um backup dentro da pasta, recusando caminhos.
+pub fn backup_file(dir: &Path, name: &str) -> Result<PathBuf, AppError> {
+    let path = dir.join(name);
+    let plain_name = Path::new(name).file_name().is_some_and(|f| f == name);
+    if !plain_name || !path.is_file() {
+        return Err(AppError::not_found(format!("Backup {} não encontrado", name)));
+    }
+    Ok(path)
+}
+
+/// Recuperação na tela de erro ou de bloqueio: troca o banco que não abriu
+/// pelo backup `name` da pasta `dir`, conferido com
+/// [`backup::check_restore`]. Um banco cifrado exige a senha `key` e só é
+/// trocado por um backup cifrado com ela, para a troca não desligar a
+/// criptografia.
+pub async fn restore_backup(
+    database: &Path,
+    dir: &Path,
+    name: &str,
+    key: Option<&str>,
+) -> Result<Option<PathBuf>, AppError> {
+    let backup = backup_file(dir, name)?;
+    if awaits_key(database) && key.is_none() {
+        return Err(AppError::validation("Informe a senha do banco para restaurar um backup").with_field("passphrase"));
+    }
+    backup::check_restore(&backup, name, key).await?;
+    replace_database_file(database, &backup, "falha")
+}
+
+/// Substitui o arquivo do banco (fechado) pela cópia do backup. O arquivo
+/// atual e seus `-wal`/`-shm` são renomeados com o sufixo `.<label>-<data>`
+/// e não são apagados. Devolve o novo nome do arquivo anterior, se existia.
+pub fn replace_database_file(database: &Path, backup: &Path, label: &str) -> Result<Option<PathBuf>, AppError> {
+    let suffix = format!("{}-{}", label, Utc::now().format("%Y%m%d-%H%M%S"));
+    let aside = |path: &Path| -> Result<Option<PathBuf>, AppError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut moved = path.as_os_str().to_owned();
+        moved.push(format!(".{}", suffix));
+        let moved = PathBuf::from(moved);
+        fs::rename(path, &moved)?;
+        Ok(Some(moved))
+    };
+
+    let previous = aside(database)?;
+    for ext in ["-wal", "-shm"] {
+        let mut sidecar = database.as_os_str().to_owned();
+        sidecar.push(ext);
+        aside(Path::new(&sidecar))?;
+    }
+
+    fs::copy(backup, database)?;
+    Ok(previous)
+}
+
+/// Abre a pasta no gerenciador de arquivos do sistema.
+pub fn open_folder(path: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(path)?;
+
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let program = "xdg-open";
+
+    std::process::Command::new(program)
+        .arg(path)
+        .spawn()
+        .map_err(|e| AppError::internal(format!("Não foi possível abrir a pasta {}: {}", path.display(), e)))?;
+    Ok(())
+}
@@ -0,0 +1,426 @@
+//! Controle de estoque: histórico de movimentações, produtos abaixo do
+//! mínimo e sugestões de compra pela média de vendas.
+//!
+//! Toda alteração do estoque passa por [`record`], que soma a quantidade ao
+//! produto e grava a movimentação na mesma operação; assim o estoque é
+//! sempre a soma do histórico, conferida por [`check_consistency`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::db::entities::{product, sale, stock_movement};
+use crate::error::AppError;
+
+/// Estoque mínimo de produtos cadastrados sem um valor próprio.
+pub const DEFAULT_MIN_STOCK: i32 = 10;
+
+/// Dias de vendas usados na média quando o período não é informado.
+pub const DEFAULT_SALES_WINDOW_DAYS: u32 = 30;
+
+/// Maior período aceito para a média de vendas.
+pub const MAX_SALES_WINDOW_DAYS: u32 = 365;
+
+/// Observação das movimentações que registram o estoque anterior ao histórico.
+pub const OPENING_BALANCE_NOTE: &str = "Saldo inicial";
+
+pub(crate) fn default_min_stock() -> i32 {
+    DEFAULT_MIN_STOCK
+}
+
+/// Motivo da movimentação, gravado em `stock_movements.reason`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum StockReason {
+    Sale,
+    Purchase,
+    Adjustment,
+    Loss,
+    Return,
+}
+
+impl StockReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StockReason::Sale => "sale",
+            StockReason::Purchase => "purchase",
+            StockReason::Adjustment => "adjustment",
+            StockReason::Loss => "loss",
+            StockReason::Return => "return",
+        }
+    }
+}
+
+/// Movimentação a registrar.
+#[derive(Clone, Debug)]
+pub struct Movement {
+    pub product_id: i32,
+    pub delta: i32,
+    pub reason: StockReason,
+    pub reference_id: Option<i32>,
+    pub username: Option<String>,
+    pub note: Option<String>,
+}
+
+impl Movement {
+    pub fn new(product_id: i32, delta: i32, reason: StockReason) -> Self {
+        Movement { product_id, delta, reason, reference_id: None, username: None, note: None }
+    }
+}
+
+/// Produto cujo estoque não bate com a soma das movimentações.
+#[derive(Clone, Debug, PartialEq, Serialize, TS)]
+pub struct StockDiscrepancy {
+    pub product_id: i32,
+    pub name: String,
+    pub stock_quantity: i32,
+    pub movements_total: i32,
+}
+
+/// Sugestão de compra para um produto que vai ficar abaixo do mínimo.
+#[derive(Clone, Debug, Serialize, TS)]
+pub struct ReorderSuggestion {
+    pub product: product::Model,
+    /// Quantidade vendida no período.
+    pub sold_qty: f64,
+    pub average_daily_sales: f64,
+    /// Dias até zerar o estoque no ritmo atual; `null` sem vendas no período.
+    pub days_of_stock: Option<f64>,
+    pub suggested_qty: i32,
+}
+
+/// Soma `delta` ao estoque do produto e grava a movimentação. Use dentro de
+/// uma transação junto com a operação que a originou.
+pub async fn record<C: ConnectionTrait>(db: &C, movement: Movement) -> Result<stock_movement::Model, AppError> {
+    let updated = product::Entity::update_many()
+        .col_expr(
+            product::Column::StockQuantity,
+            Expr::col(product::Column::StockQuantity).add(movement.delta),
+        )
+        .filter(product::Column::Id.eq(movement.product_id))
+        .exec(db)
+        .await?;
+    if updated.rows_affected == 0 {
+        return Err(AppError::not_found("Produto não encontrado"));
+    }
+    log(db, movement).await
+}
+
+/// Grava só a movimentação, para estoque já gravado no produto (cadastro
+/// com estoque inicial).
+pub(crate) async fn log<C: ConnectionTrait>(db: &C, movement: Movement) -> Result<stock_movement::Model, AppError> {
+    stock_movement::ActiveModel {
+        product_id: Set(movement.product_id),
+        delta: Set(movement.delta),
+        reason: Set(movement.reason.as_str().to_string()),
+        reference_id: Set(movement.reference_id),
+        username: Set(movement.username),
+        note: Set(movement.note),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(AppError::from)
+}
+
+/// Ajuste manual: contagem, perda (quebra, vazamento) ou devolução de cliente.
+/// Vendas e compras movimentam o estoque sozinhas.
+pub async fn adjust_stock(
+    db: &DatabaseConnection,
+    product_id: i32,
+    delta: i32,
+    reason: StockReason,
+    note: Option<String>,
+    username: &str,
+) -> Result<product::Model, AppError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(AppError::validation("Usuário responsável não informado").with_field("username"));
+    }
+    if delta == 0 {
+        return Err(AppError::validation("Informe uma quantidade diferente de zero").with_field("delta"));
+    }
+    match reason {
+        StockReason::Sale | StockReason::Purchase => {
+            return Err(AppError::validation("Vendas e compras movimentam o estoque automaticamente").with_field("reason"))
+        }
+        StockReason::Loss if delta > 0 => {
+            return Err(AppError::validation("Perdas devem reduzir o estoque").with_field("delta"))
+        }
+        StockReason::Return if delta < 0 => {
+            return Err(AppError::validation("Devoluções devem aumentar o estoque").with_field("delta"))
+        }
+        _ => {}
+    }
+
+    let txn = db.begin().await?;
+    record(
+        &txn,
+        Movement {
+            username: Some(username.to_string()),
+            note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            ..Movement::new(product_id, delta, reason)
+        },
+    )
+    .await?;
+    let product = product::Entity::find_by_id(product_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::not_found("Produto não encontrado"))?;
+    txn.commit().await?;
+    Ok(product)
+}
+
+/// Movimentações do produto, da mais recente para a mais antiga.
+pub async fn stock_history(db: &DatabaseConnection, product_id: i32) -> Result<Vec<stock_movement::Model>, AppError> {
+    if product::Entity::find_by_id(product_id).one(db).await?.is_none() {
+        return Err(AppError::not_found("Produto não encontrado"));
+    }
+    stock_movement::Entity::find()
+        .filter(stock_movement::Column::ProductId.eq(product_id))
+        .order_by_desc(stock_movement::Column::Id)
+        .all(db)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Produtos cujo estoque difere da soma das movimentações.
+pub async fn check_consistency<C: ConnectionTrait>(db: &C) -> Result<Vec<StockDiscrepancy>, AppError> {
+    let totals: HashMap<i32, i64> = stock_movement::Entity::find()
+        .select_only()
+        .column(stock_movement::Column::ProductId)
+        .column_as(stock_movement::Column::Delta.sum(), "total")
+        .group_by(stock_movement::Column::ProductId)
+        .into_tuple::<(i32, Option<i64>)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(id, total)| (id, total.unwrap_or(0)))
+        .collect();
+
+    let products = product::Entity::find().order_by_asc(product::Column::Name).all(db).await?;
+    Ok(products
+        .into_iter()
+        .filter_map(|p| {
+            let total = totals.get(&p.id).copied().unwrap_or(0);
+            (i64::from(p.stock_quantity) != total).then(|| StockDiscrepancy {
+                product_id: p.id,
+                name: p.name,
+                stock_quantity: p.stock_quantity,
+                movements_total: total.clamp(i32::MIN.into(), i32::MAX.into()) as i32,
+            })
+        })
+        .collect())
+}
+
+/// Recalcula o estoque pela soma das movimentações e devolve os produtos
+/// corrigidos, com o estoque anterior.
+pub async fn recompute_stock(db: &DatabaseConnection) -> Result<Vec<StockDiscrepancy>, AppError> {
+    let txn = db.begin().await?;
+    let discrepancies = check_consistency(&txn).await?;
+    for d in &discrepancies {
+        product::Entity::update_many()
+            .col_expr(product::Column::StockQuantity, Expr::value(d.movements_total))
+            .filter(product::Column::Id.eq(d.product_id))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+    if !discrepancies.is_empty() {
+        log::warn!("Estoque recalculado pelas movimentações: {:?}", discrepancies);
+    }
+    Ok(discrepancies)
+}
+
+/// Registra como saldo inicial o estoque dos produtos ainda sem nenhuma
+/// movimentação (cadastrados antes do histórico ou vindos de um pacote de
+/// migração antigo).
+pub async fn record_opening_balances(db: &DatabaseConnection) -> Result<(), AppError> {
+    let with_history = stock_movement::Entity::find()
+        .select_only()
+        .column(stock_movement::Column::ProductId)
+        .distinct()
+        .into_tuple::<i32>()
+        .all(db)
+        .await?;
+    let products = product::Entity::find()
+        .filter(product::Column::StockQuantity.ne(0))
+        .filter(product::Column::Id.is_not_in(with_history))
+        .all(db)
+        .await?;
+    if products.is_empty() {
+        return Ok(());
+    }
+
+    let txn = db.begin().await?;
+    for p in &products {
+        let movement = Movement {
+            note: Some(OPENING_BALANCE_NOTE.to_string()),
+            ..Movement::new(p.id, p.stock_quantity, StockReason::Adjustment)
+        };
+        log(&txn, movement).await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Quantidade de cada produto nos itens de uma venda. Itens sem o `id` do
+/// produto (vendas anteriores ao controle de estoque) não movimentam nada.
+pub(crate) fn item_quantities(items: &serde_json::Value) -> BTreeMap<i32, i32> {
+    let mut quantities = BTreeMap::new();
+    for item in items.as_array().into_iter().flatten() {
+        let Some(id) = item.get("id").and_then(|id| id.as_i64()).and_then(|id| i32::try_from(id).ok()) else {
+            continue;
+        };
+        let qty = item.get("qty").and_then(|q| q.as_f64()).unwrap_or(0.0).round() as i32;
+        *quantities.entry(id).or_insert(0) += qty;
+    }
+    quantities
+}
+
+/// Movimenta o estoque pela diferença entre os itens anteriores e os novos
+/// da venda `sale_id`: saída na venda, estorno na edição, volta na exclusão.
+/// Produtos já excluídos do cadastro são ignorados.
+pub(crate) async fn record_sale<C: ConnectionTrait>(
+    db: &C,
+    sale_id: i32,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+    reason: StockReason,
+) -> Result<(), AppError> {
+    let before = item_quantities(before);
+    let after = item_quantities(after);
+    let mut deltas: BTreeMap<i32, i32> = before;
+    for (id, qty) in after {
+        *deltas.entry(id).or_insert(0) -= qty;
+    }
+    deltas.retain(|_, delta| *delta != 0);
+    if deltas.is_empty() {
+        return Ok(());
+    }
+
+    let existing = product::Entity::find()
+        .select_only()
+        .column(product::Column::Id)
+        .filter(product::Column::Id.is_in(deltas.keys().copied()))
+        .into_tuple::<i32>()
+        .all(db)
+        .await?;
+    for id in existing {
+        let movement = Movement { reference_id: Some(sale_id), ..Movement::new(id, deltas[&id], reason) };
+        record(db, movement).await?;
+    }
+    Ok(())
+}
+
+/// Produtos com estoque abaixo do próprio mínimo, do menor estoque para o maior.
+pub async fn low_stock_products(db: &DatabaseConnection) -> Result<Vec<product::Model>, AppError> {
+    product::Entity::find()
+        .filter(Expr::col(product::Column::StockQuantity).lt(Expr::col(product::Column::MinStock)))
+        .order_by_asc(product::Column::StockQuantity)
+        .order_by_asc(product::Column::Name)
+        .all(db)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Produtos que, no ritmo de vendas dos últimos `days` dias, terminariam os
+/// próximos `days` dias abaixo do mínimo. A sugestão repõe essas vendas e o
+/// mínimo, arredondada para cima em lotes de `reorder_qty` quando houver.
+pub async fn reorder_suggestions(
+    db: &DatabaseConnection,
+    days: u32,
+    now: DateTime<Utc>,
+) -> Result<Vec<ReorderSuggestion>, AppError> {
+    if !(1..=MAX_SALES_WINDOW_DAYS).contains(&days) {
+        return Err(AppError::validation(format!(
+            "O período deve ter entre 1 e {} dias",
+            MAX_SALES_WINDOW_DAYS
+        ))
+        .with_field("days"));
+    }
+
+    let products = product::Entity::find().order_by_asc(product::Column::Name).all(db).await?;
+    let sold = sold_quantities(db, &products, now - Duration::days(days.into())).await?;
+
+    let mut suggestions: Vec<ReorderSuggestion> = products
+        .into_iter()
+        .filter_map(|product| {
+            let sold_qty = sold.get(&product.id).copied().unwrap_or(0.0);
+            let average_daily_sales = sold_qty / f64::from(days);
+            let needed = (f64::from(product.min_stock) + average_daily_sales * f64::from(days)
+                - f64::from(product.stock_quantity))
+            .ceil();
+            if needed <= 0.0 {
+                return None;
+            }
+            let needed = needed.min(i32::MAX as f64) as i32;
+            let suggested_qty = match product.reorder_qty {
+                lot if lot > 0 => ((needed - 1) / lot + 1).saturating_mul(lot),
+                _ => needed,
+            };
+            Some(ReorderSuggestion {
+                days_of_stock: (average_daily_sales > 0.0)
+                    .then(|| f64::from(product.stock_quantity.max(0)) / average_daily_sales),
+                product,
+                sold_qty,
+                average_daily_sales,
+                suggested_qty,
+            })
+        })
+        .collect();
+
+    // Os que acabam primeiro vêm antes; sem vendas, ao final
+    suggestions.sort_by(|a, b| match (a.days_of_stock, b.days_of_stock) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    Ok(suggestions)
+}
+
+/// Quantidade vendida de cada produto desde `since`. Os itens das vendas
+/// são JSON (`[{ id, name, qty, price }]`); vendas antigas, sem o `id`, são
+/// associadas pelo nome.
+async fn sold_quantities(
+    db: &DatabaseConnection,
+    products: &[product::Model],
+    since: DateTime<Utc>,
+) -> Result<HashMap<i32, f64>, AppError> {
+    let by_name: HashMap<String, i32> = products.iter().map(|p| (p.name.trim().to_lowercase(), p.id)).collect();
+    let sales = sale::Entity::find()
+        .filter(sale::Column::CreatedAt.gte(since))
+        .all(db)
+        .await?;
+
+    let mut sold = HashMap::new();
+    for sale in sales {
+        let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(&sale.items) else {
+            continue;
+        };
+        for item in items {
+            let id = item
+                .get("id")
+                .and_then(|id| id.as_i64())
+                .and_then(|id| i32::try_from(id).ok())
+                .or_else(|| {
+                    let name = item.get("name")?.as_str()?;
+                    by_name.get(&name.trim().to_lowercase()).copied()
+                });
+            let qty = item.get("qty").and_then(|q| q.as_f64()).unwrap_or(0.0);
+            if let Some(id) = id {
+                *sold.entry(id).or_insert(0.0) += qty;
+            }
+        }
+    }
+    Ok(sold)
+}
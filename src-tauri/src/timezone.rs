@@ -0,0 +1,109 @@
+//! Fuso horário do negócio, usado para definir o início de cada dia,
+//! semana e mês nos relatórios (as vendas são gravadas em UTC).
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::db::entities::setting;
+use crate::error::AppError;
+
+pub const SETTING_KEY: &str = "business_timezone";
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::America::Sao_Paulo;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+}
+
+/// Intervalo `[start, end)` em UTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+pub struct Range {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Fuso IANA do negócio. Os relatórios agrupam as vendas por hora UTC antes
+/// de levá-las ao dia local, então só valem fusos de horas inteiras; os de
+/// meia hora (ex.: Asia/Kolkata, America/St_Johns) são recusados.
+pub fn parse(name: &str) -> Result<Tz, AppError> {
+    let tz = name
+        .trim()
+        .parse::<Tz>()
+        .map_err(|_| AppError::validation(format!("Fuso horário inválido: {}", name)))?;
+    // Agora e daqui a seis meses, para pegar o horário de verão
+    let now = Utc::now();
+    let whole_hours = [now, now + Duration::days(182)]
+        .iter()
+        .all(|at| tz.offset_from_utc_datetime(&at.naive_utc()).fix().local_minus_utc() % 3600 == 0);
+    if !whole_hours {
+        return Err(AppError::validation(format!(
+            "Fuso horário não suportado: {} não tem deslocamento de horas inteiras",
+            tz.name()
+        )));
+    }
+    Ok(tz)
+}
+
+/// Fuso configurado em `settings`; usa America/Sao_Paulo se ausente ou inválido.
+pub async fn business_timezone(db: &DatabaseConnection) -> Tz {
+    match setting::Entity::find_by_id(SETTING_KEY.to_string()).one(db).await {
+        Ok(Some(s)) => parse(&s.value).unwrap_or_else(|e| {
+            log::warn!("{}; usando {}", e, DEFAULT_TIMEZONE);
+            DEFAULT_TIMEZONE
+        }),
+        Ok(None) => DEFAULT_TIMEZONE,
+        Err(e) => {
+            log::warn!("Erro ao ler fuso horário: {}; usando {}", e, DEFAULT_TIMEZONE);
+            DEFAULT_TIMEZONE
+        }
+    }
+}
+
+/// Data local (no fuso do negócio) de um instante UTC.
+pub fn local_date(tz: Tz, at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&tz).date_naive()
+}
+
+/// Primeiro instante do dia local, em UTC. Em dias com início de horário de
+/// verão a meia-noite não existe (o relógio pula para 01:00), então o dia
+/// começa no primeiro horário válido.
+pub fn day_start(tz: Tz, date: NaiveDate) -> DateTime<Utc> {
+    let mut local = date.and_hms_opt(0, 0, 0).unwrap();
+    loop {
+        if let Some(start) = tz.from_local_datetime(&local).earliest() {
+            return start.with_timezone(&Utc);
+        }
+        local += Duration::minutes(30);
+    }
+}
+
+/// Intervalo do dia, semana (segunda a domingo) ou mês que contém `at`.
+pub fn period_range(tz: Tz, at: DateTime<Utc>, period: Period) -> Range {
+    let today = local_date(tz, at);
+    let (first, next) = match period {
+        Period::Day => (today, today + Duration::days(1)),
+        Period::Week => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::days(7))
+        }
+        Period::Month => {
+            let first = today.with_day(1).unwrap();
+            let next = if first.month() == 12 {
+                NaiveDate::from_ymd_opt(first.year() + 1, 1, 1).unwrap()
+            } else {
+                NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1).unwrap()
+            };
+            (first, next)
+        }
+    };
+    Range {
+        start: day_start(tz, first),
+        end: day_start(tz, next),
+    }
+}
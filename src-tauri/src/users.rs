@@ -0,0 +1,83 @@
+//! Usuários do sistema e autenticação.
+
+use bcrypt::{hash, verify, DEFAULT_COST};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+
+use crate::db::entities::user;
+use crate::error::AppError;
+
+pub async fn login(db: &DatabaseConnection, username: &str, password: &str) -> Result<user::Model, AppError> {
+    let user = user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("Usuário não encontrado"))?;
+
+    if verify(password, &user.password_hash)? {
+        Ok(user)
+    } else {
+        Err(AppError::unauthorized("Senha incorreta"))
+    }
+}
+
+/// Confere as credenciais de um administrador, exigidas nas operações
+/// sensíveis (ex.: criptografar o banco).
+pub async fn authorize_admin(db: &DatabaseConnection, username: &str, password: &str) -> Result<user::Model, AppError> {
+    let user = login(db, username, password).await?;
+    if user.role != "admin" {
+        return Err(AppError::unauthorized("Operação permitida apenas para administradores"));
+    }
+    Ok(user)
+}
+
+pub async fn list_users(db: &DatabaseConnection) -> Result<Vec<user::Model>, AppError> {
+    user::Entity::find().all(db).await.map_err(AppError::from)
+}
+
+pub async fn create_user(
+    db: &DatabaseConnection,
+    username: String,
+    password: &str,
+    name: String,
+    role: String,
+) -> Result<user::Model, AppError> {
+    let user = user::ActiveModel {
+        username: Set(username),
+        password_hash: Set(hash(password, DEFAULT_COST)?),
+        name: Set(name),
+        role: Set(role),
+        ..Default::default()
+    };
+    user.insert(db).await.map_err(AppError::from)
+}
+
+/// Atualiza o usuário; a senha só é trocada quando informada.
+pub async fn update_user(
+    db: &DatabaseConnection,
+    id: i32,
+    username: String,
+    password: Option<&str>,
+    name: String,
+    role: String,
+) -> Result<user::Model, AppError> {
+    let mut user: user::ActiveModel = user::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Usuário não encontrado"))?
+        .into();
+
+    user.username = Set(username);
+    user.name = Set(name);
+    user.role = Set(role);
+
+    if let Some(plain) = password.filter(|p| !p.is_empty()) {
+        user.password_hash = Set(hash(plain, DEFAULT_COST)?);
+    }
+
+    user.update(db).await.map_err(AppError::from)
+}
+
+pub async fn delete_user(db: &DatabaseConnection, id: i32) -> Result<(), AppError> {
+    user::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
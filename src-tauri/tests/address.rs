@@ -0,0 +1,193 @@
+//! Tabela local de CEPs, ordem dos resolvedores e consulta ao ViaCEP.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use app_lib::address::{self, CepAddress, CepResolver, ChainResolver, LocalCepResolver};
+use app_lib::db::{Session, SharedConnection};
+use app_lib::error::{AppError, ErrorCode};
+use async_trait::async_trait;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+
+const TABLE: &str = "cep;logradouro;bairro;cidade;uf\n\
+                     29700-000;;Centro;Colatina;es\n\
+                     29.702-010;Rua Expedicionário Abílio dos Santos;Esplanada;Colatina;ES\n\
+                     \n";
+
+async fn shared(db: DatabaseConnection) -> SharedConnection {
+    SharedConnection::new(Session { connection: db, key: None })
+}
+
+/// Resolvedor fixo que registra as consultas recebidas.
+struct Fixed {
+    name: &'static str,
+    result: Result<Option<&'static str>, ()>,
+    calls: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[async_trait]
+impl CepResolver for Fixed {
+    async fn resolve(&self, cep: &str) -> Result<Option<CepAddress>, AppError> {
+        self.calls.lock().unwrap().push(self.name);
+        match self.result {
+            Ok(city) => Ok(city.map(|city| CepAddress {
+                cep: cep.to_string(),
+                street: None,
+                neighborhood: None,
+                city: city.to_string(),
+                state: "ES".to_string(),
+            })),
+            Err(()) => Err(AppError::internal("fora do ar")),
+        }
+    }
+}
+
+#[tokio::test]
+async fn imported_table_answers_local_lookups() {
+    let db = common::memory_db().await;
+    assert_eq!(address::import_cep_table(&db, TABLE.as_bytes()).await.unwrap(), 2);
+    // Reimportar atualiza em vez de duplicar
+    let update = "29700000,Av. Getúlio Vargas,Centro,Colatina,ES";
+    assert_eq!(address::import_cep_table(&db, update.as_bytes()).await.unwrap(), 1);
+
+    let resolver = LocalCepResolver::new(shared(db).await);
+    let found = address::lookup(&resolver, "29700-000").await.unwrap();
+    assert_eq!(found.street.as_deref(), Some("Av. Getúlio Vargas"));
+    assert_eq!((found.city.as_str(), found.state.as_str()), ("Colatina", "ES"));
+    let found = address::lookup(&resolver, "29702010").await.unwrap();
+    assert_eq!(found.neighborhood.as_deref(), Some("Esplanada"));
+
+    let err = address::lookup(&resolver, "01001-000").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = address::lookup(&resolver, "123").await.unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("cep")));
+}
+
+#[tokio::test]
+async fn invalid_rows_reject_the_whole_table() {
+    let db = common::memory_db().await;
+    for table in ["29700000;;Centro;Colatina;XX", "29700000;;Centro;;ES", "2970;;Centro;Colatina;ES"] {
+        let err = address::import_cep_table(&db, table.as_bytes()).await.unwrap_err();
+        assert!(err.to_string().contains("Linha 1"), "{}", err);
+    }
+    // Arquivo em Latin-1
+    let latin1: Vec<u8> = b"29700000;Rua S\xe3o Jos\xe9;Centro;Colatina;ES".to_vec();
+    address::import_cep_table(&db, latin1.as_slice()).await.unwrap();
+    let resolver = LocalCepResolver::new(shared(db).await);
+    let found = address::lookup(&resolver, "29700000").await.unwrap();
+    assert_eq!(found.street.as_deref(), Some("Rua São José"));
+}
+
+#[tokio::test]
+async fn chain_stops_at_the_first_resolver_that_finds_the_cep() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let fixed = |name, result| -> Box<dyn CepResolver> { Box::new(Fixed { name, result, calls: calls.clone() }) };
+
+    let chain = ChainResolver::new(vec![
+        fixed("local", Ok(None)),
+        fixed("viacep", Ok(Some("Colatina"))),
+        fixed("extra", Ok(Some("Outra"))),
+    ]);
+    assert_eq!(address::lookup(&chain, "29700000").await.unwrap().city, "Colatina");
+    assert_eq!(*calls.lock().unwrap(), ["local", "viacep"]);
+
+    // Falha de um resolvedor não impede os seguintes
+    calls.lock().unwrap().clear();
+    let chain = ChainResolver::new(vec![fixed("local", Err(())), fixed("viacep", Ok(Some("Colatina")))]);
+    assert_eq!(address::lookup(&chain, "29700000").await.unwrap().city, "Colatina");
+
+    // Sem resultado, o último erro é devolvido; sem erros, "não encontrado"
+    let chain = ChainResolver::new(vec![fixed("local", Err(())), fixed("viacep", Ok(None))]);
+    assert_eq!(address::lookup(&chain, "29700000").await.unwrap_err().code(), ErrorCode::Internal);
+    let chain = ChainResolver::new(vec![fixed("local", Ok(None))]);
+    assert_eq!(address::lookup(&chain, "29700000").await.unwrap_err().code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn address_migration_only_touches_rows_out_of_format() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let sql = |sql: &str| Statement::from_string(db.get_database_backend(), sql.to_string());
+    db.execute(sql(&format!("UPDATE clients SET cep = '29700-000', state = ' es ' WHERE id = {}", maria.id))).await.unwrap();
+    db.execute(sql("CREATE TEMP TABLE client_updates (id INTEGER)")).await.unwrap();
+    db.execute(sql(
+        "CREATE TEMP TRIGGER count_client_updates AFTER UPDATE ON clients BEGIN \
+         INSERT INTO client_updates VALUES (new.id); END",
+    ))
+    .await
+    .unwrap();
+
+    app_lib::db::setup_schema(&db).await.unwrap();
+    app_lib::db::setup_schema(&db).await.unwrap();
+
+    let row = db
+        .query_one(sql("SELECT cep, state, (SELECT COUNT(*) FROM client_updates) AS updates FROM clients"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.try_get::<String>("", "cep").unwrap(), "29700000");
+    assert_eq!(row.try_get::<String>("", "state").unwrap(), "ES");
+    assert_eq!(row.try_get::<i64>("", "updates").unwrap(), 1);
+}
+
+#[cfg(feature = "viacep")]
+mod viacep {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    use app_lib::address::{self, ViaCepResolver};
+    use app_lib::error::ErrorCode;
+
+    /// Servidor HTTP local que responde a cada caminho com o corpo indicado.
+    fn stub(responses: &'static [(&'static str, u16, &'static str)]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/ws", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let (status, body) = responses
+                    .iter()
+                    .find(|(p, _, _)| *p == path)
+                    .map(|(_, status, body)| (*status, *body))
+                    .unwrap_or((404, ""));
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn viacep_responses_are_mapped() {
+        let url = stub(&[
+            (
+                "/ws/29700000/json/",
+                200,
+                r#"{"cep":"29700-000","logradouro":"","bairro":"Centro","localidade":"Colatina","uf":"ES"}"#,
+            ),
+            ("/ws/99999999/json/", 200, r#"{"erro": true}"#),
+            ("/ws/11111111/json/", 400, ""),
+            ("/ws/22222222/json/", 500, ""),
+        ]);
+        let resolver = ViaCepResolver::new(format!("{}/", url));
+
+        let found = address::lookup(&resolver, "29700-000").await.unwrap();
+        assert_eq!(found.street, None);
+        assert_eq!(found.neighborhood.as_deref(), Some("Centro"));
+        assert_eq!((found.city.as_str(), found.state.as_str()), ("Colatina", "ES"));
+
+        let cases = [("99999999", ErrorCode::NotFound), ("11111111", ErrorCode::NotFound), ("22222222", ErrorCode::Internal)];
+        for (cep, code) in cases {
+            assert_eq!(address::lookup(&resolver, cep).await.unwrap_err().code(), code, "{}", cep);
+        }
+    }
+}
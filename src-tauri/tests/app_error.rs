@@ -0,0 +1,53 @@
+//! Erros dos comandos chegam ao frontend como `{ code, message, field }`.
+
+mod common;
+
+use app_lib::db::entities::user;
+use app_lib::error::{AppError, ErrorCode};
+use sea_orm::{ActiveModelTrait, Database, EntityTrait, Set};
+
+fn operator() -> user::ActiveModel {
+    user::ActiveModel {
+        username: Set("maria".into()),
+        password_hash: Set("x".into()),
+        name: Set("Maria".into()),
+        role: Set("user".into()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn serializes_code_message_and_field() {
+    let err = AppError::validation("CEP deve ter 8 dígitos").with_field("cep");
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        serde_json::json!({ "code": "validation", "message": "CEP deve ter 8 dígitos", "field": "cep" })
+    );
+
+    let err = AppError::not_found("Cliente não encontrado");
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        serde_json::json!({ "code": "not_found", "message": "Cliente não encontrado", "field": null })
+    );
+}
+
+#[tokio::test]
+async fn unique_violation_becomes_conflict_on_the_column() {
+    let db = common::memory_db().await;
+
+    operator().insert(&db).await.unwrap();
+    let err: AppError = operator().insert(&db).await.unwrap_err().into();
+
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert_eq!(err.field(), Some("username"));
+    assert_eq!(err.to_string(), "Nome de usuário já está em uso");
+}
+
+#[tokio::test]
+async fn other_database_failures_hide_the_raw_message() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let err: AppError = user::Entity::find_by_id(1).one(&db).await.unwrap_err().into();
+
+    assert_eq!(err.code(), ErrorCode::Database);
+    assert!(!err.to_string().contains("no such table"));
+}
@@ -0,0 +1,165 @@
+//! Pacote de migração: exportação de todas as tabelas e importação em uma
+//! instalação nova.
+
+mod common;
+
+use std::io::{Cursor, Read, Write};
+
+use app_lib::archive::{self, ArchiveManifest};
+use app_lib::db::entities::client;
+use app_lib::error::ErrorCode;
+use app_lib::{audit, products, search, settings, users};
+use chrono::{Duration, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+/// Banco com cadastros, vendas e um buraco nos ids de clientes.
+async fn populated_db() -> DatabaseConnection {
+    let db = common::memory_db().await;
+    let removed = common::client(&db, "Cliente Removido").await;
+    let joao = common::client(&db, "João Souza").await;
+    client::Entity::delete_by_id(removed.id).exec(&db).await.unwrap();
+
+    let filters = products::create_category(&db, "Filtros".into(), None).await.unwrap();
+    let water = common::product(&db, "Água 20L", 12.0, 30).await;
+    common::sale(&db, &joao, vec![common::item(&water, 2.0)], "PIX").await;
+    common::gallon(&db, &joao, "Pureza", Duration::days(30)).await;
+    settings::save_setting(&db, "company_name".into(), "Depósito Central".into()).await.unwrap();
+    users::create_user(&db, "caixa".into(), "caixa123", "Caixa".into(), "user".into())
+        .await
+        .unwrap();
+    assert_eq!(filters.id, 4);
+    db
+}
+
+/// Regrava o pacote trocando o conteúdo JSON do arquivo `name`.
+fn with_file<T>(bytes: &[u8], name: &str, edit: impl Fn(&mut T)) -> Vec<u8>
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    let mut source = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut target = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..source.len() {
+        let mut file = source.by_index(i).unwrap();
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        if file.name() == name {
+            let mut value: T = serde_json::from_slice(&content).unwrap();
+            edit(&mut value);
+            content = serde_json::to_vec(&value).unwrap();
+        }
+        target.start_file(file.name(), zip::write::SimpleFileOptions::default()).unwrap();
+        target.write_all(&content).unwrap();
+    }
+    target.finish().unwrap().into_inner()
+}
+
+/// Regrava o pacote trocando o manifesto.
+fn with_manifest(bytes: &[u8], edit: impl Fn(&mut ArchiveManifest)) -> Vec<u8> {
+    with_file(bytes, "manifest.json", edit)
+}
+
+#[tokio::test]
+async fn archive_round_trip_preserves_ids_and_relations() {
+    let source = populated_db().await;
+    let bytes = archive::export(&source, Utc::now()).await.unwrap();
+
+    let target = common::memory_db().await;
+    let manifest = archive::import(&target, &bytes).await.unwrap();
+    assert_eq!(manifest.schema_version, app_lib::db::SCHEMA_VERSION);
+    assert_eq!(manifest.counts["clients"], 1);
+    assert_eq!(manifest.counts["users"], 2);
+
+    let before = archive::read_data(&source).await.unwrap();
+    let after = archive::read_data(&target).await.unwrap();
+    assert_eq!(after.clients, before.clients);
+    assert_eq!(after.clients[0].id, 2);
+    assert_eq!(after.sales, before.sales);
+    assert_eq!(after.sales[0].client_id, 2);
+    assert_eq!(after.client_gallons, before.client_gallons);
+    assert_eq!(after.products, before.products);
+    assert_eq!(after.categories, before.categories);
+    assert_eq!(after.users, before.users);
+    assert_eq!(after.settings, before.settings);
+    assert_eq!(after.payment_methods, before.payment_methods);
+
+    // Senhas e índice de busca continuam valendo no destino
+    users::login(&target, "caixa", "caixa123").await.unwrap();
+    let found = search::search(&target, "Souza", 10).await.unwrap();
+    assert_eq!(found[0].id, 2);
+}
+
+#[tokio::test]
+async fn import_requires_a_fresh_install() {
+    let source = populated_db().await;
+    let bytes = archive::export(&source, Utc::now()).await.unwrap();
+
+    let err = archive::import(&source, &bytes).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let target = common::memory_db().await;
+    common::client(&target, "Já Cadastrado").await;
+    let err = archive::import(&target, &bytes).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    // Nada foi substituído
+    users::login(&target, "admin", "admin123").await.unwrap();
+}
+
+#[tokio::test]
+async fn archives_from_newer_versions_or_other_files_are_rejected() {
+    let source = populated_db().await;
+    let bytes = archive::export(&source, Utc::now()).await.unwrap();
+    let target = common::memory_db().await;
+
+    let newer = with_manifest(&bytes, |m| m.schema_version = app_lib::db::SCHEMA_VERSION + 1);
+    let err = archive::import(&target, &newer).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert!(err.to_string().contains("versão mais nova"));
+
+    let other = with_manifest(&bytes, |m| m.format = "outro".into());
+    assert_eq!(archive::import(&target, &other).await.unwrap_err().code(), ErrorCode::Validation);
+    assert_eq!(archive::import(&target, b"not a zip").await.unwrap_err().code(), ErrorCode::Validation);
+
+    // Pacote de um esquema anterior é aceito
+    let older = with_manifest(&bytes, |m| m.schema_version = 1);
+    archive::import(&target, &older).await.unwrap();
+    assert_eq!(client::Entity::find().all(&target).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn packages_with_broken_relations_are_rejected() {
+    let source = populated_db().await;
+    let bytes = archive::export(&source, Utc::now()).await.unwrap();
+    let target = common::memory_db().await;
+
+    let orphan_movements = with_file(&bytes, "stock_movements.json", |rows: &mut Vec<serde_json::Value>| {
+        rows[0]["product_id"] = 999.into();
+    });
+    let orphan_gallons = with_file(&bytes, "client_gallons.json", |rows: &mut Vec<serde_json::Value>| {
+        rows[0]["client_id"] = 999.into();
+    });
+    for broken in [orphan_movements, orphan_gallons] {
+        let err = archive::import(&target, &broken).await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation);
+        assert!(err.to_string().contains("Pacote inconsistente"), "{}", err);
+    }
+    assert!(client::Entity::find().all(&target).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn exported_file_is_recorded_in_the_audit_log() {
+    let source = populated_db().await;
+    let err = archive::export_file(&source, " ").await.unwrap_err();
+    assert_eq!(err.field(), Some("username"));
+
+    let file = archive::export_file(&source, "admin").await.unwrap();
+    assert!(file.file_name.starts_with("aquagas-") && file.file_name.ends_with(".zip"));
+    let trail = audit::list_for(&source, audit::DATABASE, 0).await.unwrap();
+    assert_eq!(trail.len(), 1);
+    assert_eq!((trail[0].action.as_str(), trail[0].username.as_str()), (archive::ACTION_EXPORT, "admin"));
+
+    // O registro vai junto no pacote
+    let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &file.content).unwrap();
+    let target = common::memory_db().await;
+    archive::import(&target, &bytes).await.unwrap();
+    assert_eq!(audit::list_for(&target, audit::DATABASE, 0).await.unwrap().len(), 1);
+}
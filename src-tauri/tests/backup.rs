@@ -0,0 +1,200 @@
+//! Backups com `VACUUM INTO`, verificação de integridade, rotação e
+//! restauração.
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use app_lib::backup::{self, BackupKind, Schedule};
+use app_lib::error::ErrorCode;
+use app_lib::{clients, db, settings};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use sea_orm::ConnectionTrait;
+
+/// Pasta temporária exclusiva do teste.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aquagas-backup-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Banco em arquivo: `VACUUM INTO` a partir de um banco em memória grava
+/// a cópia também em memória.
+async fn file_db(dir: &std::path::Path) -> sea_orm::DatabaseConnection {
+    db::open(&db::database_path(dir), None).await.unwrap()
+}
+
+fn utc(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+}
+
+#[tokio::test]
+async fn backup_is_a_verified_copy_of_the_database() {
+    let data = scratch_dir("copy");
+    let db = file_db(&data).await;
+    common::client(&db, "Maria Silva").await;
+    let dir = db::backup_dir(&data);
+
+    let file = backup::create_backup(&db, &dir, BackupKind::Manual, utc(2024, 3, 1, 12), None).await.unwrap();
+    assert_eq!(file.name, "aquagas-manual-20240301-120000.db");
+    assert!(file.size > 0);
+
+    let path = dir.join(&file.name);
+    backup::verify(&path, None).await.unwrap();
+    let copy = db::open(&path, None).await.unwrap();
+    assert!(!db::seed_demo_data(&copy).await.unwrap(), "a cópia deve ter o cliente");
+    copy.close().await.unwrap();
+
+    let listed = backup::list_backups(&dir);
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].kind, Some(BackupKind::Manual));
+    assert_eq!(listed[0].created_at, utc(2024, 3, 1, 12));
+
+    db.close().await.unwrap();
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn damaged_file_fails_verification() {
+    let dir = scratch_dir("damaged");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("aquagas-auto-20240301-120000.db");
+    fs::write(&path, vec![0x13; 4096]).unwrap();
+
+    assert!(backup::verify(&path, None).await.is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn rotation_keeps_newest_per_day_and_week() {
+    let data = scratch_dir("rotation");
+    let db = file_db(&data).await;
+    let dir = db::backup_dir(&data);
+
+    // Três semanas de backups automáticos, dois por dia (10h e 20h UTC)
+    let start = utc(2024, 3, 4, 10); // segunda-feira
+    for day in 0..21 {
+        for hours in [0, 10] {
+            let at = start + Duration::days(day) + Duration::hours(hours);
+            backup::create_backup(&db, &dir, BackupKind::Auto, at, None).await.unwrap();
+        }
+    }
+    let manual = backup::create_backup(&db, &dir, BackupKind::Manual, utc(2024, 2, 1, 9), None).await.unwrap();
+
+    let schedule = Schedule { interval_hours: 24, keep_daily: 3, keep_weekly: 2 };
+    backup::rotate(&dir, &schedule, chrono_tz::America::Sao_Paulo).unwrap();
+    db.close().await.unwrap();
+
+    let names: Vec<_> = backup::list_backups(&dir).into_iter().map(|f| f.name).collect();
+    assert_eq!(
+        names,
+        [
+            // Últimos três dias (o mais recente de cada um)
+            "aquagas-auto-20240324-200000.db",
+            "aquagas-auto-20240323-200000.db",
+            "aquagas-auto-20240322-200000.db",
+            // Semana anterior (domingo, 17/03)
+            "aquagas-auto-20240317-200000.db",
+            manual.name.as_str(),
+        ]
+    );
+
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn scheduled_backup_waits_for_the_interval() {
+    let data = scratch_dir("schedule");
+    let db = file_db(&data).await;
+    let dir = db::backup_dir(&data);
+    settings::save_setting(&db, backup::SETTING_INTERVAL_HOURS.into(), "6".into()).await.unwrap();
+
+    let first = backup::run_scheduled(&db, &dir, utc(2024, 3, 1, 8), None).await.unwrap();
+    assert!(first.is_some());
+    assert!(backup::run_scheduled(&db, &dir, utc(2024, 3, 1, 13), None).await.unwrap().is_none());
+    assert!(backup::run_scheduled(&db, &dir, utc(2024, 3, 1, 14), None).await.unwrap().is_some());
+    // Mesmo dia: a rotação mantém só o mais recente
+    let names: Vec<_> = backup::list_backups(&dir).into_iter().map(|f| f.name).collect();
+    assert_eq!(names, ["aquagas-auto-20240301-140000.db"]);
+
+    db.close().await.unwrap();
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn backup_settings_must_be_positive_integers() {
+    let db = common::memory_db().await;
+
+    let err = settings::save_setting(&db, backup::SETTING_KEEP_DAILY.into(), "zero".into()).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert!(settings::save_setting(&db, backup::SETTING_KEEP_WEEKLY.into(), "0".into()).await.is_err());
+
+    let saved = settings::save_setting(&db, backup::SETTING_KEEP_DAILY.into(), " 14 ".into()).await.unwrap();
+    assert_eq!(saved.value, "14");
+    assert_eq!(backup::load_schedule(&db).await.keep_daily, 14);
+}
+
+async fn client_names(db: &sea_orm::DatabaseConnection) -> Vec<String> {
+    clients::list_clients(db).await.unwrap().into_iter().map(|c| c.name).collect()
+}
+
+#[tokio::test]
+async fn restore_swaps_the_database_and_keeps_a_safety_copy() {
+    let data = scratch_dir("restore");
+    let database = db::database_path(&data);
+    let dir = db::backup_dir(&data);
+    let mut db = file_db(&data).await;
+    common::client(&db, "Antes do backup").await;
+    let file = backup::create_backup(&db, &dir, BackupKind::Manual, utc(2024, 3, 1, 12), None).await.unwrap();
+    common::client(&db, "Depois do backup").await;
+
+    let restored = backup::restore(&mut db, &database, &dir, &file.name, None).await.unwrap();
+    assert_eq!(restored.name, file.name);
+    assert_eq!(restored.created_at, utc(2024, 3, 1, 12));
+    assert_eq!(restored.schema_version, db::SCHEMA_VERSION);
+    assert_eq!(restored.safety_backup.kind, Some(BackupKind::Safety));
+    assert_eq!(client_names(&db).await, ["Antes do backup"]);
+
+    let safety = db::open(&dir.join(&restored.safety_backup.name), None).await.unwrap();
+    assert_eq!(client_names(&safety).await, ["Antes do backup", "Depois do backup"]);
+    safety.close().await.unwrap();
+
+    db.close().await.unwrap();
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn restore_rejects_newer_schemas_and_foreign_files() {
+    let data = scratch_dir("restore-reject");
+    let database = db::database_path(&data);
+    let dir = db::backup_dir(&data);
+    let mut db = file_db(&data).await;
+    common::client(&db, "Maria Silva").await;
+
+    let newer = backup::create_backup(&db, &dir, BackupKind::Manual, utc(2024, 3, 1, 12), None).await.unwrap();
+    let conn = sea_orm::Database::connect(format!("sqlite:{}", dir.join(&newer.name).display())).await.unwrap();
+    conn.execute_unprepared(&format!("PRAGMA user_version = {}", db::SCHEMA_VERSION + 1)).await.unwrap();
+    conn.close().await.unwrap();
+
+    let foreign = dir.join("outro-sistema.db");
+    let conn = sea_orm::Database::connect(format!("sqlite:{}?mode=rwc", foreign.display())).await.unwrap();
+    conn.execute_unprepared("CREATE TABLE notas (id INTEGER PRIMARY KEY)").await.unwrap();
+    conn.close().await.unwrap();
+
+    for name in [newer.name.as_str(), "outro-sistema.db"] {
+        let err = backup::restore(&mut db, &database, &dir, name, None).await.unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation, "{}", name);
+    }
+    assert_eq!(
+        backup::restore(&mut db, &database, &dir, "../aquagas.db", None).await.unwrap_err().code(),
+        ErrorCode::NotFound
+    );
+
+    // Nada foi trocado nem copiado
+    assert_eq!(client_names(&db).await, ["Maria Silva"]);
+    assert!(backup::list_backups(&dir).iter().all(|f| f.kind != Some(BackupKind::Safety)));
+
+    db.close().await.unwrap();
+    fs::remove_dir_all(&data).unwrap();
+}
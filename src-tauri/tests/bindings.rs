@@ -0,0 +1,21 @@
+//! Garante que `src/lib/bindings.ts` corresponde às structs Rust.
+
+use std::path::Path;
+
+#[test]
+fn typescript_bindings_are_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(app_lib::bindings::FILE);
+    let generated = app_lib::bindings::typescript();
+
+    if std::env::var_os("UPDATE_BINDINGS").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+        return;
+    }
+
+    let current = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        current == generated,
+        "{} está desatualizado; rode `UPDATE_BINDINGS=1 cargo test --test bindings`",
+        path.display()
+    );
+}
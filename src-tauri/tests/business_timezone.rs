@@ -0,0 +1,170 @@
+//! Limites de dia, semana e mês no fuso do negócio (America/Sao_Paulo por padrão).
+
+use app_lib::db::entities::{client, sale, setting};
+use app_lib::error::ErrorCode;
+use app_lib::timezone::{self, Period};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::America::Sao_Paulo;
+use sea_orm::{Database, DatabaseConnection, EntityTrait, Set};
+
+async fn empty_db() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    app_lib::db::setup_schema(&db).await.unwrap();
+    client::Entity::insert(client::ActiveModel {
+        name: Set("Cliente".into()),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    })
+    .exec(&db)
+    .await
+    .unwrap();
+    db
+}
+
+async fn add_sale(db: &DatabaseConnection, at: DateTime<Utc>, total: f64) {
+    sale::Entity::insert(sale::ActiveModel {
+        client_id: Set(1),
+        items: Set("[]".into()),
+        total: Set(total),
+        payment_method: Set("PIX".into()),
+        created_at: Set(at),
+        ..Default::default()
+    })
+    .exec(db)
+    .await
+    .unwrap();
+}
+
+fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[tokio::test]
+async fn default_timezone_is_sao_paulo() {
+    let db = empty_db().await;
+    assert_eq!(timezone::business_timezone(&db).await, Sao_Paulo);
+}
+
+#[tokio::test]
+async fn configured_timezone_is_used() {
+    let db = empty_db().await;
+    setting::Entity::insert(setting::ActiveModel {
+        key: Set(timezone::SETTING_KEY.into()),
+        value: Set("America/Manaus".into()),
+    })
+    .exec(&db)
+    .await
+    .unwrap();
+    assert_eq!(timezone::business_timezone(&db).await, chrono_tz::America::Manaus);
+}
+
+#[test]
+fn invalid_timezone_is_rejected() {
+    assert!(timezone::parse("America/Atlantida").is_err());
+    assert_eq!(timezone::parse(" America/Sao_Paulo ").unwrap(), Sao_Paulo);
+    assert_eq!(timezone::parse("America/Noronha").unwrap(), chrono_tz::America::Noronha);
+
+    // Os relatórios agrupam por hora UTC: fusos de meia hora ficariam no dia errado
+    for name in ["Asia/Kolkata", "America/St_Johns", "Australia/Lord_Howe"] {
+        let err = timezone::parse(name).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation, "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn late_evening_sales_count_for_local_today() {
+    let db = empty_db().await;
+    // 10/06 22:00 em São Paulo = 11/06 01:00 UTC
+    add_sale(&db, utc(2024, 6, 11, 1, 0), 30.0).await;
+    // 10/06 08:00 local
+    add_sale(&db, utc(2024, 6, 10, 11, 0), 20.0).await;
+    // 09/06 23:30 local (ontem)
+    add_sale(&db, utc(2024, 6, 10, 2, 30), 7.0).await;
+
+    // Agora: 10/06 23:59 local, que já é 11/06 em UTC
+    let stats = app_lib::reports::dashboard_stats_at(&db, utc(2024, 6, 11, 2, 59)).await.unwrap();
+    assert_eq!(stats.sales_count, 2);
+    assert_eq!(stats.revenue, 50.0);
+    assert_eq!(stats.sales_change, 1);
+
+    // Um minuto depois já é o dia seguinte no fuso local
+    let stats = app_lib::reports::dashboard_stats_at(&db, utc(2024, 6, 11, 3, 0)).await.unwrap();
+    assert_eq!(stats.sales_count, 0);
+    assert_eq!(stats.sales_change, -2);
+}
+
+#[test]
+fn day_range_at_local_midnight() {
+    // 00:00 local = 03:00 UTC
+    let range = timezone::period_range(Sao_Paulo, utc(2024, 6, 11, 3, 0), Period::Day);
+    assert_eq!(range.start, utc(2024, 6, 11, 3, 0));
+    assert_eq!(range.end, utc(2024, 6, 12, 3, 0));
+
+    // 23:59 local do dia anterior
+    let range = timezone::period_range(Sao_Paulo, utc(2024, 6, 11, 2, 59), Period::Day);
+    assert_eq!(range.start, utc(2024, 6, 10, 3, 0));
+}
+
+#[test]
+fn day_without_midnight_starts_at_first_valid_hour() {
+    // Início do horário de verão de 2018: 04/11 00:00 virou 01:00 (-02:00)
+    assert_eq!(timezone::day_start(Sao_Paulo, date(2018, 11, 4)), utc(2018, 11, 4, 3, 0));
+    assert_eq!(timezone::day_start(Sao_Paulo, date(2018, 11, 5)), utc(2018, 11, 5, 2, 0));
+
+    let range = timezone::period_range(Sao_Paulo, utc(2018, 11, 4, 12, 0), Period::Day);
+    assert_eq!(range.end - range.start, Duration::hours(23));
+}
+
+#[test]
+fn day_with_repeated_hour_lasts_25_hours() {
+    // Fim do horário de verão de 2019: 17/02 00:00 voltou para 16/02 23:00
+    let range = timezone::period_range(Sao_Paulo, utc(2019, 2, 16, 12, 0), Period::Day);
+    assert_eq!(range.start, utc(2019, 2, 16, 2, 0));
+    assert_eq!(range.end, utc(2019, 2, 17, 3, 0));
+    assert_eq!(range.end - range.start, Duration::hours(25));
+}
+
+#[test]
+fn week_and_month_ranges() {
+    // Quarta-feira, 12/06/2024
+    let at = utc(2024, 6, 12, 15, 0);
+    let week = timezone::period_range(Sao_Paulo, at, Period::Week);
+    assert_eq!(week.start, utc(2024, 6, 10, 3, 0));
+    assert_eq!(week.end, utc(2024, 6, 17, 3, 0));
+
+    let month = timezone::period_range(Sao_Paulo, at, Period::Month);
+    assert_eq!(month.start, utc(2024, 6, 1, 3, 0));
+    assert_eq!(month.end, utc(2024, 7, 1, 3, 0));
+
+    // 31/12 22:00 local ainda é dezembro, embora já seja janeiro em UTC
+    let month = timezone::period_range(Sao_Paulo, utc(2025, 1, 1, 1, 0), Period::Month);
+    assert_eq!(month.start, utc(2024, 12, 1, 3, 0));
+    assert_eq!(month.end, utc(2025, 1, 1, 3, 0));
+}
+
+#[tokio::test]
+async fn chart_buckets_follow_local_days_across_dst() {
+    let db = empty_db().await;
+    // 16/02/2019 23:30 no horário de verão (-02:00) = 17/02 01:30 UTC
+    add_sale(&db, utc(2019, 2, 17, 1, 30), 10.0).await;
+    // 16/02/2019 23:30 após o fim do horário de verão (-03:00) = 17/02 02:30 UTC
+    add_sale(&db, utc(2019, 2, 17, 2, 30), 5.0).await;
+    // 17/02/2019 00:30 local = 03:30 UTC
+    add_sale(&db, utc(2019, 2, 17, 3, 30), 1.0).await;
+
+    let report = app_lib::reports::sales_report(
+        &db,
+        &utc(2019, 2, 16, 0, 0).to_rfc3339(),
+        &utc(2019, 2, 18, 0, 0).to_rfc3339(),
+        "todos",
+    )
+    .await
+    .unwrap();
+
+    let days: Vec<_> = report.chart_data.iter().map(|d| (d.date.as_str(), d.revenue)).collect();
+    assert_eq!(days, [("2019-02-16", 15.0), ("2019-02-17", 1.0)]);
+}
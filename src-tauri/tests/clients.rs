@@ -0,0 +1,115 @@
+//! Serviços de clientes executados sobre um banco SQLite em memória.
+
+mod common;
+
+use app_lib::clients::{self, ClientInput, ClientQuery};
+use app_lib::error::ErrorCode;
+
+fn input(name: &str) -> ClientInput {
+    ClientInput {
+        name: name.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn create_normalizes_document_and_address() {
+    let db = common::memory_db().await;
+
+    let client = clients::create_client(
+        &db,
+        ClientInput {
+            document: Some("529.982.247-25".into()),
+            cep: Some("29700-000".into()),
+            state: Some("es".into()),
+            street: Some("Av. Tailândia".into()),
+            number: Some("127".into()),
+            neighborhood: Some("Columbia".into()),
+            ..input("Maria Silva")
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(client.document.as_deref(), Some("52998224725"));
+    assert_eq!(client.cep.as_deref(), Some("29700000"));
+    assert_eq!(client.state.as_deref(), Some("ES"));
+    assert!(client.address.unwrap().starts_with("Av. Tailândia, 127"));
+}
+
+#[tokio::test]
+async fn duplicate_document_is_a_conflict_on_the_field() {
+    let db = common::memory_db().await;
+    let with_cpf = |name: &str| ClientInput {
+        document: Some("529.982.247-25".into()),
+        ..input(name)
+    };
+
+    let first = clients::create_client(&db, with_cpf("Maria")).await.unwrap();
+    let err = clients::create_client(&db, with_cpf("João")).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert_eq!(err.field(), Some("document"));
+
+    // O próprio cliente pode manter o documento ao ser editado
+    let updated = clients::update_client(&db, first.id, with_cpf("Maria Silva")).await.unwrap();
+    assert_eq!(updated.name, "Maria Silva");
+}
+
+#[tokio::test]
+async fn invalid_cep_is_rejected() {
+    let db = common::memory_db().await;
+    let err = clients::create_client(&db, ClientInput { cep: Some("123".into()), ..input("Ana") })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert_eq!(err.field(), Some("cep"));
+}
+
+#[tokio::test]
+async fn update_of_missing_client_is_not_found() {
+    let db = common::memory_db().await;
+    let err = clients::update_client(&db, 99, input("Ana")).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn page_filters_by_state_and_search() {
+    let db = common::memory_db().await;
+    for (name, state) in [("Maria", "ES"), ("Mariana", "SP"), ("João", "ES")] {
+        clients::create_client(&db, ClientInput { state: Some(state.into()), ..input(name) })
+            .await
+            .unwrap();
+    }
+
+    let page = clients::clients_page(
+        &db,
+        ClientQuery {
+            search: Some("mari".into()),
+            state: Some("es".into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].name, "Maria");
+}
+
+#[tokio::test]
+async fn gallons_appear_in_client_details() {
+    let db = common::memory_db().await;
+    let client = clients::create_client(&db, input("Maria")).await.unwrap();
+
+    clients::add_gallon(&db, client.id, "Pedra Azul".into(), "2030-01-31T12:00:00Z")
+        .await
+        .unwrap();
+    let err = clients::add_gallon(&db, client.id, "Pedra Azul".into(), "31/01/2030")
+        .await
+        .unwrap_err();
+    assert_eq!(err.field(), Some("expiration_date"));
+
+    let details = clients::client_details(&db, client.id).await.unwrap();
+    assert_eq!(details.gallons.len(), 1);
+    assert_eq!(details.total_orders, 0);
+}
@@ -0,0 +1,115 @@
+//! Banco em memória e cadastros prontos para os testes de integração.
+//!
+//! Cada arquivo de teste é compilado separadamente e usa só parte dos
+//! auxiliares, por isso o `dead_code` é liberado aqui.
+#![allow(dead_code)]
+
+use app_lib::db::entities::{client, client_gallon, product, sale};
+use app_lib::{clients, products, sales};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
+
+/// Banco SQLite em memória com o esquema e as sementes de `setup_schema`
+/// (categorias, admin/admin123, formas de envio e pagamento).
+pub async fn memory_db() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    app_lib::db::setup_schema(&db).await.unwrap();
+    db
+}
+
+pub async fn client(db: &DatabaseConnection, name: &str) -> client::Model {
+    clients::create_client(
+        db,
+        clients::ClientInput {
+            name: name.into(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Produto da categoria semeada "Água".
+pub async fn product(db: &DatabaseConnection, name: &str, price: f64, stock_quantity: i32) -> product::Model {
+    products::create_product(
+        db,
+        products::ProductInput {
+            name: name.into(),
+            price,
+            stock_quantity,
+            category: "Água".into(),
+            category_id: Some(1),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Item de venda no formato gravado pelo PDV.
+pub fn item(product: &product::Model, qty: f64) -> serde_json::Value {
+    serde_json::json!({ "id": product.id, "name": product.name, "qty": qty, "price": product.price })
+}
+
+/// Venda registrada agora, com o total calculado a partir dos itens.
+pub async fn sale(
+    db: &DatabaseConnection,
+    client: &client::Model,
+    items: Vec<serde_json::Value>,
+    payment_method: &str,
+) -> sale::Model {
+    let total = items
+        .iter()
+        .map(|i| i["qty"].as_f64().unwrap_or(0.0) * i["price"].as_f64().unwrap_or(0.0))
+        .sum();
+    sales::create_sale(
+        db,
+        sales::SaleInput {
+            client_id: client.id,
+            items: serde_json::Value::Array(items),
+            total,
+            payment_method: payment_method.into(),
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Venda com data e total fixos, para relatórios.
+pub async fn sale_at(
+    db: &DatabaseConnection,
+    client: &client::Model,
+    total: f64,
+    payment_method: &str,
+    created_at: DateTime<Utc>,
+) -> sale::Model {
+    sale::ActiveModel {
+        client_id: Set(client.id),
+        items: Set("[]".into()),
+        total: Set(total),
+        payment_method: Set(payment_method.into()),
+        created_at: Set(created_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
+
+/// Galão em posse do cliente que vence daqui a `expires_in`.
+pub async fn gallon(
+    db: &DatabaseConnection,
+    client: &client::Model,
+    brand: &str,
+    expires_in: Duration,
+) -> client_gallon::Model {
+    client_gallon::ActiveModel {
+        client_id: Set(client.id),
+        brand: Set(brand.into()),
+        expiration_date: Set(Utc::now() + expires_in),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
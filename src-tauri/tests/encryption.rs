@@ -0,0 +1,150 @@
+//! Criptografia do banco com o SQLCipher.
+#![cfg(feature = "sqlcipher")]
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use app_lib::backup::{self, BackupKind};
+use app_lib::db::{self, Session};
+use app_lib::{encryption, startup};
+use app_lib::error::ErrorCode;
+use chrono::Utc;
+
+const PASSPHRASE: &str = "agua-e-gas-2024";
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aquagas-encryption-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Banco em arquivo, sem senha, com um cliente e um backup.
+async fn plaintext_session(data: &Path) -> Session {
+    let connection = db::open(&db::database_path(data), None).await.unwrap();
+    common::client(&connection, "Maria Silva").await;
+    backup::create_backup(&connection, &db::backup_dir(data), BackupKind::Manual, Utc::now(), None)
+        .await
+        .unwrap();
+    Session { connection, key: None }
+}
+
+fn contains(path: &Path, text: &str) -> bool {
+    fs::read(path).unwrap().windows(text.len()).any(|w| w == text.as_bytes())
+}
+
+#[tokio::test]
+async fn encrypted_database_is_unreadable_without_the_key() {
+    let data = scratch_dir("unreadable");
+    let database = db::database_path(&data);
+    let dir = db::backup_dir(&data);
+    let mut session = plaintext_session(&data).await;
+    assert!(!db::is_encrypted(&database));
+
+    let result = encryption::encrypt_database(&mut session, &database, &dir, PASSPHRASE).await.unwrap();
+    assert_eq!(result.encrypted_backups.len(), 1);
+    assert!(result.skipped_backups.is_empty());
+    assert_eq!(session.key.as_deref(), Some(PASSPHRASE));
+    session.connection.close().await.unwrap();
+
+    let backup_path = dir.join(&result.encrypted_backups[0]);
+    for path in [&database, &backup_path] {
+        assert!(db::is_encrypted(path), "{}", path.display());
+        assert!(!contains(path, "Maria Silva"), "{}", path.display());
+    }
+
+    assert!(db::open(&database, None).await.is_err());
+    assert!(db::open(&database, Some("senha-errada")).await.is_err());
+    assert!(backup::verify(&backup_path, None).await.is_err());
+
+    let reopened = db::open(&database, Some(PASSPHRASE)).await.unwrap();
+    assert!(!db::seed_demo_data(&reopened).await.unwrap(), "o cliente deve continuar no banco");
+    assert_eq!(db::schema_version(&reopened).await.unwrap(), db::SCHEMA_VERSION);
+    reopened.close().await.unwrap();
+    backup::verify(&backup_path, Some(PASSPHRASE)).await.unwrap();
+
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn session_keeps_working_and_new_backups_are_encrypted() {
+    let data = scratch_dir("session");
+    let database = db::database_path(&data);
+    let dir = db::backup_dir(&data);
+    let mut session = plaintext_session(&data).await;
+    encryption::encrypt_database(&mut session, &database, &dir, PASSPHRASE).await.unwrap();
+
+    common::client(&session.connection, "João Souza").await;
+    let file = backup::create_backup(&session.connection, &dir, BackupKind::Manual, Utc::now(), session.key.as_deref())
+        .await
+        .unwrap();
+    let path = dir.join(&file.name);
+    assert!(db::is_encrypted(&path));
+    assert!(!contains(&path, "João Souza"));
+
+    // Com a criptografia ativa, backups sem senha não são restaurados
+    let plain = dir.join("aquagas-manual-20200101-000000.db");
+    let conn = db::open(&plain, None).await.unwrap();
+    conn.close().await.unwrap();
+    let err = backup::restore(&mut session.connection, &database, &dir, "aquagas-manual-20200101-000000.db", session.key.as_deref())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    session.connection.close().await.unwrap();
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn encryption_requires_a_strong_passphrase_and_runs_once() {
+    let data = scratch_dir("once");
+    let database = db::database_path(&data);
+    let dir = db::backup_dir(&data);
+    let mut session = plaintext_session(&data).await;
+
+    let err = encryption::encrypt_database(&mut session, &database, &dir, "curta").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert_eq!(err.field(), Some("passphrase"));
+    assert!(!db::is_encrypted(&database));
+
+    encryption::encrypt_database(&mut session, &database, &dir, PASSPHRASE).await.unwrap();
+    let err = encryption::encrypt_database(&mut session, &database, &dir, PASSPHRASE).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    session.connection.close().await.unwrap();
+    fs::remove_dir_all(&data).unwrap();
+}
+
+#[tokio::test]
+async fn startup_restore_keeps_the_database_encrypted() {
+    let data = scratch_dir("startup-restore");
+    let database = db::database_path(&data);
+    let dir = db::backup_dir(&data);
+    let mut session = plaintext_session(&data).await;
+    encryption::encrypt_database(&mut session, &database, &dir, PASSPHRASE).await.unwrap();
+    let encrypted = backup::create_backup(&session.connection, &dir, BackupKind::Manual, Utc::now(), Some(PASSPHRASE))
+        .await
+        .unwrap()
+        .name;
+    // Backup sem senha que ficou de fora da criptografia (ex.: cópia manual)
+    let legacy = "aquagas-manual-20200101-000000.db";
+    db::open(&dir.join(legacy), None).await.unwrap().close().await.unwrap();
+    session.connection.close().await.unwrap();
+    assert!(!db::is_encrypted(&dir.join(legacy)));
+
+    // Na tela de bloqueio a troca exige a senha e um backup cifrado com ela
+    let err = startup::restore_backup(&database, &dir, legacy, None).await.unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("passphrase")));
+    let err = startup::restore_backup(&database, &dir, legacy, Some(PASSPHRASE)).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert!(startup::restore_backup(&database, &dir, &encrypted, Some("senha-errada")).await.is_err());
+    assert!(db::is_encrypted(&database));
+    assert!(fs::read_dir(&data).unwrap().all(|f| !f.unwrap().file_name().to_string_lossy().contains(".falha")));
+
+    startup::restore_backup(&database, &dir, &encrypted, Some(PASSPHRASE)).await.unwrap().unwrap();
+    assert!(db::is_encrypted(&database));
+    db::open(&database, Some(PASSPHRASE)).await.unwrap().close().await.unwrap();
+
+    fs::remove_dir_all(&data).unwrap();
+}
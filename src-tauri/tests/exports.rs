@@ -0,0 +1,100 @@
+//! Planilhas de vendas, produtos, clientes e contas a receber.
+
+mod common;
+
+use std::io::{Cursor, Read};
+
+use app_lib::error::ErrorCode;
+use app_lib::exports;
+use app_lib::sales::RECEIVABLE_PAYMENT_METHOD;
+use app_lib::spreadsheet::{self, Cell, Format, Sheet};
+use chrono::{TimeZone, Utc};
+
+const START: &str = "2024-03-01T00:00:00Z";
+const END: &str = "2024-03-10T23:59:59Z";
+
+fn csv_text(bytes: &[u8]) -> String {
+    let text = bytes.strip_prefix(b"\xEF\xBB\xBF").expect("CSV sem BOM UTF-8");
+    String::from_utf8(text.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn sales_csv_uses_brazilian_excel_conventions_and_report_filters() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria; Silva").await;
+    let day = |d: u32| Utc.with_ymd_and_hms(2024, 3, d, 15, 30, 0).unwrap();
+    common::sale_at(&db, &maria, 1234.5, "PIX", day(2)).await;
+    common::sale_at(&db, &maria, 10.0, "Dinheiro", day(3)).await;
+    common::sale_at(&db, &maria, 99.0, "PIX", day(20)).await;
+
+    let sheet = exports::sales_sheet(&db, START, END, "PIX").await.unwrap();
+    let text = csv_text(&spreadsheet::to_csv(&sheet).unwrap());
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines[0], "Nº;Data;Cliente;Itens;Pagamento;Total");
+    assert_eq!(lines.len(), 2);
+    // Horário no fuso do negócio (America/Sao_Paulo)
+    assert!(lines[1].ends_with(";02/03/2024 12:30;\"Maria; Silva\";;PIX;1234,50"), "{}", lines[1]);
+
+    let all = exports::sales_sheet(&db, START, END, "todos").await.unwrap();
+    assert_eq!(all.rows.len(), 2);
+
+    let err = exports::sales_sheet(&db, "ontem", END, "todos").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+}
+
+#[tokio::test]
+async fn receivables_only_list_credit_sales_of_the_period() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let day = |d: u32| Utc.with_ymd_and_hms(2024, 3, d, 15, 0, 0).unwrap();
+    common::sale_at(&db, &maria, 25.0, RECEIVABLE_PAYMENT_METHOD, day(4)).await;
+    common::sale_at(&db, &maria, 30.0, "PIX", day(4)).await;
+    common::sale_at(&db, &maria, 40.0, RECEIVABLE_PAYMENT_METHOD, day(25)).await;
+
+    let sheet = exports::receivables_sheet(&db, START, END).await.unwrap();
+    assert_eq!(sheet.rows.len(), 1);
+    assert_eq!(sheet.rows[0][2], Cell::Text("Maria".into()));
+    assert_eq!(sheet.rows[0][5], Cell::Money(25.0));
+}
+
+#[tokio::test]
+async fn products_and_clients_are_exported_as_xlsx() {
+    let db = common::memory_db().await;
+    common::product(&db, "Água <20L> & cia", 12.5, 40).await;
+    common::client(&db, "=HYPERLINK(\"x\")").await;
+
+    let products = exports::products_sheet(&db).await.unwrap();
+    assert_eq!(products.rows[0][2], Cell::Text("Água".into()));
+
+    let file = exports::export(&db, &products, Format::Xlsx).await.unwrap();
+    assert!(file.file_name.starts_with("produtos-") && file.file_name.ends_with(".xlsx"));
+    let bytes = spreadsheet::to_xlsx(&products).unwrap();
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    for part in ["[Content_Types].xml", "xl/workbook.xml", "xl/styles.xml"] {
+        assert!(zip.by_name(part).is_ok(), "{}", part);
+    }
+    let mut sheet = String::new();
+    zip.by_name("xl/worksheets/sheet1.xml").unwrap().read_to_string(&mut sheet).unwrap();
+    assert!(sheet.contains("Água &lt;20L&gt; &amp; cia"));
+    assert!(sheet.contains(r#"<c r="D2" s="2"><v>12.5</v></c>"#));
+    assert!(sheet.contains(r#"<c r="E2" s="0"><v>40</v></c>"#));
+
+    // Fórmulas não são executadas ao abrir o CSV
+    let clients = exports::clients_sheet(&db).await.unwrap();
+    let text = csv_text(&spreadsheet::to_csv(&clients).unwrap());
+    assert!(text.contains("'=HYPERLINK"));
+
+    let mut sheet = Sheet::new("Campos", &["Valor"]);
+    for value in ["+55 (27) 99999-1234", "-5", "\t=1+1", "\r@SUM(A1)", "+cmd|' /C calc'!A0", "-"] {
+        sheet.rows.push(vec![Cell::from(value)]);
+    }
+    let text = csv_text(&spreadsheet::to_csv(&sheet).unwrap());
+    let lines: Vec<&str> = text.split('\n').skip(1).take(2).collect();
+    assert_eq!(lines, ["+55 (27) 99999-1234", "-5"]);
+    for guarded in ["'\t=1+1", "'\r@SUM(A1)", "'+cmd|", "'-"] {
+        assert!(text.contains(guarded), "{:?}", guarded);
+    }
+
+    assert_eq!(Format::parse("XLSX").unwrap(), Format::Xlsx);
+    assert_eq!(Format::parse("pdf").unwrap_err().field(), Some("format"));
+}
@@ -0,0 +1,23 @@
+//! Formatação pt-BR usada nos PDFs e exportações.
+
+use app_lib::format::{change, currency, decimal};
+
+#[test]
+fn formats_currency_with_brazilian_separators() {
+    assert_eq!(currency(0.0), "R$ 0,00");
+    assert_eq!(currency(5.5), "R$ 5,50");
+    assert_eq!(currency(1234.56), "R$ 1.234,56");
+    assert_eq!(currency(1_234_567.891), "R$ 1.234.567,89");
+    assert_eq!(currency(-10.0), "-R$ 10,00");
+    assert_eq!(currency(-0.001), "R$ 0,00");
+}
+
+#[test]
+fn formats_decimals_and_changes() {
+    assert_eq!(decimal(999.0, 0), "999");
+    assert_eq!(decimal(1000.0, 0), "1.000");
+    assert_eq!(decimal(12.345, 1), "12,3");
+    assert_eq!(change(0.123, 1), "+12,3%");
+    assert_eq!(change(-0.5, 0), "-50%");
+    assert_eq!(change(0.0, 1), "+0,0%");
+}
@@ -0,0 +1,168 @@
+//! Importação de clientes e produtos por planilha.
+
+mod common;
+
+use app_lib::clients;
+use app_lib::db::entities::{client, product};
+use app_lib::error::ErrorCode;
+use app_lib::imports::{self, ColumnMapping, ImportKind};
+use app_lib::spreadsheet::{self, Cell, Format, Sheet};
+use sea_orm::{EntityTrait, PaginatorTrait};
+
+const CLIENTS_CSV: &str = "Nome;Telefone;CPF;Cidade;UF\n\
+Maria Silva;(27) 99999-1234;529.982.247-25;Colatina;es\n\
+João Souza;27 3333-4444;111.111.111-11;Colatina;ES\n\
+;;;;\n\
+Ana Lima;123;;Vitória;ES\n\
+Pedro Alves;+55 27 98888-7777;;Vitória;XX\n\
+Carla Dias;27988887777;;Serra;ES\n";
+
+fn errors(row: &imports::ImportRow) -> Vec<&str> {
+    row.errors.iter().filter_map(|e| e.field.as_deref()).collect()
+}
+
+#[tokio::test]
+async fn client_preview_validates_each_row_without_saving() {
+    let db = common::memory_db().await;
+
+    let preview = imports::preview(&db, ImportKind::Clients, Format::Csv, CLIENTS_CSV.as_bytes(), None)
+        .await
+        .unwrap();
+    assert_eq!(preview.headers[2], "CPF");
+    let expected: ColumnMapping =
+        [("name", 0), ("phone", 1), ("document", 2), ("city", 3), ("state", 4)].map(|(f, c)| (f.to_string(), c)).into();
+    assert_eq!(preview.mapping, expected);
+    assert!(preview.missing_fields.is_empty());
+
+    // A linha em branco (4) é ignorada
+    let lines: Vec<_> = preview.rows.iter().map(|r| r.line).collect();
+    assert_eq!(lines, [2, 3, 5, 6, 7]);
+    assert!(preview.rows[0].errors.is_empty());
+    assert_eq!(errors(&preview.rows[1]), ["document"]);
+    assert_eq!(errors(&preview.rows[2]), ["phone"]);
+    assert_eq!(errors(&preview.rows[3]), ["state"]);
+    // Mesmo telefone da linha 6, que também é rejeitada pela UF
+    assert!(preview.rows[4].errors.is_empty());
+    assert_eq!((preview.valid_rows, preview.invalid_rows), (2, 3));
+
+    assert_eq!(client::Entity::find().count(&db).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn client_commit_saves_valid_rows_and_rejects_duplicates() {
+    let db = common::memory_db().await;
+    let csv = "Nome;Telefone;CPF\n\
+Maria Silva;(27) 99999-1234;529.982.247-25\n\
+Maria S.;27999991234;\n\
+Outra Maria;;52998224725\n\
+Sem zeros;;1234567890\n";
+    let mapping: ColumnMapping = [("name", 0), ("phone", 1), ("document", 2)].map(|(f, c)| (f.to_string(), c)).into();
+
+    let result = imports::commit(&db, ImportKind::Clients, Format::Csv, csv.as_bytes(), mapping.clone())
+        .await
+        .unwrap();
+    assert_eq!(result.imported, 2);
+    let rejected: Vec<_> = result.rejected.iter().map(|r| (r.line, errors(r))).collect();
+    assert_eq!(rejected, [(3, vec!["phone"]), (4, vec!["document"])]);
+    assert!(result.rejected[0].errors[0].message.contains("linha 2"));
+
+    let maria = clients::client_details(&db, 1).await.unwrap().client;
+    assert_eq!(maria.phone.as_deref(), Some("(27) 99999-1234"));
+    assert_eq!(maria.document.as_deref(), Some("52998224725"));
+    // CPF lido como número, sem o zero à esquerda
+    let padded = clients::client_details(&db, 2).await.unwrap().client;
+    assert_eq!(padded.document.as_deref(), Some("01234567890"));
+
+    // Uma segunda importação do mesmo arquivo não duplica nada
+    let again = imports::commit(&db, ImportKind::Clients, Format::Csv, csv.as_bytes(), mapping).await.unwrap();
+    assert_eq!(again.imported, 0);
+    assert!(again.rejected[0].errors[0].message.contains("Maria Silva"));
+    assert_eq!(client::Entity::find().count(&db).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn products_are_imported_from_xlsx_with_category_by_name() {
+    let db = common::memory_db().await;
+    common::product(&db, "Água 20L", 12.0, 5).await;
+
+    let mut sheet = Sheet::new("Produtos", &["Descrição", "Preço", "Qtd", "Grupo"]);
+    for row in [
+        vec![Cell::from("Água 10L"), Cell::Money(8.5), Cell::Integer(20), Cell::from("água")],
+        vec![Cell::from("Gás P13"), Cell::from("R$ 1.110,90"), Cell::Empty, Cell::from("Gás")],
+        vec![Cell::from("água 20l"), Cell::from("12"), Cell::Integer(1), Cell::from("Água")],
+        vec![Cell::from("Filtro"), Cell::from("abc"), Cell::Integer(-1), Cell::from("Filtros")],
+    ] {
+        sheet.rows.push(row);
+    }
+    let xlsx = spreadsheet::to_xlsx(&sheet).unwrap();
+
+    // "Grupo" não é reconhecido: falta escolher a coluna da categoria
+    let preview = imports::preview(&db, ImportKind::Products, Format::Xlsx, &xlsx, None).await.unwrap();
+    assert_eq!(preview.missing_fields, ["category"]);
+    assert!(preview.rows.is_empty());
+    let err = imports::commit(&db, ImportKind::Products, Format::Xlsx, &xlsx, preview.mapping.clone())
+        .await
+        .unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("mapping")));
+
+    let mut mapping = preview.mapping;
+    mapping.insert("category".into(), 3);
+    let preview = imports::preview(&db, ImportKind::Products, Format::Xlsx, &xlsx, Some(mapping.clone()))
+        .await
+        .unwrap();
+    assert_eq!(errors(&preview.rows[2]), ["name"]);
+    assert_eq!(errors(&preview.rows[3]), ["price", "stock_quantity", "category"]);
+
+    let result = imports::commit(&db, ImportKind::Products, Format::Xlsx, &xlsx, mapping).await.unwrap();
+    assert_eq!(result.imported, 2);
+    let products = product::Entity::find().all(&db).await.unwrap();
+    let gas = products.iter().find(|p| p.name == "Gás P13").unwrap();
+    assert_eq!((gas.price, gas.stock_quantity, gas.category.as_str()), (1110.9, 0, "Gás"));
+    let water = products.iter().find(|p| p.name == "Água 10L").unwrap();
+    assert_eq!((water.price, water.stock_quantity, water.category_id), (8.5, 20, Some(1)));
+}
+
+#[test]
+fn phones_and_decimals_follow_brazilian_formats() {
+    assert_eq!(clients::normalize_phone("+55 (27) 99999-1234").unwrap(), "(27) 99999-1234");
+    assert_eq!(clients::normalize_phone("2733334444").unwrap(), "(27) 3333-4444");
+    assert!(clients::normalize_phone("0273333444").is_err());
+    assert!(clients::normalize_phone("99999-1234").is_err());
+
+    assert_eq!(imports::parse_decimal("1.234,56"), Some(1234.56));
+    assert_eq!(imports::parse_decimal("R$ 12,5"), Some(12.5));
+    assert_eq!(imports::parse_decimal("1.500"), Some(1500.0));
+    assert_eq!(imports::parse_decimal("R$ 1.500"), Some(1500.0));
+    assert_eq!(imports::parse_decimal("-2.000,5"), Some(-2000.5));
+    // O ponto só separa milhares: "12.5" é ambíguo e fica como erro na linha
+    assert_eq!(imports::parse_decimal("12.5"), None);
+    assert_eq!(imports::parse_decimal("1.50"), None);
+    assert_eq!(imports::parse_decimal("1.500.00"), None);
+    assert_eq!(imports::parse_decimal("doze"), None);
+}
+
+#[test]
+fn csv_saved_by_excel_is_read_as_windows_1252() {
+    // "Conceição;“Água” 20L;10 €" gravado pelo Excel em Windows-1252
+    let bytes = b"Concei\xe7\xe3o;\x93\xc1gua\x94 20L;10 \x80\n";
+    let rows = spreadsheet::read_csv(bytes).unwrap();
+    assert_eq!(rows, [["Conceição", "“Água” 20L", "10 €"]]);
+}
+
+#[test]
+fn xlsx_references_beyond_excel_limits_are_ignored() {
+    use std::io::Write;
+
+    let sheet = r#"<worksheet><sheetData>
+        <row r="1"><c r="A1" t="inlineStr"><is><t>Nome</t></is></c><c r="ZZZZZZZZZZZZZZZ1" t="inlineStr"><is><t>x</t></is></c></row>
+        <row r="2"><c r="B2" t="inlineStr"><is><t>Maria</t></is></c><c r="XFE2" t="inlineStr"><is><t>x</t></is></c></row>
+        <row r="99999999999"><c r="A3" t="inlineStr"><is><t>Ana</t></is></c></row>
+    </sheetData></worksheet>"#;
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file("xl/worksheets/sheet1.xml", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(sheet.as_bytes()).unwrap();
+    let bytes = zip.finish().unwrap().into_inner();
+
+    let rows = spreadsheet::read_xlsx(&bytes).unwrap();
+    assert_eq!(rows, [vec!["Nome"], vec!["", "Maria"], vec!["Ana"]]);
+}
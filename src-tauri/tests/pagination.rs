@@ -0,0 +1,93 @@
+//! Paginação, ordenação e busca das listagens de clientes e produtos.
+
+mod common;
+
+use app_lib::clients::{self, ClientQuery};
+use app_lib::pagination::{SortDirection, MAX_PAGE_SIZE};
+use app_lib::products::{self, ProductQuery};
+
+#[tokio::test]
+async fn pages_split_the_listing_without_repeating_rows() {
+    let db = common::memory_db().await;
+    for i in 0..7 {
+        common::client(&db, &format!("Cliente {}", i)).await;
+    }
+
+    let mut seen = Vec::new();
+    for page in 0..3 {
+        let result = clients::clients_page(
+            &db,
+            ClientQuery { page: Some(page), page_size: Some(3), sort_by: Some("name".into()), ..Default::default() },
+        )
+        .await
+        .unwrap();
+        assert_eq!((result.total, result.total_pages, result.page_size), (7, 3, 3));
+        seen.extend(result.items.into_iter().map(|c| c.name));
+    }
+    let expected: Vec<_> = (0..7).map(|i| format!("Cliente {}", i)).collect();
+    assert_eq!(seen, expected);
+
+    // Página além do fim vem vazia; tamanho fora dos limites é ajustado
+    let past = clients::clients_page(&db, ClientQuery { page: Some(5), page_size: Some(3), ..Default::default() })
+        .await
+        .unwrap();
+    assert!(past.items.is_empty());
+    let page = clients::clients_page(&db, ClientQuery { page_size: Some(0), ..Default::default() }).await.unwrap();
+    assert_eq!(page.page_size, 1);
+    let page = clients::clients_page(&db, ClientQuery { page_size: Some(10_000), ..Default::default() }).await.unwrap();
+    assert_eq!((page.page_size, page.items.len()), (MAX_PAGE_SIZE, 7));
+}
+
+#[tokio::test]
+async fn sort_direction_and_tiebreaker_are_applied() {
+    let db = common::memory_db().await;
+    let a = common::product(&db, "Água 20L", 12.0, 5).await;
+    let b = common::product(&db, "Gás P13", 110.0, 5).await;
+    let c = common::product(&db, "Água 10L", 8.0, 9).await;
+
+    let ids = |dir| {
+        let db = &db;
+        async move {
+            let query = ProductQuery { sort_by: Some("stock_quantity".into()), sort_dir: Some(dir), ..Default::default() };
+            let page = products::products_page(db, query).await.unwrap();
+            page.items.iter().map(|p| p.id).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(ids(SortDirection::Asc).await, [a.id, b.id, c.id]);
+    assert_eq!(ids(SortDirection::Desc).await, [c.id, b.id, a.id]);
+
+    let err = clients::clients_page(&db, ClientQuery { sort_by: Some("password".into()), ..Default::default() })
+        .await
+        .unwrap_err();
+    assert_eq!(err.field(), Some("sort_by"));
+}
+
+#[tokio::test]
+async fn search_treats_like_wildcards_literally() {
+    let db = common::memory_db().await;
+    for name in ["Promo 50% off", "Promo 500 ml", "Caixa_12", "Caixa 12", "Barra\\Invertida"] {
+        common::product(&db, name, 1.0, 1).await;
+    }
+
+    let names = |search: &'static str| {
+        let db = &db;
+        async move {
+            let query = ProductQuery { search: Some(search.into()), ..Default::default() };
+            let page = products::products_page(db, query).await.unwrap();
+            page.items.into_iter().map(|p| p.name).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(names("50%").await, ["Promo 50% off"]);
+    assert_eq!(names("a_1").await, ["Caixa_12"]);
+    assert_eq!(names("%").await, ["Promo 50% off"]);
+    assert_eq!(names("a\\i").await, ["Barra\\Invertida"]);
+    assert_eq!(names("promo").await.len(), 2);
+
+    common::client(&db, "Ana_Paula").await;
+    common::client(&db, "Ana Paula").await;
+    let page = clients::clients_page(&db, ClientQuery { search: Some("_".into()), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].name, "Ana_Paula");
+}
@@ -0,0 +1,45 @@
+//! BR Code do PIX comparado com os exemplos publicados pelo Banco Central.
+
+use app_lib::error::ErrorCode;
+use app_lib::pix::{self, PixCharge, PixConfig};
+
+fn fulano() -> PixConfig {
+    PixConfig {
+        key: "123e4567-e12b-12d1-a456-426655440000".into(),
+        merchant_name: "Fulano de Tal".into(),
+        merchant_city: "BRASILIA".into(),
+    }
+}
+
+#[test]
+fn crc16_matches_the_ccitt_false_check_value() {
+    assert_eq!(pix::crc16(b"123456789"), 0x29B1);
+    assert_eq!(pix::crc16(b""), 0xFFFF);
+}
+
+#[test]
+fn static_payload_matches_the_central_bank_example() {
+    // Manual de Padrões para Iniciação do Pix, exemplo de QR Code estático
+    let payload = pix::build_payload(&fulano(), &PixCharge::default()).unwrap();
+    assert_eq!(
+        payload,
+        "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR\
+         5913Fulano de Tal6008BRASILIA62070503***63041D3D"
+    );
+
+    // O CRC é sempre o do restante do payload
+    let charge = PixCharge { amount: Some(25.0), txid: Some("VENDA42".into()), ..Default::default() };
+    let payload = pix::build_payload(&fulano(), &charge).unwrap();
+    let (body, crc) = payload.split_at(payload.len() - 4);
+    assert!(body.contains("540525.00") && body.ends_with("6304"));
+    assert_eq!(crc, format!("{:04X}", pix::crc16(body.as_bytes())));
+}
+
+#[test]
+fn invalid_amounts_are_rejected() {
+    for amount in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let charge = PixCharge { amount: Some(amount), ..Default::default() };
+        let err = pix::build_payload(&fulano(), &charge).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Validation, "{}", amount);
+    }
+}
@@ -0,0 +1,112 @@
+//! Exportação e anonimização de dados de clientes (LGPD).
+
+mod common;
+
+use app_lib::error::ErrorCode;
+use app_lib::{audit, clients, privacy, sales, search};
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+
+async fn registered_client(db: &DatabaseConnection) -> app_lib::db::entities::client::Model {
+    clients::create_client(
+        db,
+        clients::ClientInput {
+            name: "Maria Silva".into(),
+            phone: Some("(27) 99999-1234".into()),
+            document: Some("529.982.247-25".into()),
+            cep: Some("29700-000".into()),
+            city: Some("Colatina".into()),
+            state: Some("ES".into()),
+            street: Some("Rua das Flores".into()),
+            number: Some("10".into()),
+            observations: Some("Entregar após as 18h".into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn export_gathers_everything_about_the_client() {
+    let db = common::memory_db().await;
+    let maria = registered_client(&db).await;
+    let other = common::client(&db, "João Souza").await;
+    common::sale_at(&db, &maria, 50.0, "Dinheiro", Utc::now()).await;
+    common::sale_at(&db, &maria, 30.0, sales::RECEIVABLE_PAYMENT_METHOD, Utc::now()).await;
+    common::sale_at(&db, &other, 99.0, sales::RECEIVABLE_PAYMENT_METHOD, Utc::now()).await;
+    common::gallon(&db, &maria, "Pureza", Duration::days(30)).await;
+
+    let export = privacy::export_client_data(&db, maria.id, "admin", "json").await.unwrap();
+    assert_eq!(export.client.document.as_deref(), Some("52998224725"));
+    assert_eq!(export.sales.len(), 2);
+    assert_eq!(export.gallons.len(), 1);
+    assert_eq!(export.receivables.len(), 1);
+    assert_eq!(export.receivables[0].total, 30.0);
+
+    assert_eq!(export.audit_log.len(), 1);
+    assert_eq!(export.audit_log[0].action, privacy::ACTION_EXPORT);
+    assert_eq!(export.audit_log[0].username, "admin");
+
+    let err = privacy::export_client_data(&db, 999, "admin", "json").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn anonymize_removes_personal_data_but_keeps_sales() {
+    let db = common::memory_db().await;
+    let maria = registered_client(&db).await;
+    let sale = common::sale_at(&db, &maria, 42.5, "PIX", Utc::now()).await;
+    common::gallon(&db, &maria, "Pureza", Duration::days(30)).await;
+
+    let anonymized = privacy::anonymize_client(&db, maria.id, "admin").await.unwrap();
+    assert_eq!(anonymized.name, format!("Cliente anonimizado #{}", maria.id));
+    assert!(anonymized.anonymized_at.is_some());
+    for field in [
+        &anonymized.phone,
+        &anonymized.document,
+        &anonymized.cep,
+        &anonymized.city,
+        &anonymized.street,
+        &anonymized.address,
+        &anonymized.observations,
+    ] {
+        assert!(field.is_none());
+    }
+
+    let details = clients::client_details(&db, maria.id).await.unwrap();
+    assert_eq!(details.sales.len(), 1);
+    assert_eq!(details.sales[0].id, sale.id);
+    assert_eq!(details.sales[0].total, 42.5);
+    assert!(details.gallons.is_empty());
+
+    assert!(search::search(&db, "Maria", 10).await.unwrap().is_empty());
+
+    let trail = audit::list_for(&db, audit::CLIENT, maria.id).await.unwrap();
+    assert_eq!(trail.len(), 1);
+    assert_eq!(trail[0].action, privacy::ACTION_ANONYMIZE);
+
+    let err = privacy::anonymize_client(&db, maria.id, "admin").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    let err = clients::update_client(&db, maria.id, clients::ClientInput { name: "Maria".into(), ..Default::default() })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    let err = clients::add_gallon(&db, maria.id, "Pureza".into(), "2030-01-31T12:00:00Z").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert!(clients::client_details(&db, maria.id).await.unwrap().gallons.is_empty());
+}
+
+#[tokio::test]
+async fn anonymize_without_responsible_user_changes_nothing() {
+    let db = common::memory_db().await;
+    let maria = registered_client(&db).await;
+
+    let err = privacy::anonymize_client(&db, maria.id, "  ").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    let details = clients::client_details(&db, maria.id).await.unwrap();
+    assert_eq!(details.client.name, "Maria Silva");
+    assert!(details.client.anonymized_at.is_none());
+    assert!(audit::list_for(&db, audit::CLIENT, maria.id).await.unwrap().is_empty());
+}
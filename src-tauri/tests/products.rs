@@ -0,0 +1,119 @@
+//! Serviços de produtos e categorias executados sobre um banco SQLite em memória.
+
+mod common;
+
+use app_lib::error::ErrorCode;
+use app_lib::pagination::SortDirection;
+use app_lib::products::{self, ProductInput, ProductQuery};
+use app_lib::purchases::{self, PurchaseItemInput, SupplierInput};
+use app_lib::stock;
+
+fn input(name: &str, price: f64, stock_quantity: i32) -> ProductInput {
+    ProductInput {
+        name: name.into(),
+        price,
+        stock_quantity,
+        category: "Água".into(),
+        category_id: Some(1),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn create_update_and_delete() {
+    let db = common::memory_db().await;
+
+    let product = products::create_product(&db, input("Água 20L", 12.0, 30)).await.unwrap();
+    let updated = products::update_product(&db, product.id, input("Água 20L", 13.5, 28)).await.unwrap();
+    assert_eq!(updated.price, 13.5);
+    // O estoque só muda pelas movimentações
+    assert_eq!(updated.stock_quantity, 30);
+
+    let err = products::update_product(&db, 999, input("Água 20L", 1.0, 1)).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn delete_keeps_products_with_history() {
+    let db = common::memory_db().await;
+    let water = products::create_product(&db, input("Água 20L", 12.0, 30)).await.unwrap();
+    let gas = products::create_product(&db, input("Gás P13", 110.0, 0)).await.unwrap();
+    let typo = products::create_product(&db, input("Agua 2OL", 12.0, 0)).await.unwrap();
+
+    // O estoque inicial já está no histórico de movimentações
+    let err = products::delete_product(&db, water.id).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let supplier = purchases::create_supplier(&db, SupplierInput { name: "Distribuidora".into(), ..Default::default() })
+        .await
+        .unwrap();
+    let items = vec![PurchaseItemInput { product_id: gas.id, quantity: 5, unit_cost: 80.0 }];
+    purchases::create_purchase_order(&db, supplier.id, items, None, "admin").await.unwrap();
+    let err = products::delete_product(&db, gas.id).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert_eq!(stock::stock_history(&db, water.id).await.unwrap().len(), 1);
+
+    products::delete_product(&db, typo.id).await.unwrap();
+    let names: Vec<_> = products::list_products(&db).await.unwrap().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, ["Água 20L", "Gás P13"]);
+}
+
+#[tokio::test]
+async fn page_sorts_and_filters_by_category() {
+    let db = common::memory_db().await;
+    products::create_product(&db, input("Água 20L", 12.0, 30)).await.unwrap();
+    products::create_product(&db, input("Água 10L", 8.0, 5)).await.unwrap();
+    products::create_product(&db, ProductInput { category: "Gás".into(), category_id: Some(2), ..input("Gás P13", 110.0, 12) })
+        .await
+        .unwrap();
+
+    let page = products::products_page(
+        &db,
+        ProductQuery {
+            category_id: Some(1),
+            sort_by: Some("price".into()),
+            sort_dir: Some(SortDirection::Desc),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let names: Vec<_> = page.items.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["Água 20L", "Água 10L"]);
+
+    let err = products::products_page(&db, ProductQuery { sort_by: Some("id; DROP".into()), ..Default::default() })
+        .await
+        .unwrap_err();
+    assert_eq!(err.field(), Some("sort_by"));
+}
+
+#[tokio::test]
+async fn popular_products_are_the_four_largest_stocks() {
+    let db = common::memory_db().await;
+    for (i, stock) in [3, 50, 7, 20, 1].into_iter().enumerate() {
+        products::create_product(&db, input(&format!("Produto {}", i), 1.0, stock)).await.unwrap();
+    }
+
+    let stocks: Vec<_> = products::popular_products(&db)
+        .await
+        .unwrap()
+        .iter()
+        .map(|p| p.stock_quantity)
+        .collect();
+    assert_eq!(stocks, [50, 20, 7, 3]);
+}
+
+#[tokio::test]
+async fn categories_are_seeded_and_editable() {
+    let db = common::memory_db().await;
+    assert_eq!(products::list_categories(&db).await.unwrap().len(), 3);
+
+    let category = products::create_category(&db, "Filtros".into(), None).await.unwrap();
+    let renamed = products::update_category(&db, category.id, "Filtros e refis".into(), Some("Refis".into()))
+        .await
+        .unwrap();
+    assert_eq!(renamed.name, "Filtros e refis");
+
+    products::delete_category(&db, category.id).await.unwrap();
+    assert_eq!(products::list_categories(&db).await.unwrap().len(), 3);
+}
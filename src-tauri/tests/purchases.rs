@@ -0,0 +1,211 @@
+//! Fornecedores e pedidos de compra com recebimento parcial no estoque.
+
+mod common;
+
+use app_lib::db::entities::{product, supplier};
+use app_lib::error::ErrorCode;
+use app_lib::products::{self, ProductInput};
+use app_lib::purchases::{self, PurchaseItemInput, PurchaseStatus, ReceivedItem, SupplierInput};
+use app_lib::{audit, stock};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+async fn distributor(db: &DatabaseConnection) -> supplier::Model {
+    purchases::create_supplier(
+        db,
+        SupplierInput {
+            name: " Distribuidora Serra ".into(),
+            document: Some("11.222.333/0001-81".into()),
+            phone: Some("27 99999-1234".into()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+fn line(product: &product::Model, quantity: i32, unit_cost: f64) -> PurchaseItemInput {
+    PurchaseItemInput { product_id: product.id, quantity, unit_cost }
+}
+
+async fn stock_of(db: &DatabaseConnection, id: i32) -> i32 {
+    product::Entity::find_by_id(id).one(db).await.unwrap().unwrap().stock_quantity
+}
+
+#[tokio::test]
+async fn receiving_in_parts_moves_stock_and_records_cost() {
+    let db = common::memory_db().await;
+    let supplier = distributor(&db).await;
+    assert_eq!(supplier.name, "Distribuidora Serra");
+    assert_eq!(supplier.document.as_deref(), Some("11222333000181"));
+    let water = common::product(&db, "Água 20L", 12.0, 5).await;
+    let gas = common::product(&db, "Gás P13", 110.0, 2).await;
+
+    let order = purchases::create_purchase_order(
+        &db,
+        supplier.id,
+        vec![line(&water, 50, 4.5), line(&gas, 10, 80.0)],
+        None,
+        "admin",
+    )
+    .await
+    .unwrap();
+    assert_eq!(order.order.status, "open");
+    assert_eq!(order.total, 1025.0);
+    assert_eq!(stock_of(&db, water.id).await, 5);
+
+    // Chegaram 20 galões, com custo diferente do combinado
+    let water_item = order.items[0].item.id;
+    let received = ReceivedItem { item_id: water_item, quantity: 20, unit_cost: Some(4.8) };
+    let order = purchases::receive_purchase_order(&db, order.order.id, Some(vec![received]), "admin")
+        .await
+        .unwrap();
+    assert_eq!(order.order.status, "partial");
+    assert_eq!((order.items[0].item.received_quantity, order.items[0].item.unit_cost), (20, 4.8));
+    assert_eq!(stock_of(&db, water.id).await, 25);
+
+    // Depois do primeiro recebimento o custo do item não muda mais
+    let repriced = ReceivedItem { item_id: water_item, quantity: 10, unit_cost: Some(5.0) };
+    let err = purchases::receive_purchase_order(&db, order.order.id, Some(vec![repriced]), "admin")
+        .await
+        .unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("items")));
+    assert_eq!(stock_of(&db, water.id).await, 25);
+    let same = ReceivedItem { item_id: water_item, quantity: 10, unit_cost: Some(4.8) };
+    let order = purchases::receive_purchase_order(&db, order.order.id, Some(vec![same]), "admin").await.unwrap();
+    assert_eq!(stock_of(&db, water.id).await, 35);
+
+    // O restante chega de uma vez
+    let order = purchases::receive_purchase_order(&db, order.order.id, None, "joao").await.unwrap();
+    assert_eq!(order.order.status, "received");
+    assert!(order.order.received_at.is_some());
+    assert_eq!((stock_of(&db, water.id).await, stock_of(&db, gas.id).await), (55, 12));
+
+    let history = stock::stock_history(&db, water.id).await.unwrap();
+    let purchases: Vec<_> = history
+        .iter()
+        .filter(|m| m.reason == "purchase")
+        .map(|m| (m.delta, m.reference_id, m.username.as_deref()))
+        .collect();
+    assert_eq!(
+        purchases,
+        [(20, Some(order.order.id), Some("joao")), (10, Some(order.order.id), Some("admin")), (20, Some(order.order.id), Some("admin"))]
+    );
+    assert!(stock::check_consistency(&db).await.unwrap().is_empty());
+
+    let err = purchases::receive_purchase_order(&db, order.order.id, None, "admin").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    let listed = purchases::list_purchase_orders(&db, Some(PurchaseStatus::Received)).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!((listed[0].supplier_name.as_str(), listed[0].total, listed[0].item_count), ("Distribuidora Serra", 1040.0, 2));
+}
+
+#[tokio::test]
+async fn cancelling_keeps_what_was_already_received() {
+    let db = common::memory_db().await;
+    let supplier = distributor(&db).await;
+    let water = common::product(&db, "Água 20L", 12.0, 0).await;
+    let order = purchases::create_purchase_order(&db, supplier.id, vec![line(&water, 10, 5.0)], None, "admin")
+        .await
+        .unwrap();
+
+    let item_id = order.items[0].item.id;
+    for quantity in [0, 11] {
+        let received = ReceivedItem { item_id, quantity, unit_cost: None };
+        let err = purchases::receive_purchase_order(&db, order.order.id, Some(vec![received]), "admin")
+            .await
+            .unwrap_err();
+        assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("items")));
+    }
+    let received = ReceivedItem { item_id, quantity: 4, unit_cost: None };
+    purchases::receive_purchase_order(&db, order.order.id, Some(vec![received]), "admin").await.unwrap();
+
+    let err = purchases::cancel_purchase_order(&db, order.order.id, " ").await.unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("username")));
+    let cancelled = purchases::cancel_purchase_order(&db, order.order.id, "gerente").await.unwrap();
+    assert_eq!(cancelled.status, "cancelled");
+    assert!(cancelled.cancelled_at.is_some());
+    assert_eq!(stock_of(&db, water.id).await, 4);
+    let trail = audit::list_for(&db, audit::PURCHASE_ORDER, order.order.id).await.unwrap();
+    assert_eq!(trail.len(), 1);
+    assert_eq!((trail[0].action.as_str(), trail[0].username.as_str()), (purchases::ACTION_CANCEL, "gerente"));
+    assert!(trail[0].details.as_deref().unwrap().contains("partial"));
+    let err = purchases::cancel_purchase_order(&db, order.order.id, "gerente").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+
+    // Fornecedor com pedidos não pode ser excluído
+    let err = purchases::delete_supplier(&db, supplier.id).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+}
+
+#[tokio::test]
+async fn orders_and_suppliers_are_validated() {
+    let db = common::memory_db().await;
+    let supplier = distributor(&db).await;
+    let water = common::product(&db, "Água 20L", 12.0, 0).await;
+
+    let cases = [
+        (999, vec![line(&water, 1, 5.0)], "supplier_id"),
+        (supplier.id, vec![], "items"),
+        (supplier.id, vec![line(&water, 0, 5.0)], "items"),
+        (supplier.id, vec![line(&water, 1, -1.0)], "items"),
+        (supplier.id, vec![line(&water, 1, 5.0), line(&water, 2, 5.0)], "items"),
+        (supplier.id, vec![PurchaseItemInput { product_id: 999, quantity: 1, unit_cost: 1.0 }], "items"),
+    ];
+    for (supplier_id, items, field) in cases {
+        let err = purchases::create_purchase_order(&db, supplier_id, items, None, "admin").await.unwrap_err();
+        assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some(field)));
+    }
+
+    for (input, field) in [
+        (SupplierInput { name: " ".into(), ..Default::default() }, "name"),
+        (SupplierInput { name: "X".into(), document: Some("123".into()), ..Default::default() }, "document"),
+        (SupplierInput { name: "X".into(), phone: Some("12".into()), ..Default::default() }, "phone"),
+        (SupplierInput { name: "X".into(), email: Some("sem-arroba".into()), ..Default::default() }, "email"),
+    ] {
+        let err = purchases::create_supplier(&db, input).await.unwrap_err();
+        assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some(field)));
+    }
+
+    let err = purchases::receive_purchase_order(&db, 999, None, "admin").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn receiving_updates_the_weighted_average_cost() {
+    let db = common::memory_db().await;
+    let supplier = distributor(&db).await;
+    let input = ProductInput {
+        name: "Gás P13".into(),
+        price: 110.0,
+        stock_quantity: 10,
+        category: "Gás".into(),
+        category_id: Some(2),
+        cost_price: Some(80.0),
+        ..Default::default()
+    };
+    let gas = products::create_product(&db, input).await.unwrap();
+    let water = common::product(&db, "Água 20L", 12.0, 0).await;
+
+    let order = purchases::create_purchase_order(
+        &db,
+        supplier.id,
+        vec![line(&gas, 30, 84.0), line(&water, 20, 4.5)],
+        None,
+        "admin",
+    )
+    .await
+    .unwrap();
+    purchases::receive_purchase_order(&db, order.order.id, None, "admin").await.unwrap();
+
+    let cost_of = |id| {
+        let db = &db;
+        async move { product::Entity::find_by_id(id).one(db).await.unwrap().unwrap().cost_price }
+    };
+    // (10 × 80 + 30 × 84) / 40; sem custo anterior vale o da compra
+    assert_eq!(cost_of(gas.id).await, 83.0);
+    assert_eq!(cost_of(water.id).await, 4.5);
+
+    assert_eq!(purchases::average_cost(0, 83.0, 5, 90.0), 90.0);
+    assert_eq!(purchases::average_cost(3, 10.0, 0, 99.0), 10.0);
+}
@@ -0,0 +1,95 @@
+//! Garante que as consultas com nome de cliente não fazem uma consulta por venda.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use app_lib::db::entities::{client, client_gallon, sale};
+use chrono::{Duration, Utc};
+use sea_orm::{Database, DatabaseConnection, EntityTrait, Set};
+
+const CLIENTS: usize = 200;
+
+async fn seeded_db(sales: usize) -> (DatabaseConnection, Arc<AtomicUsize>) {
+    let mut db = Database::connect("sqlite::memory:").await.unwrap();
+    app_lib::db::setup_schema(&db).await.unwrap();
+
+    let now = Utc::now();
+    let clients: Vec<_> = (0..CLIENTS)
+        .map(|i| client::ActiveModel {
+            name: Set(format!("Cliente {}", i)),
+            created_at: Set(now),
+            ..Default::default()
+        })
+        .collect();
+    for chunk in clients.chunks(500) {
+        client::Entity::insert_many(chunk.to_vec()).exec(&db).await.unwrap();
+    }
+
+    let sales: Vec<_> = (0..sales)
+        .map(|i| sale::ActiveModel {
+            client_id: Set((i % CLIENTS) as i32 + 1),
+            items: Set("[]".into()),
+            total: Set(10.0 + (i % 7) as f64),
+            payment_method: Set(if i % 2 == 0 { "PIX" } else { "Dinheiro" }.into()),
+            created_at: Set(now - Duration::minutes(i as i64)),
+            ..Default::default()
+        })
+        .collect();
+    for chunk in sales.chunks(500) {
+        sale::Entity::insert_many(chunk.to_vec()).exec(&db).await.unwrap();
+    }
+
+    let gallons: Vec<_> = (0..CLIENTS)
+        .map(|i| client_gallon::ActiveModel {
+            client_id: Set(i as i32 + 1),
+            brand: Set("Marca".into()),
+            expiration_date: Set(now + Duration::days((i % 20) as i64 + 1)),
+            ..Default::default()
+        })
+        .collect();
+    client_gallon::Entity::insert_many(gallons).exec(&db).await.unwrap();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let c = counter.clone();
+    db.set_metric_callback(move |_| {
+        c.fetch_add(1, Ordering::SeqCst);
+    });
+    (db, counter)
+}
+
+/// Número de consultas feitas por `f`, identificado pelo comando.
+async fn measure<F, Fut, T>(label: &'static str, counter: &AtomicUsize, f: F) -> (&'static str, usize)
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<T, app_lib::error::AppError>>,
+{
+    counter.store(0, Ordering::SeqCst);
+    f().await.unwrap();
+    (label, counter.load(Ordering::SeqCst))
+}
+
+async fn query_counts(sales: usize) -> Vec<(&'static str, usize)> {
+    let (db, counter) = seeded_db(sales).await;
+    let start = (Utc::now() - Duration::days(30)).to_rfc3339();
+    let end = (Utc::now() + Duration::days(1)).to_rfc3339();
+
+    vec![
+        measure("get_recent_sales", &counter, || app_lib::sales::recent_sales(&db)).await,
+        measure("get_sales_report", &counter, || {
+            app_lib::reports::sales_report(&db, &start, &end, "todos")
+        })
+        .await,
+        measure("get_expiring_gallons", &counter, || app_lib::reports::expiring_gallons(&db)).await,
+        measure("get_sale_details", &counter, || app_lib::sales::sale_details(&db, 1)).await,
+    ]
+}
+
+#[tokio::test]
+async fn client_name_lookups_do_not_grow_with_sales() {
+    let small = query_counts(10).await;
+    let large = query_counts(5_000).await;
+
+    assert_eq!(small, large);
+    // O relatório também lê o fuso horário do negócio nas configurações
+    assert!(large.iter().all(|&(_, q)| q <= 5), "consultas: {:?}", large);
+}
@@ -0,0 +1,105 @@
+//! Os totais calculados no banco devem ser iguais aos calculados em memória.
+
+use std::collections::{HashMap, HashSet};
+
+use app_lib::db::entities::{client, sale};
+use chrono::{Duration, TimeZone, Utc};
+use sea_orm::{Database, DatabaseConnection, EntityTrait, Set};
+
+async fn seeded_db() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    app_lib::db::setup_schema(&db).await.unwrap();
+
+    let clients: Vec<_> = (0..15)
+        .map(|i| client::ActiveModel {
+            name: Set(format!("Cliente {}", i)),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        })
+        .collect();
+    client::Entity::insert_many(clients).exec(&db).await.unwrap();
+
+    let base = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    let methods = ["PIX", "Dinheiro", "Fiado"];
+    let sales: Vec<_> = (0..600)
+        .map(|i| sale::ActiveModel {
+            client_id: Set(i % 15 + 1),
+            items: Set("[]".into()),
+            total: Set(5.0 + (i % 13) as f64 * 2.5),
+            payment_method: Set(methods[i as usize % 3].into()),
+            created_at: Set(base + Duration::minutes(i as i64 * 97)),
+            ..Default::default()
+        })
+        .collect();
+    for chunk in sales.chunks(200) {
+        sale::Entity::insert_many(chunk.to_vec()).exec(&db).await.unwrap();
+    }
+    db
+}
+
+/// Cálculo anterior, feito sobre todas as vendas carregadas em memória.
+fn expected(sales: &[sale::Model], prev: &[sale::Model]) -> serde_json::Value {
+    let total_revenue: f64 = sales.iter().map(|s| s.total).sum();
+    let sales_count = sales.len() as f64;
+    let avg_ticket = if sales_count > 0.0 { total_revenue / sales_count } else { 0.0 };
+    let unique_clients = sales.iter().map(|s| s.client_id).collect::<HashSet<_>>().len();
+
+    let prev_revenue: f64 = prev.iter().map(|s| s.total).sum();
+    let prev_sales_count = prev.len() as f64;
+    let prev_avg_ticket = if prev_sales_count > 0.0 { prev_revenue / prev_sales_count } else { 0.0 };
+    let prev_unique_clients = prev.iter().map(|s| s.client_id).collect::<HashSet<_>>().len();
+
+    let change = |curr: f64, prev: f64| if prev == 0.0 { None } else { Some((curr - prev) / prev) };
+    let metric = |curr: f64, prev: f64| serde_json::json!({ "value": curr, "previous": prev, "change": change(curr, prev) });
+
+    // O gráfico agrupa pelo dia no fuso do negócio (padrão America/Sao_Paulo)
+    let mut chart_map: HashMap<String, f64> = HashMap::new();
+    for sale in sales {
+        let day = sale.created_at.with_timezone(&chrono_tz::America::Sao_Paulo).date_naive();
+        *chart_map.entry(day.to_string()).or_insert(0.0) += sale.total;
+    }
+    let mut chart_data: Vec<_> = chart_map
+        .into_iter()
+        .map(|(date, revenue)| serde_json::json!({ "date": date, "revenue": revenue }))
+        .collect();
+    chart_data.sort_by_key(|v| v["date"].as_str().unwrap().to_string());
+
+    serde_json::json!({
+        "summary": {
+            "revenue": metric(total_revenue, prev_revenue),
+            "sales_count": metric(sales_count, prev_sales_count),
+            "average_ticket": metric(avg_ticket, prev_avg_ticket),
+            "unique_clients": metric(unique_clients as f64, prev_unique_clients as f64)
+        },
+        "chart_data": chart_data,
+    })
+}
+
+#[tokio::test]
+async fn sales_report_matches_in_memory_totals() {
+    let db = seeded_db().await;
+    let all = sale::Entity::find().all(&db).await.unwrap();
+
+    let start = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 29, 23, 59, 59).unwrap();
+    let prev_start = start - (end - start) - Duration::seconds(1);
+    let prev_end = start - Duration::seconds(1);
+
+    for method in ["todos", "PIX", "Fiado"] {
+        let in_period = |s: &&sale::Model, from, to| {
+            s.created_at >= from && s.created_at <= to && (method == "todos" || s.payment_method == method)
+        };
+        let current: Vec<_> = all.iter().filter(|s| in_period(s, start, end)).cloned().collect();
+        let prev: Vec<_> = all.iter().filter(|s| in_period(s, prev_start, prev_end)).cloned().collect();
+
+        let report = app_lib::reports::sales_report(&db, &start.to_rfc3339(), &end.to_rfc3339(), method)
+            .await
+            .unwrap();
+        let report = serde_json::to_value(report).unwrap();
+
+        let want = expected(&current, &prev);
+        assert_eq!(report["summary"], want["summary"], "forma de pagamento: {}", method);
+        assert_eq!(report["chart_data"], want["chart_data"], "forma de pagamento: {}", method);
+        assert_eq!(report["sales_list"].as_array().unwrap().len(), current.len());
+    }
+}
@@ -0,0 +1,161 @@
+//! Totais do relatório de vendas, lucratividade, indicadores do painel e
+//! galões a vencer.
+
+mod common;
+
+use app_lib::db::entities::{product, sale};
+use app_lib::products::{self, ProductInput};
+use app_lib::{reports, sales};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use chrono::{Duration, TimeZone, Utc};
+
+#[tokio::test]
+async fn report_totals_compare_with_the_previous_period() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let joao = common::client(&db, "João").await;
+
+    // Período atual: 1 a 10/03 (UTC); anterior: 19 a 28/02
+    let day = |d: u32, m: u32| Utc.with_ymd_and_hms(2024, m, d, 15, 0, 0).unwrap();
+    common::sale_at(&db, &maria, 30.0, "PIX", day(2, 3)).await;
+    common::sale_at(&db, &maria, 10.0, "Dinheiro", day(2, 3)).await;
+    common::sale_at(&db, &joao, 20.0, "PIX", day(5, 3)).await;
+    common::sale_at(&db, &maria, 40.0, "PIX", day(25, 2)).await;
+    // Fora dos dois períodos
+    common::sale_at(&db, &joao, 999.0, "PIX", day(15, 3)).await;
+
+    let report = reports::sales_report(&db, "2024-03-01T00:00:00Z", "2024-03-10T23:59:59Z", "todos")
+        .await
+        .unwrap();
+    let summary = &report.summary;
+    assert_eq!(summary.revenue.value, 60.0);
+    assert_eq!(summary.revenue.previous, 40.0);
+    assert_eq!(summary.revenue.change, Some(0.5));
+    assert_eq!(summary.sales_count.value, 3.0);
+    assert_eq!(summary.average_ticket.value, 20.0);
+    assert_eq!(summary.unique_clients.value, 2.0);
+    assert_eq!(summary.unique_clients.previous, 1.0);
+    assert_eq!(report.sales_list.len(), 3);
+    assert_eq!(report.sales_list[0].client_name, "João");
+
+    let chart: Vec<_> = report.chart_data.iter().map(|d| (d.date.as_str(), d.revenue)).collect();
+    assert_eq!(chart, [("2024-03-02", 40.0), ("2024-03-05", 20.0)]);
+
+    let pix = reports::sales_report(&db, "2024-03-01T00:00:00Z", "2024-03-10T23:59:59Z", "PIX")
+        .await
+        .unwrap();
+    assert_eq!(pix.summary.revenue.value, 50.0);
+    assert_eq!(pix.summary.sales_count.value, 2.0);
+}
+
+#[tokio::test]
+async fn invalid_report_dates_point_to_the_field() {
+    let db = common::memory_db().await;
+    let err = reports::sales_report(&db, "01/03/2024", "2024-03-10T23:59:59Z", "todos")
+        .await
+        .unwrap_err();
+    assert_eq!(err.field(), Some("start_iso"));
+}
+
+#[tokio::test]
+async fn profitability_uses_the_cost_recorded_on_each_sale() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let product = |name: &str, price, category: &str, category_id, cost| ProductInput {
+        name: name.into(),
+        price,
+        stock_quantity: 50,
+        category: category.into(),
+        category_id: Some(category_id),
+        cost_price: Some(cost),
+        ..Default::default()
+    };
+    let water = products::create_product(&db, product("Água 20L", 12.0, "Água", 1, 5.0)).await.unwrap();
+    let gas = products::create_product(&db, product("Gás P13", 110.0, "Gás", 2, 80.0)).await.unwrap();
+
+    let sale = common::sale(&db, &maria, vec![common::item(&water, 10.0), common::item(&gas, 1.0)], "PIX").await;
+    let items: serde_json::Value = serde_json::from_str(&sale.items).unwrap();
+    assert_eq!((items[0]["cost"].as_f64(), items[1]["cost"].as_f64()), (Some(5.0), Some(80.0)));
+
+    // O custo muda depois da venda; a venda editada mantém o custo original
+    let mut changed: product::ActiveModel = water.clone().into();
+    changed.cost_price = Set(7.0);
+    changed.update(&db).await.unwrap();
+    let input = sales::SaleInput {
+        client_id: maria.id,
+        items: serde_json::json!([common::item(&water, 10.0), common::item(&gas, 2.0)]),
+        total: 340.0,
+        payment_method: "PIX".into(),
+    };
+    sales::update_sale(&db, sale.id, input).await.unwrap();
+    common::sale(&db, &maria, vec![common::item(&water, 5.0)], "Dinheiro").await;
+    // Venda antiga sem custo gravado nem id: usa o custo atual pelo nome
+    let legacy = sale::ActiveModel {
+        client_id: Set(maria.id),
+        items: Set(serde_json::json!([{ "name": "gás p13", "qty": 1.0, "price": 100.0 }]).to_string()),
+        total: Set(100.0),
+        payment_method: Set("PIX".into()),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    };
+    legacy.insert(&db).await.unwrap();
+
+    let start = (Utc::now() - Duration::days(1)).to_rfc3339();
+    let end = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let report = reports::profitability_report(&db, &start, &end, "todos").await.unwrap();
+    let summary = &report.summary;
+    // Receita 120 + 220 + 60 + 100; custo 50 + 160 + 35 + 80
+    assert_eq!((summary.revenue, summary.cost, summary.gross_profit), (500.0, 325.0, 175.0));
+    assert_eq!(summary.margin, Some(0.35));
+    assert_eq!(report.items_without_cost, 0);
+
+    let lines = |lines: &[reports::ProfitLine]| -> Vec<(String, f64, f64, f64)> {
+        lines.iter().map(|l| (l.label.clone(), l.quantity, l.revenue, l.cost)).collect()
+    };
+    assert_eq!(
+        lines(&report.by_product),
+        [("Água 20L".to_string(), 15.0, 180.0, 85.0), ("Gás P13".to_string(), 3.0, 320.0, 240.0)]
+    );
+    assert_eq!(lines(&report.by_category)[1].0, "Gás");
+    assert_eq!(report.by_day.len(), 1);
+    assert_eq!(report.by_day[0].gross_profit, 175.0);
+
+    let pix = reports::profitability_report(&db, &start, &end, "PIX").await.unwrap();
+    assert_eq!(pix.summary.revenue, 440.0);
+    assert!(product::Entity::find_by_id(gas.id).one(&db).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn dashboard_counts_today_and_yesterday() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+
+    // 14h em São Paulo
+    let now = Utc.with_ymd_and_hms(2024, 3, 6, 17, 0, 0).unwrap();
+    common::sale_at(&db, &maria, 50.0, "PIX", now - Duration::hours(1)).await;
+    common::sale_at(&db, &maria, 25.0, "PIX", now - Duration::hours(2)).await;
+    common::sale_at(&db, &maria, 100.0, "PIX", now - Duration::days(1)).await;
+
+    let stats = reports::dashboard_stats_at(&db, now).await.unwrap();
+    assert_eq!(stats.revenue, 75.0);
+    assert_eq!(stats.sales_count, 2);
+    assert_eq!(stats.revenue_yesterday, 100.0);
+    assert_eq!(stats.sales_change, 1);
+    assert_eq!(stats.client_count, 1);
+}
+
+#[tokio::test]
+async fn expiring_gallons_within_thirty_days() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let joao = common::client(&db, "João").await;
+
+    common::gallon(&db, &maria, "Pedra Azul", Duration::days(20)).await;
+    common::gallon(&db, &joao, "Águas Claras", Duration::days(3)).await;
+    common::gallon(&db, &joao, "Vencido", Duration::days(-2)).await;
+    common::gallon(&db, &maria, "Novo", Duration::days(90)).await;
+
+    let gallons = reports::expiring_gallons(&db).await.unwrap();
+    let found: Vec<_> = gallons.iter().map(|g| (g.client_name.as_str(), g.brand.as_str())).collect();
+    assert_eq!(found, [("João", "Águas Claras"), ("Maria", "Pedra Azul")]);
+}
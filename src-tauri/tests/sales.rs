@@ -0,0 +1,70 @@
+//! Registro de vendas.
+
+mod common;
+
+use app_lib::error::ErrorCode;
+use app_lib::sales::{self, SaleInput};
+
+#[tokio::test]
+async fn created_sale_keeps_items_and_client() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria Silva").await;
+    let water = common::product(&db, "Água 20L", 12.0, 30).await;
+    let gas = common::product(&db, "Gás P13", 110.0, 10).await;
+
+    let sale = common::sale(&db, &maria, vec![common::item(&water, 2.0), common::item(&gas, 1.0)], "PIX").await;
+    assert_eq!(sale.total, 134.0);
+    assert_eq!(sale.client_id, maria.id);
+
+    let details = sales::sale_details(&db, sale.id).await.unwrap();
+    assert_eq!(details.client_name, "Maria Silva");
+    assert_eq!(details.payment_method, "PIX");
+    assert_eq!(details.items[0]["name"], "Água 20L");
+    assert_eq!(details.items[1]["qty"], 1.0);
+
+    let recent = sales::recent_sales(&db).await.unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].id, sale.id);
+}
+
+#[tokio::test]
+async fn recent_sales_are_the_last_five() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let water = common::product(&db, "Água 20L", 12.0, 30).await;
+
+    let mut ids = Vec::new();
+    for qty in 1..=7 {
+        ids.push(common::sale(&db, &maria, vec![common::item(&water, qty as f64)], "Dinheiro").await.id);
+    }
+
+    let recent: Vec<_> = sales::recent_sales(&db).await.unwrap().iter().map(|s| s.id).collect();
+    ids.reverse();
+    assert_eq!(recent, ids[..5]);
+}
+
+#[tokio::test]
+async fn update_and_delete() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let joao = common::client(&db, "João").await;
+    let water = common::product(&db, "Água 20L", 12.0, 30).await;
+    let sale = common::sale(&db, &maria, vec![common::item(&water, 1.0)], "PIX").await;
+
+    let input = SaleInput {
+        client_id: joao.id,
+        items: serde_json::json!([common::item(&water, 3.0)]),
+        total: 36.0,
+        payment_method: "Fiado".into(),
+    };
+    let updated = sales::update_sale(&db, sale.id, input.clone()).await.unwrap();
+    assert_eq!(updated.client_id, joao.id);
+    assert_eq!(updated.total, 36.0);
+    assert_eq!(updated.created_at, sale.created_at);
+
+    sales::delete_sale(&db, sale.id).await.unwrap();
+    let err = sales::sale_details(&db, sale.id).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = sales::update_sale(&db, sale.id, input).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
@@ -0,0 +1,66 @@
+//! Busca textual sobre os índices FTS5 de clientes e produtos.
+
+mod common;
+
+use app_lib::clients::{self, ClientInput};
+use app_lib::search::{self, SearchKind};
+use sea_orm::DatabaseConnection;
+
+async fn titles(db: &DatabaseConnection, query: &str) -> Vec<String> {
+    search::search(db, query, 10).await.unwrap().into_iter().map(|r| r.title).collect()
+}
+
+#[tokio::test]
+async fn accents_are_ignored_in_both_directions() {
+    let db = common::memory_db().await;
+    common::client(&db, "João Conceição").await;
+    common::client(&db, "Joana Silva").await;
+    common::product(&db, "Agua 20L", 12.0, 1).await;
+
+    assert_eq!(titles(&db, "Joao").await, ["João Conceição"]);
+    assert_eq!(titles(&db, "conceicao").await, ["João Conceição"]);
+    assert_eq!(titles(&db, "água 20").await, ["Agua 20L"]);
+
+    // Termos curtos (abaixo do trigram) também ignoram acentos
+    assert_eq!(titles(&db, "Jo ça").await, ["João Conceição"]);
+    assert_eq!(titles(&db, "jo").await.len(), 2);
+    assert_eq!(titles(&db, "%").await, Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn phone_fragments_match_regardless_of_formatting() {
+    let db = common::memory_db().await;
+    let maria = clients::create_client(
+        &db,
+        ClientInput { name: "Maria".into(), phone: Some("(27) 99988-7766".into()), ..Default::default() },
+    )
+    .await
+    .unwrap();
+    common::client(&db, "Ana").await;
+
+    for query in ["99988-7766", "27 99988", "887766", "(27)"] {
+        let results = search::search(&db, query, 10).await.unwrap();
+        assert_eq!(results.len(), 1, "{}", query);
+        assert_eq!((results[0].kind, results[0].id), (SearchKind::Client, maria.id), "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn results_are_ordered_by_relevance() {
+    let db = common::memory_db().await;
+    let names = ["Kit mangueira, registro e válvula para botijão de gás", "Água 20L", "Gás P13", "Água 10L", "Copo 200ml"];
+    for name in names {
+        common::product(&db, name, 1.0, 1).await;
+    }
+    common::client(&db, "Gás do Centro").await;
+
+    let results = search::search(&db, "gas", 10).await.unwrap();
+    let products: Vec<_> = results.iter().filter(|r| r.kind == SearchKind::Product).map(|r| r.title.as_str()).collect();
+    assert_eq!(products, ["Gás P13", "Kit mangueira, registro e válvula para botijão de gás"]);
+    assert!(results.windows(2).all(|w| w[0].rank <= w[1].rank));
+
+    // Todos os termos precisam aparecer; o limite vale para o total
+    assert_eq!(titles(&db, "gas centro").await, ["Gás do Centro"]);
+    assert_eq!(search::search(&db, "gas", 1).await.unwrap().len(), 1);
+    assert!(titles(&db, "   ").await.is_empty());
+}
@@ -0,0 +1,69 @@
+//! Configurações e formas de envio/pagamento sobre um banco SQLite em memória.
+
+mod common;
+
+use app_lib::error::ErrorCode;
+use app_lib::{pix, settings, timezone};
+
+#[tokio::test]
+async fn save_setting_upserts_the_value() {
+    let db = common::memory_db().await;
+
+    settings::save_setting(&db, "pix_merchant_city".into(), "Colatina".into()).await.unwrap();
+    let saved = settings::save_setting(&db, "pix_merchant_city".into(), "Vitória".into()).await.unwrap();
+    assert_eq!(saved.value, "Vitória");
+
+    let all = settings::list_settings(&db).await.unwrap();
+    assert_eq!(all.iter().filter(|s| s.key == "pix_merchant_city").count(), 1);
+}
+
+#[tokio::test]
+async fn invalid_timezone_is_not_saved() {
+    let db = common::memory_db().await;
+
+    let err = settings::save_setting(&db, timezone::SETTING_KEY.into(), "America/Atlantida".into())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert!(settings::list_settings(&db).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn pix_code_requires_a_configured_key() {
+    let db = common::memory_db().await;
+    let charge = pix::PixCharge { amount: Some(25.0), ..Default::default() };
+
+    let err = pix::generate_configured(&db, &charge).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+
+    settings::save_setting(&db, pix::SETTING_KEY.into(), "contato@morais.com.br".into()).await.unwrap();
+    settings::save_setting(&db, pix::SETTING_MERCHANT_NAME.into(), "Morais Distribuidora".into()).await.unwrap();
+    settings::save_setting(&db, pix::SETTING_MERCHANT_CITY.into(), "Colatina".into()).await.unwrap();
+
+    let code = pix::generate_configured(&db, &charge).await.unwrap();
+    assert!(code.payload.contains("contato@morais.com.br"));
+    assert!(code.payload.contains("540525.00"));
+}
+
+#[tokio::test]
+async fn shipping_and_payment_methods_are_seeded_and_editable() {
+    let db = common::memory_db().await;
+    assert_eq!(settings::list_shipping_methods(&db).await.unwrap().len(), 3);
+    assert_eq!(settings::list_payment_methods(&db).await.unwrap().len(), 5);
+
+    let method = settings::create_shipping_method(&db, "Moto".into(), 7.5).await.unwrap();
+    let method = settings::update_shipping_method(&db, method.id, "Moto".into(), 8.0).await.unwrap();
+    assert_eq!(method.fee, 8.0);
+    settings::delete_shipping_method(&db, method.id).await.unwrap();
+
+    let payment = settings::create_payment_method(&db, "Boleto".into()).await.unwrap();
+    settings::update_payment_method(&db, payment.id, "Boleto bancário".into()).await.unwrap();
+    assert!(settings::list_payment_methods(&db)
+        .await
+        .unwrap()
+        .iter()
+        .any(|m| m.name == "Boleto bancário"));
+
+    let err = settings::update_payment_method(&db, 999, "X".into()).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
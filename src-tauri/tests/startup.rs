@@ -0,0 +1,74 @@
+//! Abertura do banco e recuperação a partir de um backup.
+
+use std::fs;
+use std::path::PathBuf;
+
+use app_lib::{backup, db, startup};
+
+/// Pasta temporária exclusiva do teste.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aquagas-startup-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn damaged_file_fails_to_open() {
+    let dir = scratch_dir("damaged");
+    let path = db::database_path(&dir);
+    fs::write(&path, vec![0x42; 8192]).unwrap();
+
+    let err = db::open(&path, None).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("banco de dados"), "{:#}", err);
+
+    // Arquivo zerado (ex.: queda de energia) não é confundido com banco cifrado
+    fs::write(&path, vec![0; 8192]).unwrap();
+    assert!(!db::is_encrypted(&path));
+    assert!(db::open(&path, None).await.is_err());
+}
+
+#[tokio::test]
+async fn restoring_a_backup_keeps_the_damaged_file_aside() {
+    let dir = scratch_dir("restore");
+    let backups = db::backup_dir(&dir);
+    fs::create_dir_all(&backups).unwrap();
+
+    // Backup válido, com um cliente cadastrado
+    let good = backups.join("aquagas-20240301.db");
+    {
+        let conn = db::open(&good, None).await.unwrap();
+        db::seed_demo_data(&conn).await.unwrap();
+        conn.close().await.unwrap();
+    }
+
+    let database = db::database_path(&dir);
+    fs::write(&database, b"corrompido").unwrap();
+    assert!(db::open(&database, None).await.is_err());
+
+    let listed = backup::list_backups(&backups);
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, "aquagas-20240301.db");
+
+    let previous = startup::restore_backup(&database, &backups, &listed[0].name, None).await.unwrap().unwrap();
+    assert_eq!(fs::read(&previous).unwrap(), b"corrompido");
+
+    let conn = db::open(&database, None).await.unwrap();
+    assert!(!db::seed_demo_data(&conn).await.unwrap(), "o backup já tinha clientes");
+    conn.close().await.unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn backup_names_cannot_escape_the_folder() {
+    let dir = scratch_dir("names");
+    let backups = db::backup_dir(&dir);
+    fs::create_dir_all(&backups).unwrap();
+    fs::write(dir.join("outro.db"), b"").unwrap();
+
+    assert!(startup::backup_file(&backups, "../outro.db").is_err());
+    assert!(startup::backup_file(&backups, "inexistente.db").is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
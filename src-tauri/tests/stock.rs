@@ -0,0 +1,230 @@
+//! Movimentações de estoque, estoque mínimo por produto e sugestões de compra.
+
+mod common;
+
+use app_lib::db::entities::{product, sale, stock_movement};
+use app_lib::error::ErrorCode;
+use app_lib::products::{self, ProductInput};
+use app_lib::stock::StockReason;
+use app_lib::{reports, sales, stock};
+use chrono::{Duration, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+
+async fn product_with(
+    db: &DatabaseConnection,
+    name: &str,
+    stock_quantity: i32,
+    min_stock: i32,
+    reorder_qty: i32,
+) -> product::Model {
+    products::create_product(
+        db,
+        ProductInput {
+            name: name.into(),
+            price: 10.0,
+            stock_quantity,
+            category: "Gás".into(),
+            category_id: Some(2),
+            min_stock: Some(min_stock),
+            reorder_qty: Some(reorder_qty),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn low_stock_uses_each_product_minimum() {
+    let db = common::memory_db().await;
+    let gas = product_with(&db, "Gás P13", 12, 15, 0).await;
+    product_with(&db, "Abraçadeira", 3, 2, 0).await;
+    // Sem mínimo informado, vale o padrão de 10
+    let water = common::product(&db, "Água 20L", 12.0, 9).await;
+    assert_eq!(water.min_stock, stock::DEFAULT_MIN_STOCK);
+
+    let low: Vec<_> = stock::low_stock_products(&db).await.unwrap().into_iter().map(|p| p.id).collect();
+    assert_eq!(low, [water.id, gas.id]);
+    assert_eq!(reports::dashboard_stats_at(&db, Utc::now()).await.unwrap().alerts, 2);
+
+    // A edição sem os campos mantém os valores atuais
+    let input = ProductInput {
+        name: "Gás P13".into(),
+        price: 10.0,
+        category: "Gás".into(),
+        category_id: Some(2),
+        ..Default::default()
+    };
+    let gas = products::update_product(&db, gas.id, input).await.unwrap();
+    assert_eq!((gas.min_stock, gas.reorder_qty), (15, 0));
+    assert_eq!(stock::low_stock_products(&db).await.unwrap().len(), 2);
+
+    let err = products::create_product(&db, ProductInput { min_stock: Some(-1), ..Default::default() })
+        .await
+        .unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("min_stock")));
+}
+
+#[tokio::test]
+async fn reorder_suggestions_follow_average_daily_sales() {
+    let db = common::memory_db().await;
+    let now = Utc::now();
+    let maria = common::client(&db, "Maria").await;
+    let water = product_with(&db, "Água 20L", 100, 10, 12).await;
+    let gas = product_with(&db, "Gás P13", 12, 15, 0).await;
+    product_with(&db, "Abraçadeira", 10, 2, 0).await;
+
+    // 60 galões em 30 dias: 2 por dia, restando 40
+    common::sale(&db, &maria, vec![common::item(&water, 45.0)], "PIX").await;
+    common::sale(&db, &maria, vec![common::item(&water, 15.0)], "Dinheiro").await;
+    // Venda antiga, sem o id do produto, associada pelo nome
+    common::sale(&db, &maria, vec![serde_json::json!({ "name": "abraçadeira", "qty": 3.0, "price": 1.0 })], "PIX").await;
+    sale::ActiveModel {
+        client_id: Set(maria.id),
+        items: Set(serde_json::json!([{ "name": "Abraçadeira", "qty": 100.0, "price": 1.0 }]).to_string()),
+        total: Set(100.0),
+        payment_method: Set("PIX".into()),
+        created_at: Set(now - Duration::days(45)),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let suggestions = stock::reorder_suggestions(&db, 30, now).await.unwrap();
+    let summary: Vec<_> = suggestions
+        .iter()
+        .map(|s| (s.product.name.as_str(), s.sold_qty, s.days_of_stock, s.suggested_qty))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            // 10 de mínimo + 60 previstos - 40 em estoque = 30, em lotes de 12
+            ("Água 20L", 60.0, Some(20.0), 36),
+            // Sem vendas: só completa o mínimo
+            ("Gás P13", 0.0, None, 3),
+        ]
+    );
+    assert_eq!(suggestions[0].average_daily_sales, 2.0);
+    assert_eq!(suggestions[1].product.id, gas.id);
+
+    let err = stock::reorder_suggestions(&db, 0, now).await.unwrap_err();
+    assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some("days")));
+}
+
+fn reasons(history: &[stock_movement::Model]) -> Vec<(&str, i32)> {
+    history.iter().map(|m| (m.reason.as_str(), m.delta)).collect()
+}
+
+#[tokio::test]
+async fn sales_move_stock_through_the_ledger() {
+    let db = common::memory_db().await;
+    let maria = common::client(&db, "Maria").await;
+    let water = common::product(&db, "Água 20L", 12.0, 30).await;
+    let gas = common::product(&db, "Gás P13", 110.0, 8).await;
+
+    let sale = common::sale(&db, &maria, vec![common::item(&water, 3.0), common::item(&gas, 1.0)], "PIX").await;
+    let input = sales::SaleInput {
+        client_id: maria.id,
+        items: serde_json::json!([common::item(&water, 5.0)]),
+        total: 60.0,
+        payment_method: "PIX".into(),
+    };
+    sales::update_sale(&db, sale.id, input).await.unwrap();
+    let stock_of = |id| {
+        let db = &db;
+        async move { product::Entity::find_by_id(id).one(db).await.unwrap().unwrap().stock_quantity }
+    };
+    assert_eq!((stock_of(water.id).await, stock_of(gas.id).await), (25, 8));
+
+    sales::delete_sale(&db, sale.id).await.unwrap();
+    assert_eq!(stock_of(water.id).await, 30);
+
+    let history = stock::stock_history(&db, water.id).await.unwrap();
+    assert_eq!(reasons(&history), [("return", 5), ("sale", -2), ("sale", -3), ("adjustment", 30)]);
+    assert!(history[..3].iter().all(|m| m.reference_id == Some(sale.id)));
+    assert_eq!(reasons(&stock::stock_history(&db, gas.id).await.unwrap()), [("sale", 1), ("sale", -1), ("adjustment", 8)]);
+    assert!(stock::check_consistency(&db).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn manual_adjustments_are_validated_and_attributed() {
+    let db = common::memory_db().await;
+    let gas = common::product(&db, "Gás P13", 110.0, 8).await;
+
+    let gas = stock::adjust_stock(&db, gas.id, -2, StockReason::Loss, Some(" Vazamento ".into()), "admin")
+        .await
+        .unwrap();
+    assert_eq!(gas.stock_quantity, 6);
+    let history = stock::stock_history(&db, gas.id).await.unwrap();
+    assert_eq!(history[0].username.as_deref(), Some("admin"));
+    assert_eq!(history[0].note.as_deref(), Some("Vazamento"));
+
+    for (delta, reason, field) in [
+        (2, StockReason::Loss, "delta"),
+        (-1, StockReason::Return, "delta"),
+        (0, StockReason::Adjustment, "delta"),
+        (5, StockReason::Purchase, "reason"),
+    ] {
+        let err = stock::adjust_stock(&db, gas.id, delta, reason, None, "admin").await.unwrap_err();
+        assert_eq!((err.code(), err.field()), (ErrorCode::Validation, Some(field)));
+    }
+    let err = stock::adjust_stock(&db, 999, 1, StockReason::Adjustment, None, "admin").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    // A edição do produto não mexe no estoque, mesmo com outro valor no
+    // formulário (aberto antes de uma venda, por exemplo)
+    let input = ProductInput {
+        name: "Gás P13".into(),
+        price: 110.0,
+        stock_quantity: 10,
+        category: "Água".into(),
+        category_id: Some(1),
+        ..Default::default()
+    };
+    let gas = products::update_product(&db, gas.id, input).await.unwrap();
+    assert_eq!(gas.stock_quantity, 6);
+    let history = stock::stock_history(&db, gas.id).await.unwrap();
+    assert_eq!(reasons(&history), [("loss", -2), ("adjustment", 8)]);
+}
+
+#[tokio::test]
+async fn consistency_check_recomputes_stock_from_movements() {
+    let db = common::memory_db().await;
+    let water = common::product(&db, "Água 20L", 12.0, 30).await;
+    // Produto gravado sem passar pelo histórico, como os anteriores a ele
+    let legacy = product::ActiveModel {
+        name: Set("Registro".into()),
+        price: Set(25.0),
+        stock_quantity: Set(4),
+        category: Set("Acessórios".into()),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+    product::Entity::update_many()
+        .col_expr(product::Column::StockQuantity, Expr::value(33))
+        .filter(product::Column::Id.eq(water.id))
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let found: Vec<_> = stock::check_consistency(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.name, d.stock_quantity, d.movements_total))
+        .collect();
+    assert_eq!(found, [("Registro".to_string(), 4, 0), ("Água 20L".to_string(), 33, 30)]);
+
+    // O saldo inicial cobre o produto sem histórico; o outro é recalculado
+    stock::record_opening_balances(&db).await.unwrap();
+    let fixed = stock::recompute_stock(&db).await.unwrap();
+    assert_eq!(fixed.len(), 1);
+    assert_eq!(product::Entity::find_by_id(water.id).one(&db).await.unwrap().unwrap().stock_quantity, 30);
+    let history = stock::stock_history(&db, legacy.id).await.unwrap();
+    assert_eq!(history[0].note.as_deref(), Some(stock::OPENING_BALANCE_NOTE));
+    assert!(stock::check_consistency(&db).await.unwrap().is_empty());
+}
@@ -0,0 +1,56 @@
+//! Login e cadastro de usuários.
+
+mod common;
+
+use app_lib::error::ErrorCode;
+use app_lib::users;
+
+#[tokio::test]
+async fn seeded_admin_can_log_in() {
+    let db = common::memory_db().await;
+
+    let admin = users::login(&db, "admin", "admin123").await.unwrap();
+    assert_eq!(admin.role, "admin");
+}
+
+#[tokio::test]
+async fn wrong_credentials_are_unauthorized() {
+    let db = common::memory_db().await;
+
+    let err = users::login(&db, "admin", "errada").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    assert_eq!(err.to_string(), "Senha incorreta");
+
+    let err = users::login(&db, "ninguem", "admin123").await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn password_is_kept_when_update_leaves_it_blank() {
+    let db = common::memory_db().await;
+    let user = users::create_user(&db, "maria".into(), "segredo", "Maria".into(), "user".into())
+        .await
+        .unwrap();
+    assert_ne!(user.password_hash, "segredo");
+
+    users::update_user(&db, user.id, "maria".into(), Some(""), "Maria Silva".into(), "user".into())
+        .await
+        .unwrap();
+    assert_eq!(users::login(&db, "maria", "segredo").await.unwrap().name, "Maria Silva");
+
+    users::update_user(&db, user.id, "maria".into(), Some("nova"), "Maria Silva".into(), "user".into())
+        .await
+        .unwrap();
+    assert!(users::login(&db, "maria", "segredo").await.is_err());
+    assert!(users::login(&db, "maria", "nova").await.is_ok());
+}
+
+#[tokio::test]
+async fn duplicate_username_is_a_conflict() {
+    let db = common::memory_db().await;
+    let err = users::create_user(&db, "admin".into(), "x", "Outro".into(), "user".into())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert_eq!(err.field(), Some("username"));
+}
@@ -9,12 +9,14 @@ import NovaVenda from "./pages/NovaVenda";
 import Clientes from "./pages/Clientes";
 import Relatorios from "./pages/Relatorios";
 import Produtos from "./pages/Produtos";
+import Compras from "./pages/Compras";
 import Vendas from "./pages/Vendas";
 import Configuracoes from "./pages/Configuracoes";
 import Login from "./pages/Login";
 import NotFound from "./pages/NotFound";
 import { ThemeProvider } from "@/components/theme-provider";
 import { UpdateChecker } from "@/components/UpdateChecker";
+import { StartupGate } from "@/components/StartupGate";
 
 const queryClient = new QueryClient();
 
@@ -31,29 +33,32 @@ const App = () => (
         <Toaster />
         <Sonner />
         <UpdateChecker />
-        <BrowserRouter>
-          <Routes>
-            <Route path="/login" element={<Login />} />
-            <Route
-              path="/*"
-              element={
-                <ProtectedRoute>
-                  <AppLayout>
-                    <Routes>
-                      <Route path="/" element={<Dashboard />} />
-                      <Route path="/vendas" element={<Vendas />} />
-                      <Route path="/clientes" element={<Clientes />} />
-                      <Route path="/produtos" element={<Produtos />} />
-                      <Route path="/configuracoes" element={<Configuracoes />} />
-                      <Route path="/relatorios" element={<Relatorios />} />
-                      <Route path="*" element={<Navigate to="/" replace />} />
-                    </Routes>
-                  </AppLayout>
-                </ProtectedRoute>
-              }
-            />
-          </Routes>
-        </BrowserRouter>
+        <StartupGate>
+          <BrowserRouter>
+            <Routes>
+              <Route path="/login" element={<Login />} />
+              <Route
+                path="/*"
+                element={
+                  <ProtectedRoute>
+                    <AppLayout>
+                      <Routes>
+                        <Route path="/" element={<Dashboard />} />
+                        <Route path="/vendas" element={<Vendas />} />
+                        <Route path="/clientes" element={<Clientes />} />
+                        <Route path="/produtos" element={<Produtos />} />
+                        <Route path="/compras" element={<Compras />} />
+                        <Route path="/configuracoes" element={<Configuracoes />} />
+                        <Route path="/relatorios" element={<Relatorios />} />
+                        <Route path="*" element={<Navigate to="/" replace />} />
+                      </Routes>
+                    </AppLayout>
+                  </ProtectedRoute>
+                }
+              />
+            </Routes>
+          </BrowserRouter>
+        </StartupGate>
       </TooltipProvider>
     </QueryClientProvider>
   </ThemeProvider>
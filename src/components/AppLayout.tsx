@@ -17,6 +17,7 @@ import {
   Sun,
   Moon,
   Tags,
+  Truck,
 } from "lucide-react";
 import { getVersion } from "@tauri-apps/api/app";
 import { getCurrentWindow } from "@tauri-apps/api/window";
@@ -31,6 +32,7 @@ const navItems = [
   { label: "Vendas", icon: Tags, path: "/vendas" },
   { label: "Clientes", icon: Users, path: "/clientes" },
   { label: "Produtos", icon: Package, path: "/produtos" },
+  { label: "Compras", icon: Truck, path: "/compras" },
   { label: "Relatórios", icon: BarChart3, path: "/relatorios" },
   { label: "Configurações", icon: Settings, path: "/configuracoes" },
 ];
@@ -0,0 +1,244 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Upload } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from "@/components/ui/dialog";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { toast } from "@/components/ui/sonner";
+import type { ImportKind, ImportPreview, ImportResult } from "@/lib/bindings";
+import { fileToBase64 } from "@/lib/utils/download";
+import { errorMessage } from "@/lib/utils/errors";
+
+type Mapping = ImportPreview["mapping"];
+
+const FIELD_LABELS: Record<ImportKind, Record<string, string>> = {
+  clients: {
+    name: "Nome *",
+    phone: "Telefone",
+    document: "CPF / CNPJ",
+    cep: "CEP",
+    city: "Cidade",
+    state: "UF",
+    street: "Rua",
+    number: "Número",
+    complement: "Complemento",
+    neighborhood: "Bairro",
+    observations: "Observações",
+  },
+  products: {
+    name: "Nome *",
+    price: "Preço *",
+    stock_quantity: "Estoque",
+    category: "Categoria *",
+    min_stock: "Estoque mínimo",
+    reorder_qty: "Lote de compra",
+    cost_price: "Custo",
+  },
+};
+
+const TITLES: Record<ImportKind, string> = {
+  clients: "Importar Clientes",
+  products: "Importar Produtos",
+};
+
+const NO_COLUMN = "none";
+
+interface ImportDialogProps {
+  kind: ImportKind;
+  onImported: () => void;
+}
+
+/** Importação por planilha: escolha do arquivo, mapeamento das colunas, prévia e gravação. */
+export const ImportDialog = ({ kind, onImported }: ImportDialogProps) => {
+  const [isOpen, setIsOpen] = useState(false);
+  const [upload, setUpload] = useState<{ format: string; content: string } | null>(null);
+  const [preview, setPreview] = useState<ImportPreview | null>(null);
+  const [isWorking, setIsWorking] = useState(false);
+
+  const reset = () => {
+    setUpload(null);
+    setPreview(null);
+  };
+
+  const loadPreview = async (file: { format: string; content: string }, mapping: Mapping | null) => {
+    setIsWorking(true);
+    try {
+      const data = await invoke<ImportPreview>("preview_import", { kind, ...file, mapping });
+      setPreview(data);
+    } catch (error) {
+      toast.error(errorMessage(error));
+    } finally {
+      setIsWorking(false);
+    }
+  };
+
+  const handleFile = async (file: File | undefined) => {
+    if (!file) return;
+    const format = file.name.toLowerCase().endsWith(".xlsx") ? "xlsx" : "csv";
+    const selected = { format, content: await fileToBase64(file) };
+    setUpload(selected);
+    await loadPreview(selected, null);
+  };
+
+  const handleMappingChange = (field: string, value: string) => {
+    if (!upload || !preview) return;
+    const mapping: Mapping = { ...preview.mapping };
+    if (value === NO_COLUMN) {
+      delete mapping[field];
+    } else {
+      mapping[field] = Number(value);
+    }
+    loadPreview(upload, mapping);
+  };
+
+  const handleCommit = async () => {
+    if (!upload || !preview) return;
+    setIsWorking(true);
+    try {
+      const result = await invoke<ImportResult>("commit_import", { kind, ...upload, mapping: preview.mapping });
+      toast.success(
+        `${result.imported} registro(s) importado(s)` +
+          (result.rejected.length > 0 ? `, ${result.rejected.length} linha(s) ignorada(s)` : "")
+      );
+      setIsOpen(false);
+      reset();
+      onImported();
+    } catch (error) {
+      toast.error(errorMessage(error));
+    } finally {
+      setIsWorking(false);
+    }
+  };
+
+  const labels = FIELD_LABELS[kind];
+  const fields = Object.keys(labels);
+
+  return (
+    <Dialog open={isOpen} onOpenChange={(open) => {
+      setIsOpen(open);
+      if (!open) reset();
+    }}>
+      <DialogTrigger asChild>
+        <Button variant="outline" className="gap-2">
+          <Upload className="h-4 w-4" />
+          Importar
+        </Button>
+      </DialogTrigger>
+      <DialogContent className="max-w-4xl max-h-[90vh] overflow-y-auto">
+        <DialogHeader>
+          <DialogTitle>{TITLES[kind]}</DialogTitle>
+          <DialogDescription>
+            Envie uma planilha CSV ou XLSX com cabeçalho na primeira linha. Linhas com erro ou já cadastradas são ignoradas.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-2">
+          <Label htmlFor="import-file">Arquivo</Label>
+          <Input
+            id="import-file"
+            type="file"
+            accept=".csv,.xlsx"
+            onChange={(e) => handleFile(e.target.files?.[0])}
+          />
+        </div>
+
+        {preview && (
+          <>
+            <div className="space-y-2">
+              <h3 className="text-sm font-semibold">Colunas</h3>
+              <div className="grid grid-cols-2 md:grid-cols-3 gap-3">
+                {fields.map((field) => (
+                  <div key={field} className="space-y-1">
+                    <Label className="text-xs text-muted-foreground">{labels[field]}</Label>
+                    <Select
+                      value={preview.mapping[field] !== undefined ? String(preview.mapping[field]) : NO_COLUMN}
+                      onValueChange={(value) => handleMappingChange(field, value)}
+                      disabled={isWorking}
+                    >
+                      <SelectTrigger className="h-8 text-xs">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value={NO_COLUMN}>Não importar</SelectItem>
+                        {preview.headers.map((header, i) => (
+                          <SelectItem key={i} value={String(i)}>{header || `Coluna ${i + 1}`}</SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                  </div>
+                ))}
+              </div>
+            </div>
+
+            {preview.missing_fields.length > 0 ? (
+              <p className="text-sm text-destructive">
+                Selecione a coluna de: {preview.missing_fields.map((f) => labels[f] ?? f).join(", ")}
+              </p>
+            ) : (
+              <div className="space-y-2">
+                <p className="text-sm">
+                  <span className="font-semibold text-primary">{preview.valid_rows}</span> linha(s) prontas para importar
+                  {preview.invalid_rows > 0 && (
+                    <>, <span className="font-semibold text-destructive">{preview.invalid_rows}</span> com erro</>
+                  )}
+                </p>
+                <div className="rounded-lg border border-border/60 overflow-hidden max-h-72 overflow-y-auto">
+                  <table className="w-full">
+                    <thead>
+                      <tr className="bg-muted/50 text-left">
+                        <th className="px-3 py-2 text-xs font-semibold text-muted-foreground">Linha</th>
+                        <th className="px-3 py-2 text-xs font-semibold text-muted-foreground">Nome</th>
+                        <th className="px-3 py-2 text-xs font-semibold text-muted-foreground">Situação</th>
+                      </tr>
+                    </thead>
+                    <tbody className="divide-y divide-border">
+                      {preview.rows.map((row) => (
+                        <tr key={row.line}>
+                          <td className="px-3 py-1.5 text-xs text-muted-foreground">{row.line}</td>
+                          <td className="px-3 py-1.5 text-sm">{row.values.name ?? "-"}</td>
+                          <td className="px-3 py-1.5 text-xs">
+                            {row.errors.length === 0 ? (
+                              <span className="text-primary">OK</span>
+                            ) : (
+                              <span className="text-destructive">{row.errors.map((e) => e.message).join("; ")}</span>
+                            )}
+                          </td>
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                </div>
+              </div>
+            )}
+          </>
+        )}
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => setIsOpen(false)}>Cancelar</Button>
+          <Button
+            onClick={handleCommit}
+            disabled={!preview || isWorking || preview.missing_fields.length > 0 || preview.valid_rows === 0}
+          >
+            {isWorking ? "Processando..." : `Importar ${preview?.valid_rows ?? 0} linha(s)`}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
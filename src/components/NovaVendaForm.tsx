@@ -165,6 +165,7 @@ export const NovaVendaForm = ({ onSuccess }: NovaVendaFormProps) => {
 
         try {
             const itemsPayload = cart.map(i => ({
+                id: i.productId,
                 name: i.name,
                 qty: i.qty,
                 price: i.price
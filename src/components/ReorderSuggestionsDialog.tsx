@@ -0,0 +1,119 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { ShoppingCart } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from "@/components/ui/dialog";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { toast } from "@/components/ui/sonner";
+import type { ReorderSuggestion } from "@/lib/bindings";
+import { errorMessage } from "@/lib/utils/errors";
+import { formatNumber } from "@/lib/utils/format";
+
+const PERIODS = [7, 15, 30, 60, 90];
+
+/** Produtos a comprar pela média de vendas do período escolhido. */
+export const ReorderSuggestionsDialog = () => {
+  const [isOpen, setIsOpen] = useState(false);
+  const [days, setDays] = useState(30);
+  const [suggestions, setSuggestions] = useState<ReorderSuggestion[] | null>(null);
+
+  const load = async (period: number) => {
+    try {
+      setSuggestions(await invoke<ReorderSuggestion[]>("get_reorder_suggestions", { days: period }));
+    } catch (error) {
+      toast.error(errorMessage(error));
+    }
+  };
+
+  const handleOpenChange = (open: boolean) => {
+    setIsOpen(open);
+    if (open) load(days);
+  };
+
+  const handlePeriodChange = (value: string) => {
+    const period = Number(value);
+    setDays(period);
+    load(period);
+  };
+
+  return (
+    <Dialog open={isOpen} onOpenChange={handleOpenChange}>
+      <DialogTrigger asChild>
+        <Button variant="outline" className="gap-2">
+          <ShoppingCart className="h-4 w-4" />
+          Reposição
+        </Button>
+      </DialogTrigger>
+      <DialogContent className="max-w-3xl max-h-[90vh] overflow-y-auto">
+        <DialogHeader>
+          <DialogTitle>Sugestões de Compra</DialogTitle>
+          <DialogDescription>
+            Quantidade para cobrir as vendas previstas e manter o estoque mínimo, arredondada pelo lote de compra.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="flex items-center gap-2 text-sm">
+          <span className="text-muted-foreground">Média de vendas dos últimos</span>
+          <Select value={String(days)} onValueChange={handlePeriodChange}>
+            <SelectTrigger className="h-8 w-24">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {PERIODS.map((period) => (
+                <SelectItem key={period} value={String(period)}>{period} dias</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+
+        {suggestions && suggestions.length === 0 ? (
+          <p className="py-6 text-center text-sm text-muted-foreground">Nenhum produto precisa de reposição no período.</p>
+        ) : (
+          <div className="rounded-lg border border-border/60 overflow-hidden">
+            <table className="w-full">
+              <thead>
+                <tr className="bg-muted/50 text-left text-xs font-semibold text-muted-foreground">
+                  <th className="px-3 py-2">Produto</th>
+                  <th className="px-3 py-2 text-right">Estoque</th>
+                  <th className="px-3 py-2 text-right">Mínimo</th>
+                  <th className="px-3 py-2 text-right">Vendas/dia</th>
+                  <th className="px-3 py-2 text-right">Dura (dias)</th>
+                  <th className="px-3 py-2 text-right">Comprar</th>
+                </tr>
+              </thead>
+              <tbody className="divide-y divide-border">
+                {suggestions?.map((s) => (
+                  <tr key={s.product.id}>
+                    <td className="px-3 py-1.5 text-sm">{s.product.name}</td>
+                    <td className={`px-3 py-1.5 text-sm text-right ${s.product.stock_quantity < s.product.min_stock ? "text-destructive font-semibold" : ""}`}>
+                      {s.product.stock_quantity}
+                    </td>
+                    <td className="px-3 py-1.5 text-sm text-right">{s.product.min_stock}</td>
+                    <td className="px-3 py-1.5 text-sm text-right">{formatNumber(s.average_daily_sales, 1)}</td>
+                    <td className="px-3 py-1.5 text-sm text-right">
+                      {s.days_of_stock !== null ? formatNumber(s.days_of_stock) : "-"}
+                    </td>
+                    <td className="px-3 py-1.5 text-sm text-right font-semibold text-primary">{s.suggested_qty}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        )}
+      </DialogContent>
+    </Dialog>
+  );
+};
@@ -0,0 +1,215 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { AlertTriangle, FolderOpen, Loader2, Lock, RefreshCw, RotateCcw } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { toast } from "@/components/ui/sonner";
+import { useConfirm } from "@/hooks/use-confirm";
+import type { StartupStatus } from "@/lib/bindings";
+import { errorMessage } from "@/lib/utils/errors";
+import { formatNumber } from "@/lib/utils/format";
+
+const formatSize = (bytes: number) =>
+    bytes >= 1024 * 1024
+        ? `${formatNumber(bytes / (1024 * 1024), 1)} MB`
+        : `${formatNumber(bytes / 1024, 0)} KB`;
+
+/**
+ * Só libera o app depois que o banco de dados abriu. Se a abertura falhar,
+ * mostra o erro e as ações de recuperação em vez de seguir sem banco.
+ */
+export function StartupGate({ children }: { children: React.ReactNode }) {
+    const [status, setStatus] = useState<StartupStatus | null>(null);
+    const [busy, setBusy] = useState<string | null>(null);
+    const [passphrase, setPassphrase] = useState("");
+    const { confirm, ConfirmDialog } = useConfirm();
+
+    useEffect(() => {
+        invoke<StartupStatus>("get_startup_status")
+            .then(setStatus)
+            .catch((err) =>
+                setStatus({ state: "failed", error: errorMessage(err), data_dir: "", backups: [] })
+            );
+    }, []);
+
+    const run = async (action: string, command: string, args?: Record<string, unknown>) => {
+        setBusy(action);
+        try {
+            const next = await invoke<StartupStatus>(command, args);
+            setStatus(next);
+            if (next.state === "ready") toast.success("Banco de dados aberto com sucesso");
+        } catch (err) {
+            toast.error(errorMessage(err));
+        } finally {
+            setBusy(null);
+        }
+    };
+
+    const handleRestore = async (name: string) => {
+        const ok = await confirm(
+            "Restaurar backup",
+            `O banco atual será substituído por "${name}". O arquivo atual é mantido na pasta de dados com o sufixo ".falha". Deseja continuar?`
+        );
+        if (!ok) return;
+        // Com o banco cifrado, só backups cifrados com a senha digitada
+        const locked = status?.state === "locked";
+        await run(name, "restore_startup_backup", { name, passphrase: locked ? passphrase : null });
+    };
+
+    const handleOpenFolder = async () => {
+        try {
+            await invoke("open_data_folder");
+        } catch (err) {
+            toast.error(errorMessage(err));
+        }
+    };
+
+    if (!status) {
+        return (
+            <div className="flex min-h-screen items-center justify-center bg-background">
+                <Loader2 className="h-8 w-8 animate-spin text-muted-foreground" />
+            </div>
+        );
+    }
+
+    if (status.state === "ready") return <>{children}</>;
+
+    const openFolderButton = (
+        <Button variant="outline" onClick={handleOpenFolder} disabled={busy !== null}>
+            <FolderOpen className="mr-2 h-4 w-4" />
+            Abrir pasta de dados
+        </Button>
+    );
+
+    const backupList = (
+        <div className="space-y-2">
+            <h3 className="text-sm font-semibold">Backups disponíveis</h3>
+            {status.backups.length === 0 ? (
+                <p className="text-sm text-muted-foreground">Nenhum backup encontrado na pasta de dados.</p>
+            ) : (
+                <ul className="divide-y rounded-lg border">
+                    {status.backups.map((backup) => (
+                        <li key={backup.name} className="flex items-center justify-between gap-3 p-3">
+                            <div className="min-w-0">
+                                <p className="truncate text-sm font-medium">{backup.name}</p>
+                                <p className="text-xs text-muted-foreground">
+                                    {new Date(backup.created_at).toLocaleString("pt-BR")} • {formatSize(backup.size)}
+                                </p>
+                            </div>
+                            <Button
+                                size="sm"
+                                variant="secondary"
+                                onClick={() => handleRestore(backup.name)}
+                                disabled={busy !== null || (status.state === "locked" && !passphrase)}
+                            >
+                                {busy === backup.name ? (
+                                    <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                                ) : (
+                                    <RotateCcw className="mr-2 h-4 w-4" />
+                                )}
+                                Restaurar
+                            </Button>
+                        </li>
+                    ))}
+                </ul>
+            )}
+        </div>
+    );
+
+    if (status.state === "locked") {
+        return (
+            <div className="flex min-h-screen items-center justify-center bg-background p-4">
+                <ConfirmDialog />
+                <Card className="w-full max-w-md card-shadow">
+                    <CardHeader className="space-y-2">
+                        <CardTitle className="flex items-center gap-2">
+                            <Lock className="h-5 w-5 text-primary" />
+                            Banco de dados protegido
+                        </CardTitle>
+                        <CardDescription>
+                            Os dados estão criptografados. Informe a senha do banco definida pelo administrador.
+                        </CardDescription>
+                    </CardHeader>
+                    <CardContent className="space-y-5">
+                        <form
+                            className="space-y-4"
+                            onSubmit={(e) => {
+                                e.preventDefault();
+                                run("unlock", "unlock_database", { passphrase });
+                            }}
+                        >
+                            <div className="space-y-2">
+                                <Label htmlFor="passphrase">Senha do banco</Label>
+                                <Input
+                                    id="passphrase"
+                                    type="password"
+                                    autoFocus
+                                    value={passphrase}
+                                    onChange={(e) => setPassphrase(e.target.value)}
+                                />
+                            </div>
+                            {status.error && <p className="text-sm text-destructive">{status.error}</p>}
+                            <Button type="submit" className="w-full" disabled={busy !== null || !passphrase}>
+                                {busy === "unlock" && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+                                Desbloquear
+                            </Button>
+                        </form>
+                        <div className="space-y-3 border-t pt-5">
+                            <p className="text-xs text-muted-foreground">
+                                Se a senha estiver correta e o banco mesmo assim não abrir, o arquivo pode estar danificado:
+                                restaure um backup criptografado com a mesma senha ou verifique os arquivos na pasta de dados.
+                            </p>
+                            {openFolderButton}
+                            {backupList}
+                        </div>
+                    </CardContent>
+                </Card>
+            </div>
+        );
+    }
+
+    return (
+        <div className="flex min-h-screen items-center justify-center bg-background p-4">
+            <ConfirmDialog />
+            <Card className="w-full max-w-2xl card-shadow border-destructive/40">
+                <CardHeader className="space-y-2">
+                    <CardTitle className="flex items-center gap-2 text-destructive">
+                        <AlertTriangle className="h-5 w-5" />
+                        Não foi possível abrir o banco de dados
+                    </CardTitle>
+                    <CardDescription>
+                        O sistema não pode ser usado sem o banco. Tente novamente, restaure um backup ou verifique os arquivos na pasta de dados.
+                    </CardDescription>
+                </CardHeader>
+                <CardContent className="space-y-5">
+                    {status.error && (
+                        <pre className="whitespace-pre-wrap break-words rounded-lg bg-destructive/10 p-3 text-xs text-destructive">
+                            {status.error}
+                        </pre>
+                    )}
+                    {status.data_dir && (
+                        <p className="text-xs text-muted-foreground">
+                            Pasta de dados: <span className="font-mono">{status.data_dir}</span>
+                        </p>
+                    )}
+
+                    <div className="flex flex-wrap gap-2">
+                        <Button onClick={() => run("retry", "retry_startup")} disabled={busy !== null}>
+                            {busy === "retry" ? (
+                                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                            ) : (
+                                <RefreshCw className="mr-2 h-4 w-4" />
+                            )}
+                            Tentar novamente
+                        </Button>
+                        {openFolderButton}
+                    </div>
+
+                    {backupList}
+                </CardContent>
+            </Card>
+        </div>
+    );
+}
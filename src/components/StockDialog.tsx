@@ -0,0 +1,158 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { toast } from "@/components/ui/sonner";
+import type { StockMovement, StockReason } from "@/lib/bindings";
+import { errorMessage } from "@/lib/utils/errors";
+
+export const REASON_LABELS: Record<StockReason, string> = {
+  sale: "Venda",
+  purchase: "Compra",
+  adjustment: "Ajuste",
+  loss: "Perda",
+  return: "Devolução",
+};
+
+const MANUAL_REASONS: StockReason[] = ["adjustment", "loss", "return"];
+
+interface StockDialogProps {
+  product: { id: number; name: string; stock_quantity: number } | null;
+  onOpenChange: (open: boolean) => void;
+  onAdjusted: () => void;
+}
+
+/** Histórico de movimentações do produto e ajuste manual do estoque. */
+export const StockDialog = ({ product, onOpenChange, onAdjusted }: StockDialogProps) => {
+  const [history, setHistory] = useState<StockMovement[]>([]);
+  const [stock, setStock] = useState(0);
+  const [delta, setDelta] = useState("");
+  const [reason, setReason] = useState<StockReason>("adjustment");
+  const [note, setNote] = useState("");
+  const [isSaving, setIsSaving] = useState(false);
+
+  const loadHistory = async (productId: number) => {
+    try {
+      setHistory(await invoke<StockMovement[]>("get_stock_history", { productId }));
+    } catch (error) {
+      toast.error(errorMessage(error));
+    }
+  };
+
+  useEffect(() => {
+    if (!product) return;
+    setStock(product.stock_quantity);
+    setDelta("");
+    setReason("adjustment");
+    setNote("");
+    loadHistory(product.id);
+  }, [product]);
+
+  const handleAdjust = async () => {
+    if (!product) return;
+    const user = JSON.parse(localStorage.getItem("user") || "{}");
+    const quantity = parseInt(delta);
+    setIsSaving(true);
+    try {
+      const updated = await invoke<{ stock_quantity: number }>("adjust_stock", {
+        productId: product.id,
+        delta: reason === "loss" ? -Math.abs(quantity) : reason === "return" ? Math.abs(quantity) : quantity,
+        reason,
+        note: note || null,
+        username: user.username,
+      });
+      setStock(updated.stock_quantity);
+      setDelta("");
+      setNote("");
+      loadHistory(product.id);
+      onAdjusted();
+      toast.success("Estoque ajustado!");
+    } catch (error) {
+      toast.error(errorMessage(error));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <Dialog open={product !== null} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl max-h-[90vh] overflow-y-auto">
+        <DialogHeader>
+          <DialogTitle>Estoque: {product?.name}</DialogTitle>
+          <DialogDescription>
+            Estoque atual: <span className="font-semibold text-foreground">{stock}</span>. Vendas e compras movimentam o estoque automaticamente.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="grid grid-cols-3 gap-3 items-end">
+          <div className="space-y-1">
+            <Label className="text-xs">Motivo</Label>
+            <Select value={reason} onValueChange={(value) => setReason(value as StockReason)}>
+              <SelectTrigger className="h-9">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {MANUAL_REASONS.map((r) => (
+                  <SelectItem key={r} value={r}>{REASON_LABELS[r]}</SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+          <div className="space-y-1">
+            <Label className="text-xs">{reason === "adjustment" ? "Quantidade (+/-)" : "Quantidade"}</Label>
+            <Input type="number" className="h-9" value={delta} onChange={(e) => setDelta(e.target.value)} />
+          </div>
+          <Button onClick={handleAdjust} disabled={isSaving || !parseInt(delta)}>
+            {isSaving ? "Salvando..." : "Registrar"}
+          </Button>
+          <div className="col-span-3 space-y-1">
+            <Label className="text-xs">Observação</Label>
+            <Input className="h-9" value={note} onChange={(e) => setNote(e.target.value)} placeholder="Ex: contagem do depósito" />
+          </div>
+        </div>
+
+        <div className="rounded-lg border border-border/60 overflow-hidden max-h-72 overflow-y-auto">
+          <table className="w-full">
+            <thead>
+              <tr className="bg-muted/50 text-left text-xs font-semibold text-muted-foreground">
+                <th className="px-3 py-2">Data</th>
+                <th className="px-3 py-2">Motivo</th>
+                <th className="px-3 py-2 text-right">Quantidade</th>
+                <th className="px-3 py-2">Detalhes</th>
+              </tr>
+            </thead>
+            <tbody className="divide-y divide-border">
+              {history.map((m) => (
+                <tr key={m.id}>
+                  <td className="px-3 py-1.5 text-xs text-muted-foreground">{new Date(m.created_at).toLocaleString("pt-BR")}</td>
+                  <td className="px-3 py-1.5 text-sm">{REASON_LABELS[m.reason as StockReason] ?? m.reason}</td>
+                  <td className={`px-3 py-1.5 text-sm text-right font-semibold ${m.delta < 0 ? "text-destructive" : "text-primary"}`}>
+                    {m.delta > 0 ? `+${m.delta}` : m.delta}
+                  </td>
+                  <td className="px-3 py-1.5 text-xs text-muted-foreground">
+                    {[m.reference_id && `#${m.reference_id}`, m.username, m.note].filter(Boolean).join(" • ")}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+};
@@ -0,0 +1,285 @@
+// Arquivo gerado por src-tauri/src/bindings.rs. Não edite manualmente.
+
+export type AuditLog = { id: number, action: string, 
+/**
+ * Tipo do registro afetado, ex.: `client`.
+ */
+entity: string, entity_id: number, 
+/**
+ * Usuário que executou a operação.
+ */
+username: string, details: string | null, created_at: string, };
+
+export type Category = { id: number, name: string, description: string | null, };
+
+export type Client = { id: number, name: string, phone: string | null, document: string | null, cep: string | null, city: string | null, state: string | null, street: string | null, number: string | null, complement: string | null, neighborhood: string | null, address: string | null, observations: string | null, created_at: string, 
+/**
+ * Quando os dados pessoais foram apagados a pedido do titular (LGPD).
+ */
+anonymized_at: string | null, };
+
+export type ClientGallon = { id: number, client_id: number, brand: string, expiration_date: string, };
+
+export type PaymentMethod = { id: number, name: string, };
+
+export type Product = { id: number, name: string, price: number, stock_quantity: number, category: string, category_id: number | null, 
+/**
+ * Abaixo desta quantidade o produto entra nos alertas de estoque.
+ */
+min_stock: number, 
+/**
+ * Lote de compra; as sugestões de reposição são múltiplos dele. 0 sem lote.
+ */
+reorder_qty: number, 
+/**
+ * Custo médio ponderado, atualizado a cada recebimento de compra.
+ */
+cost_price: number, };
+
+export type PurchaseOrder = { id: number, supplier_id: number, 
+/**
+ * `open`, `partial`, `received` ou `cancelled`.
+ */
+status: string, notes: string | null, 
+/**
+ * Usuário que fez o pedido.
+ */
+username: string, created_at: string, 
+/**
+ * Recebimento completo.
+ */
+received_at: string | null, cancelled_at: string | null, };
+
+export type PurchaseOrderItem = { id: number, purchase_order_id: number, product_id: number, quantity: number, received_quantity: number, 
+/**
+ * Custo unitário combinado; atualizado com o custo informado no recebimento.
+ */
+unit_cost: number, };
+
+export type Sale = { id: number, client_id: number, items: string, total: number, payment_method: string, created_at: string, };
+
+export type Setting = { key: string, value: string, };
+
+export type ShippingMethod = { id: number, name: string, fee: number, };
+
+export type StockMovement = { id: number, product_id: number, 
+/**
+ * Quantidade somada ao estoque (negativa nas saídas).
+ */
+delta: number, 
+/**
+ * `sale`, `purchase`, `adjustment`, `loss` ou `return`.
+ */
+reason: string, 
+/**
+ * Venda ou pedido de compra que originou a movimentação.
+ */
+reference_id: number | null, 
+/**
+ * Usuário que registrou; vazio nas movimentações automáticas.
+ */
+username: string | null, note: string | null, created_at: string, };
+
+export type Supplier = { id: number, name: string, 
+/**
+ * CNPJ ou CPF, apenas dígitos.
+ */
+document: string | null, phone: string | null, email: string | null, notes: string | null, created_at: string, };
+
+export type User = { id: number, username: string, password_hash: string, name: string, role: string, };
+
+export type CepAddress = { cep: string, street: string | null, neighborhood: string | null, city: string, state: string, };
+
+export type ArchiveManifest = { format: string, archive_version: number, 
+/**
+ * `db::SCHEMA_VERSION` do app que gerou o pacote.
+ */
+schema_version: number, app_version: string, exported_at: string, 
+/**
+ * Quantidade de registros por tabela.
+ */
+counts: { [key in string]?: number }, };
+
+export type BackupFile = { name: string, 
+/**
+ * `null` para arquivos `.db` copiados para a pasta manualmente.
+ */
+kind: BackupKind | null, created_at: string, size: number, };
+
+export type BackupKind = "auto" | "manual" | "safety";
+
+export type RestoredBackup = { name: string, created_at: string, 
+/**
+ * Versão do esquema do backup, antes das migrações aplicadas ao abrir.
+ */
+schema_version: number, 
+/**
+ * Cópia do banco que foi substituído.
+ */
+safety_backup: BackupFile, };
+
+export type ClientDetails = { client: Client, sales: Array<Sale>, gallons: Array<ClientGallon>, total_orders: number, };
+
+export type EncryptedDatabase = { 
+/**
+ * Backups sem criptografia que foram cifrados junto com o banco.
+ */
+encrypted_backups: Array<string>, 
+/**
+ * Backups que não puderam ser cifrados (ex.: arquivo danificado) e
+ * continuam legíveis na pasta.
+ */
+skipped_backups: Array<string>, };
+
+export type EncryptionStatus = { 
+/**
+ * Se o app foi compilado com o SQLCipher.
+ */
+supported: boolean, encrypted: boolean, };
+
+export type AppError = { code: ErrorCode, message: string, field: string | null, };
+
+export type ErrorCode = "not_found" | "validation" | "conflict" | "unauthorized" | "database" | "internal";
+
+export type ImportIssue = { 
+/**
+ * Campo de destino com problema; `null` quando é a linha toda.
+ */
+field: string | null, message: string, };
+
+export type ImportKind = "clients" | "products";
+
+export type ImportPreview = { headers: Array<string>, 
+/**
+ * Mapeamento usado: o informado ou o sugerido pelos cabeçalhos.
+ */
+mapping: { [key in string]?: number }, 
+/**
+ * Campos obrigatórios sem coluna; enquanto houver, as linhas não são
+ * validadas e `rows` vem vazio.
+ */
+missing_fields: Array<string>, rows: Array<ImportRow>, valid_rows: number, invalid_rows: number, };
+
+export type ImportResult = { imported: number, 
+/**
+ * Linhas que não foram gravadas, com os motivos.
+ */
+rejected: Array<ImportRow>, };
+
+export type ImportRow = { 
+/**
+ * Linha na planilha (o cabeçalho é a linha 1).
+ */
+line: number, 
+/**
+ * Valores lidos, por campo de destino.
+ */
+values: { [key in string]?: string }, errors: Array<ImportIssue>, };
+
+export type Page<T> = { items: Array<T>, total: number, page: number, page_size: number, total_pages: number, };
+
+export type SortDirection = "asc" | "desc";
+
+export type PixCode = { payload: string, png_base64: string, svg: string, };
+
+export type ClientDataExport = { generated_at: string, client: Client, sales: Array<Sale>, gallons: Array<ClientGallon>, 
+/**
+ * Vendas a prazo (fiado) do cliente, também presentes em `sales`.
+ */
+receivables: Array<Sale>, audit_log: Array<AuditLog>, };
+
+export type PurchaseItemInput = { product_id: number, quantity: number, unit_cost: number, };
+
+export type PurchaseOrderDetails = { order: PurchaseOrder, supplier: Supplier, items: Array<PurchaseOrderLine>, total: number, };
+
+export type PurchaseOrderLine = { item: PurchaseOrderItem, product_name: string, };
+
+export type PurchaseOrderSummary = { order: PurchaseOrder, supplier_name: string, total: number, item_count: number, };
+
+export type PurchaseStatus = "open" | "partial" | "received" | "cancelled";
+
+export type ReceivedItem = { item_id: number, quantity: number, unit_cost: number | null, };
+
+export type DailyRevenue = { date: string, revenue: number, };
+
+export type DashboardStats = { revenue: number, revenue_yesterday: number, revenue_change: number | null, sales_count: number, sales_yesterday: number, 
+/**
+ * Diferença absoluta de pedidos em relação a ontem.
+ */
+sales_change: number, client_count: number, alerts: number, };
+
+export type ExpiringGallon = { id: number, client_name: string, brand: string, expiration_date: string, };
+
+export type Metric = { value: number, previous: number, change: number | null, };
+
+export type ProfitLine = { label: string, quantity: number, revenue: number, cost: number, gross_profit: number, 
+/**
+ * Lucro bruto sobre a receita; `None` sem receita.
+ */
+margin: number | null, };
+
+export type ProfitabilityReport = { summary: ProfitLine, 
+/**
+ * Itens vendidos sem custo conhecido, contados com custo zero.
+ */
+items_without_cost: number, by_product: Array<ProfitLine>, by_category: Array<ProfitLine>, 
+/**
+ * Por dia local (AAAA-MM-DD), em ordem cronológica.
+ */
+by_day: Array<ProfitLine>, };
+
+export type ReportSale = { id: number, client_name: string, 
+/**
+ * Itens da venda em JSON, como gravados.
+ */
+items: string, total: number, payment_method: string, created_at: string, };
+
+export type ReportSummary = { revenue: Metric, sales_count: Metric, average_ticket: Metric, unique_clients: Metric, };
+
+export type SalesReport = { summary: ReportSummary, chart_data: Array<DailyRevenue>, sales_list: Array<ReportSale>, };
+
+export type RecentSale = { id: number, client_name: string, 
+/**
+ * Itens da venda em JSON, como gravados.
+ */
+items: string, total: number, created_at: string, };
+
+export type SaleDetails = { id: number, client_name: string, items: JsonValue, total: number, payment_method: string, created_at: string, };
+
+export type SearchKind = "client" | "product";
+
+export type SearchResult = { kind: SearchKind, id: number, title: string, subtitle: string | null, 
+/**
+ * Relevância calculada pelo bm25 (quanto menor, mais relevante).
+ */
+rank: number, };
+
+export type ExportedFile = { file_name: string, content: string, };
+
+export type StartupState = "ready" | "locked" | "failed";
+
+export type StartupStatus = { state: StartupState, 
+/**
+ * Motivo da falha ao abrir o banco (ou senha incorreta).
+ */
+error: string | null, data_dir: string, backups: Array<BackupFile>, };
+
+export type ReorderSuggestion = { product: Product, 
+/**
+ * Quantidade vendida no período.
+ */
+sold_qty: number, average_daily_sales: number, 
+/**
+ * Dias até zerar o estoque no ritmo atual; `null` sem vendas no período.
+ */
+days_of_stock: number | null, suggested_qty: number, };
+
+export type StockDiscrepancy = { product_id: number, name: string, stock_quantity: number, movements_total: number, };
+
+export type StockReason = "sale" | "purchase" | "adjustment" | "loss" | "return";
+
+export type Period = "day" | "week" | "month";
+
+export type Range = { start: string, end: string, };
+
+export type JsonValue = number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null;
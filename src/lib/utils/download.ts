@@ -0,0 +1,27 @@
+import type { ExportedFile } from "@/lib/bindings";
+
+/** Salva um arquivo na pasta de downloads do usuário. */
+export const downloadBlob = (fileName: string, blob: Blob) => {
+    const url = URL.createObjectURL(blob);
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = fileName;
+    link.click();
+    URL.revokeObjectURL(url);
+};
+
+/** Salva uma planilha gerada pelos comandos de exportação (conteúdo em base64). */
+export const downloadExportedFile = async (file: ExportedFile) => {
+    const blob = await fetch(`data:application/octet-stream;base64,${file.content}`).then(res => res.blob());
+    downloadBlob(file.file_name, blob);
+};
+
+/** Lê um arquivo escolhido pelo usuário em base64, para os comandos de importação. */
+export const fileToBase64 = async (file: File) => {
+    const bytes = new Uint8Array(await file.arrayBuffer());
+    let binary = "";
+    for (let i = 0; i < bytes.length; i += 0x8000) {
+        binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+    }
+    return btoa(binary);
+};
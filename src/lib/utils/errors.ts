@@ -0,0 +1,11 @@
+import type { AppError } from "@/lib/bindings";
+
+export const isAppError = (error: unknown): error is AppError =>
+    typeof error === "object" && error !== null && "code" in error && "message" in error;
+
+/** Mensagem para exibir ao usuário a partir do erro de um comando. */
+export const errorMessage = (error: unknown) => {
+    if (isAppError(error)) return error.message;
+    if (error instanceof Error) return error.message;
+    return String(error);
+};
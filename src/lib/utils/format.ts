@@ -0,0 +1,26 @@
+const currencyFormatter = new Intl.NumberFormat("pt-BR", {
+    style: "currency",
+    currency: "BRL",
+});
+
+const changeFormatter = new Intl.NumberFormat("pt-BR", {
+    style: "percent",
+    maximumFractionDigits: 1,
+    signDisplay: "exceptZero",
+});
+
+export const formatCurrency = (value: number) => currencyFormatter.format(value);
+
+export const formatNumber = (value: number, fractionDigits = 0) =>
+    value.toLocaleString("pt-BR", {
+        minimumFractionDigits: fractionDigits,
+        maximumFractionDigits: fractionDigits,
+    });
+
+/** Variação como razão (0.25 = +25%); sem base de comparação vira "—". */
+export const formatChange = (ratio: number | null) =>
+    ratio === null ? "—" : changeFormatter.format(ratio);
+
+/** Diferença absoluta com sinal, ex.: "+3" ou "-2". */
+export const formatDelta = (value: number) =>
+    value > 0 ? `+${value}` : value.toString();
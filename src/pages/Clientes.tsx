@@ -12,7 +12,10 @@ import {
   UserPlus,
   Phone,
   MapPin,
-  ShoppingCart
+  ShoppingCart,
+  Download,
+  FileText,
+  ShieldOff
 } from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
@@ -29,20 +32,14 @@ import {
   DialogFooter,
 } from "@/components/ui/dialog";
 import { StatusBadge } from "@/components/StatusBadge";
+import { ImportDialog } from "@/components/ImportDialog";
 import { toast } from "@/components/ui/sonner";
 import { maskCEP, maskCPFCNPJ, maskPhone } from "@/lib/utils/masks";
+import type { CepAddress, Client as ClientRecord, ClientDataExport, ClientDetails } from "@/lib/bindings";
+import { errorMessage } from "@/lib/utils/errors";
+import { downloadBlob } from "@/lib/utils/download";
 
-interface Client {
-  id: number;
-  name: string;
-  phone: string | null;
-  document: string | null;
-  cep: string | null;
-  city: string | null;
-  state: string | null;
-  address: string | null;
-  observations: string | null;
-  created_at: string;
+interface Client extends ClientRecord {
   since?: string;
   totalPedidos?: number;
   galoes?: any[];
@@ -65,9 +62,17 @@ const Clientes = () => {
   const [cep, setCep] = useState("");
   const [city, setCity] = useState("");
   const [state, setState] = useState("");
-  const [address, setAddress] = useState("");
+  const [street, setStreet] = useState("");
+  const [number, setNumber] = useState("");
+  const [complement, setComplement] = useState("");
+  const [neighborhood, setNeighborhood] = useState("");
   const [observations, setObservations] = useState("");
 
+  // LGPD
+  const [isAnonymizeOpen, setIsAnonymizeOpen] = useState(false);
+  const [adminPassword, setAdminPassword] = useState("");
+  const [isAnonymizing, setIsAnonymizing] = useState(false);
+
   useEffect(() => {
     loadClients();
   }, []);
@@ -79,25 +84,16 @@ const Clientes = () => {
     const pureCEP = formatted.replace(/\D/g, "");
     if (pureCEP.length === 8) {
       try {
-        const response = await fetch(`https://viacep.com.br/ws/${pureCEP}/json/`);
-        const data = await response.json();
-
-        if (data.erro) {
-          toast.error("CEP não encontrado");
-          return;
-        }
-
-        setCity(data.localidade || "");
-        setState(data.uf || "");
-        if (data.logradouro) {
-          setAddress(prev => {
-            const complement = data.bairro ? `, ${data.bairro}` : "";
-            return `${data.logradouro}${complement}`;
-          });
-        }
+        const data = await invoke<CepAddress>("lookup_cep", { cep: pureCEP });
+
+        setCity(data.city);
+        setState(data.state);
+        if (data.street) setStreet(data.street);
+        if (data.neighborhood) setNeighborhood(data.neighborhood);
         toast.success("Endereço preenchido automaticamente!");
       } catch (error) {
         console.error("Erro ao buscar CEP:", error);
+        toast.error(errorMessage(error));
       }
     }
   };
@@ -118,7 +114,10 @@ const Clientes = () => {
         cep: cep || null,
         city: city || null,
         state: state || null,
-        address: address || null,
+        street: street || null,
+        number: number || null,
+        complement: complement || null,
+        neighborhood: neighborhood || null,
         observations: observations || null,
       };
 
@@ -134,7 +133,7 @@ const Clientes = () => {
       resetForm();
       loadClients();
     } catch (error) {
-      toast.error("Erro ao salvar cliente: " + error);
+      toast.error("Erro ao salvar cliente: " + errorMessage(error));
     }
   };
 
@@ -147,7 +146,10 @@ const Clientes = () => {
     setCep("");
     setCity("");
     setState("");
-    setAddress("");
+    setStreet("");
+    setNumber("");
+    setComplement("");
+    setNeighborhood("");
     setObservations("");
   };
 
@@ -156,11 +158,14 @@ const Clientes = () => {
     setEditingId(client.id);
     setName(client.name || "");
     setPhone(client.phone || "");
-    setDocument(client.document || "");
-    setCep(client.cep || "");
+    setDocument(client.document ? maskCPFCNPJ(client.document) : "");
+    setCep(client.cep ? maskCEP(client.cep) : "");
     setCity(client.city || "");
     setState(client.state || "");
-    setAddress(client.address || "");
+    setStreet(client.street || "");
+    setNumber(client.number || "");
+    setComplement(client.complement || "");
+    setNeighborhood(client.neighborhood || "");
     setObservations(client.observations || "");
     setIsDialogOpen(true);
   };
@@ -168,7 +173,7 @@ const Clientes = () => {
   const loadDetails = async () => {
     if (!selectedId) return;
     try {
-      const details = await invoke<any>("get_client_details", { client_id: selectedId });
+      const details = await invoke<ClientDetails>("get_client_details", { client_id: selectedId });
 
       // Verificação defensiva se o ID ainda é o mesmo após o await
       setClients(prev => {
@@ -178,14 +183,14 @@ const Clientes = () => {
         const newClients = [...prev];
         newClients[index] = {
           ...newClients[index],
-          totalPedidos: details.totalPedidos,
-          historico: details.historico.map((h: any) => ({
+          totalPedidos: details.total_orders,
+          historico: details.sales.map((h) => ({
             data: new Date(h.created_at).toLocaleDateString('pt-BR'),
             items: h.items,
             pagamento: h.payment_method || "Não informado", // Confirmando snake_case
             total: `R$ ${h.total.toFixed(2)} `
           })),
-          galoes: details.galoes.map((g: any) => ({
+          galoes: details.gallons.map((g) => ({
             id: g.id,
             brand: g.brand,
             vencimento: new Date(g.expiration_date).toLocaleDateString('pt-BR'),
@@ -200,6 +205,58 @@ const Clientes = () => {
     }
   };
 
+  const currentUsername = () => JSON.parse(localStorage.getItem("user") || "{}").username;
+
+  const handleExportJson = async () => {
+    if (!selectedId) return;
+    try {
+      const data = await invoke<ClientDataExport>("export_client_data", {
+        clientId: selectedId,
+        username: currentUsername(),
+      });
+      const blob = new Blob([JSON.stringify(data, null, 2)], { type: "application/json" });
+      downloadBlob(`cliente-${selectedId}-dados.json`, blob);
+      toast.success("Dados do cliente exportados");
+    } catch (error) {
+      toast.error("Erro ao exportar dados: " + errorMessage(error));
+    }
+  };
+
+  const handleExportPdf = async () => {
+    if (!selectedId) return;
+    try {
+      const pdfBase64 = await invoke<string>("export_client_data_pdf", {
+        clientId: selectedId,
+        username: currentUsername(),
+      });
+      const blob = await fetch(`data:application/pdf;base64,${pdfBase64}`).then(res => res.blob());
+      window.open(URL.createObjectURL(blob), '_blank');
+    } catch (error) {
+      toast.error("Erro ao gerar PDF: " + errorMessage(error));
+    }
+  };
+
+  const handleAnonymize = async () => {
+    if (!selectedId) return;
+    setIsAnonymizing(true);
+    try {
+      await invoke("anonymize_client", {
+        clientId: selectedId,
+        username: currentUsername(),
+        password: adminPassword,
+      });
+      toast.success("Dados pessoais do cliente removidos");
+      setIsAnonymizeOpen(false);
+      setAdminPassword("");
+      await loadClients();
+      await loadDetails();
+    } catch (error) {
+      toast.error(errorMessage(error));
+    } finally {
+      setIsAnonymizing(false);
+    }
+  };
+
   const loadClients = async () => {
     try {
       const data = await invoke<Client[]>("get_clients");
@@ -248,104 +305,134 @@ const Clientes = () => {
           <h1 className="text-2xl font-bold text-foreground">Clientes</h1>
           <p className="text-sm text-muted-foreground">{clients.length} clientes cadastrados</p>
         </div>
-        <Dialog open={isDialogOpen} onOpenChange={(open) => {
-          setIsDialogOpen(open);
-          if (!open) resetForm();
-        }}>
-          <DialogTrigger asChild>
-            <Button className="gap-1.5" onClick={() => setIsEditing(false)}>
-              <UserPlus className="h-4 w-4" />
-              Novo Cliente
-            </Button>
-          </DialogTrigger>
-          <DialogContent className="max-w-2xl">
-            <DialogHeader>
-              <DialogTitle>{isEditing ? "Editar Cliente" : "Cadastrar Novo Cliente"}</DialogTitle>
-              <DialogDescription>
-                {isEditing ? "Altere as informações do cliente abaixo." : "Insira as informações detalhadas para o novo cadastro."}
-              </DialogDescription>
-            </DialogHeader>
-            <div className="grid gap-4 py-4 sm:grid-cols-2">
-              <div className="grid gap-2 sm:col-span-2">
-                <Label htmlFor="name">Nome Completo</Label>
-                <Input
-                  id="name"
-                  value={name}
-                  onChange={(e) => setName(e.target.value)}
-                  placeholder="Nome do cliente"
-                />
-              </div>
-              <div className="grid gap-2">
-                <Label htmlFor="document">CPF ou CNPJ</Label>
-                <Input
-                  id="document"
-                  value={document}
-                  onChange={(e) => setDocument(maskCPFCNPJ(e.target.value))}
-                  placeholder="000.000.000-00"
-                />
-              </div>
-              <div className="grid gap-2">
-                <Label htmlFor="phone">Telefone / WhatsApp</Label>
-                <Input
-                  id="phone"
-                  value={phone}
-                  onChange={(e) => setPhone(maskPhone(e.target.value))}
-                  placeholder="(00) 00000-0000"
-                />
-              </div>
-              <div className="grid gap-2">
-                <Label htmlFor="cep">CEP</Label>
-                <Input
-                  id="cep"
-                  value={cep}
-                  onChange={(e) => handleCEPChange(e.target.value)}
-                  placeholder="00000-000"
-                />
-              </div>
-              <div className="grid gap-2">
-                <Label htmlFor="address">Endereço</Label>
-                <Input
-                  id="address"
-                  value={address}
-                  onChange={(e) => setAddress(e.target.value)}
-                  placeholder="Rua, Número, Bairro..."
-                />
-              </div>
-              <div className="grid gap-2">
-                <Label htmlFor="city">Cidade</Label>
-                <Input
-                  id="city"
-                  value={city}
-                  onChange={(e) => setCity(e.target.value)}
-                  placeholder="Nome da cidade"
-                />
-              </div>
-              <div className="grid gap-2">
-                <Label htmlFor="state">Estado (UF)</Label>
-                <Input
-                  id="state"
-                  value={state}
-                  onChange={(e) => setState(e.target.value)}
-                  placeholder="Ex: SP"
-                  maxLength={2}
-                />
-              </div>
-              <div className="grid gap-2 sm:col-span-2">
-                <Label htmlFor="observations">Observações</Label>
-                <Input
-                  id="observations"
-                  value={observations}
-                  onChange={(e) => setObservations(e.target.value)}
-                  placeholder="Informações adicionais..."
-                />
+        <div className="flex items-center gap-2">
+          <ImportDialog kind="clients" onImported={loadClients} />
+          <Dialog open={isDialogOpen} onOpenChange={(open) => {
+            setIsDialogOpen(open);
+            if (!open) resetForm();
+          }}>
+            <DialogTrigger asChild>
+              <Button className="gap-1.5" onClick={() => setIsEditing(false)}>
+                <UserPlus className="h-4 w-4" />
+                Novo Cliente
+              </Button>
+            </DialogTrigger>
+            <DialogContent className="max-w-2xl">
+              <DialogHeader>
+                <DialogTitle>{isEditing ? "Editar Cliente" : "Cadastrar Novo Cliente"}</DialogTitle>
+                <DialogDescription>
+                  {isEditing ? "Altere as informações do cliente abaixo." : "Insira as informações detalhadas para o novo cadastro."}
+                </DialogDescription>
+              </DialogHeader>
+              <div className="grid gap-4 py-4 sm:grid-cols-2">
+                <div className="grid gap-2 sm:col-span-2">
+                  <Label htmlFor="name">Nome Completo</Label>
+                  <Input
+                    id="name"
+                    value={name}
+                    onChange={(e) => setName(e.target.value)}
+                    placeholder="Nome do cliente"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="document">CPF ou CNPJ</Label>
+                  <Input
+                    id="document"
+                    value={document}
+                    onChange={(e) => setDocument(maskCPFCNPJ(e.target.value))}
+                    placeholder="000.000.000-00"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="phone">Telefone / WhatsApp</Label>
+                  <Input
+                    id="phone"
+                    value={phone}
+                    onChange={(e) => setPhone(maskPhone(e.target.value))}
+                    placeholder="(00) 00000-0000"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="cep">CEP</Label>
+                  <Input
+                    id="cep"
+                    value={cep}
+                    onChange={(e) => handleCEPChange(e.target.value)}
+                    placeholder="00000-000"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="street">Logradouro</Label>
+                  <Input
+                    id="street"
+                    value={street}
+                    onChange={(e) => setStreet(e.target.value)}
+                    placeholder="Rua, Avenida..."
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="number">Número</Label>
+                  <Input
+                    id="number"
+                    value={number}
+                    onChange={(e) => setNumber(e.target.value)}
+                    placeholder="Ex: 127"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="complement">Complemento</Label>
+                  <Input
+                    id="complement"
+                    value={complement}
+                    onChange={(e) => setComplement(e.target.value)}
+                    placeholder="Apto, Bloco..."
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="neighborhood">Bairro</Label>
+                  <Input
+                    id="neighborhood"
+                    value={neighborhood}
+                    onChange={(e) => setNeighborhood(e.target.value)}
+                    placeholder="Nome do bairro"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="city">Cidade</Label>
+                  <Input
+                    id="city"
+                    value={city}
+                    onChange={(e) => setCity(e.target.value)}
+                    placeholder="Nome da cidade"
+                  />
+                </div>
+                <div className="grid gap-2">
+                  <Label htmlFor="state">Estado (UF)</Label>
+                  <Input
+                    id="state"
+                    value={state}
+                    onChange={(e) => setState(e.target.value.toUpperCase())}
+                    placeholder="Ex: SP"
+                    maxLength={2}
+                  />
+                </div>
+                <div className="grid gap-2 sm:col-span-2">
+                  <Label htmlFor="observations">Observações</Label>
+                  <Input
+                    id="observations"
+                    value={observations}
+                    onChange={(e) => setObservations(e.target.value)}
+                    placeholder="Informações adicionais..."
+                  />
+                </div>
               </div>
-            </div>
-            <DialogFooter>
-              <Button variant="outline" onClick={() => setIsDialogOpen(false)}>Cancelar</Button>
-              <Button onClick={handleSaveClient}>{isEditing ? "Salvar Alterações" : "Salvar Cliente"}</Button>
-            </DialogFooter>
-          </DialogContent>
-        </Dialog>
+              <DialogFooter>
+                <Button variant="outline" onClick={() => setIsDialogOpen(false)}>Cancelar</Button>
+                <Button onClick={handleSaveClient}>{isEditing ? "Salvar Alterações" : "Salvar Cliente"}</Button>
+              </DialogFooter>
+            </DialogContent>
+          </Dialog>
+        </div>
       </div>
 
       <div className="grid gap-6 lg:grid-cols-3">
@@ -397,10 +484,54 @@ const Clientes = () => {
                   <p className="text-xs text-muted-foreground">Cliente desde {selected.since}</p>
                 </div>
               </div>
-              <Button variant="outline" size="sm" onClick={() => handleEditClick(selected as Client)}>
-                Editar
-              </Button>
+              <div className="flex items-center gap-2">
+                <Button variant="outline" size="sm" onClick={handleExportJson} title="Exportar dados (JSON)">
+                  <Download className="h-4 w-4" />
+                </Button>
+                <Button variant="outline" size="sm" onClick={handleExportPdf} title="Exportar dados (PDF)">
+                  <FileText className="h-4 w-4" />
+                </Button>
+                {!(selected as Client).anonymized_at && (
+                  <>
+                    <Button variant="outline" size="sm" onClick={() => setIsAnonymizeOpen(true)} title="Anonimizar">
+                      <ShieldOff className="h-4 w-4" />
+                    </Button>
+                    <Button variant="outline" size="sm" onClick={() => handleEditClick(selected as Client)}>
+                      Editar
+                    </Button>
+                  </>
+                )}
+              </div>
             </div>
+            <Dialog open={isAnonymizeOpen} onOpenChange={(open) => {
+              setIsAnonymizeOpen(open);
+              if (!open) setAdminPassword("");
+            }}>
+              <DialogContent>
+                <DialogHeader>
+                  <DialogTitle>Anonimizar Cliente</DialogTitle>
+                  <DialogDescription>
+                    Nome, contato, documento, endereço, observações e galões de {selected.name} serão
+                    apagados definitivamente. As vendas continuam registradas. Confirme com a senha de administrador.
+                  </DialogDescription>
+                </DialogHeader>
+                <div className="space-y-2">
+                  <Label htmlFor="admin-password">Senha do administrador</Label>
+                  <Input
+                    id="admin-password"
+                    type="password"
+                    value={adminPassword}
+                    onChange={(e) => setAdminPassword(e.target.value)}
+                  />
+                </div>
+                <DialogFooter>
+                  <Button variant="outline" onClick={() => setIsAnonymizeOpen(false)}>Cancelar</Button>
+                  <Button variant="destructive" onClick={handleAnonymize} disabled={!adminPassword || isAnonymizing}>
+                    {isAnonymizing ? "Anonimizando..." : "Anonimizar"}
+                  </Button>
+                </DialogFooter>
+              </DialogContent>
+            </Dialog>
 
             <div className="flex gap-4 mt-3 text-xs text-muted-foreground">
               <span className="flex items-center gap-1">
@@ -446,7 +577,7 @@ const Clientes = () => {
                   <div className="grid grid-cols-2 gap-y-4 gap-x-6 text-sm">
                     <div>
                       <p className="text-xs text-muted-foreground mb-0.5">CPF / CNPJ</p>
-                      <p className="font-medium">{selected.document || "Não informado"}</p>
+                      <p className="font-medium">{selected.document ? maskCPFCNPJ(selected.document) : "Não informado"}</p>
                     </div>
                     <div>
                       <p className="text-xs text-muted-foreground mb-0.5">Telefone</p>
@@ -454,7 +585,7 @@ const Clientes = () => {
                     </div>
                     <div>
                       <p className="text-xs text-muted-foreground mb-0.5">CEP</p>
-                      <p className="font-medium">{selected.cep || "Não informado"}</p>
+                      <p className="font-medium">{selected.cep ? maskCEP(selected.cep) : "Não informado"}</p>
                     </div>
                     <div>
                       <p className="text-xs text-muted-foreground mb-0.5">Cidade / UF</p>
@@ -513,56 +644,58 @@ const Clientes = () => {
               <TabsContent value="galoes" className="mt-4">
                 <div className="flex justify-between items-center mb-4">
                   <h3 className="text-sm font-semibold">Galões em Posse</h3>
-                  <Dialog>
-                    <DialogTrigger asChild>
-                      <Button size="sm" variant="outline" className="h-8 gap-1">
-                        <Gem className="h-3.5 w-3.5" /> Adicionar Galão
-                      </Button>
-                    </DialogTrigger>
-                    <DialogContent>
-                      <DialogHeader>
-                        <DialogTitle>Registrar Novo Galão</DialogTitle>
-                      </DialogHeader>
-                      <div className="grid gap-4 py-4">
-                        <div className="grid gap-2">
-                          <Label>Marca do Galão</Label>
-                          <Input id="brand" placeholder="Ex: Indaiá, Minalba..." />
+                  {!(selected as Client).anonymized_at && (
+                    <Dialog>
+                      <DialogTrigger asChild>
+                        <Button size="sm" variant="outline" className="h-8 gap-1">
+                          <Gem className="h-3.5 w-3.5" /> Adicionar Galão
+                        </Button>
+                      </DialogTrigger>
+                      <DialogContent>
+                        <DialogHeader>
+                          <DialogTitle>Registrar Novo Galão</DialogTitle>
+                        </DialogHeader>
+                        <div className="grid gap-4 py-4">
+                          <div className="grid gap-2">
+                            <Label>Marca do Galão</Label>
+                            <Input id="brand" placeholder="Ex: Indaiá, Minalba..." />
+                          </div>
+                          <div className="grid gap-2">
+                            <Label>Data de Vencimento</Label>
+                            <Input id="exp_date" type="date" />
+                          </div>
                         </div>
-                        <div className="grid gap-2">
-                          <Label>Data de Vencimento</Label>
-                          <Input id="exp_date" type="date" />
-                        </div>
-                      </div>
-                      <DialogFooter>
-                        <Button onClick={async () => {
-                          const brandElement = document.getElementById('brand') as HTMLInputElement;
-                          const expDateElement = document.getElementById('exp_date') as HTMLInputElement;
-                          const brand = brandElement.value;
-                          const expDate = expDateElement.value;
-
-                          if (!brand || !expDate) {
-                            toast.error("Preencha todos os campos");
-                            return;
-                          }
-
-                          try {
-                            await invoke("add_client_gallon", {
-                              clientId: selectedId,
-                              brand,
-                              expirationDate: new Date(expDate).toISOString()
-                            });
-                            loadDetails();
-                            toast.success("Galão registrado com sucesso!");
-                            // Limpar campos
-                            brandElement.value = "";
-                            expDateElement.value = "";
-                          } catch (err) {
-                            toast.error(String(err));
-                          }
-                        }}>Salvar Galão</Button>
-                      </DialogFooter>
-                    </DialogContent>
-                  </Dialog>
+                        <DialogFooter>
+                          <Button onClick={async () => {
+                            const brandElement = document.getElementById('brand') as HTMLInputElement;
+                            const expDateElement = document.getElementById('exp_date') as HTMLInputElement;
+                            const brand = brandElement.value;
+                            const expDate = expDateElement.value;
+
+                            if (!brand || !expDate) {
+                              toast.error("Preencha todos os campos");
+                              return;
+                            }
+
+                            try {
+                              await invoke("add_client_gallon", {
+                                clientId: selectedId,
+                                brand,
+                                expirationDate: new Date(expDate).toISOString()
+                              });
+                              loadDetails();
+                              toast.success("Galão registrado com sucesso!");
+                              // Limpar campos
+                              brandElement.value = "";
+                              expDateElement.value = "";
+                            } catch (err) {
+                              toast.error(errorMessage(err));
+                            }
+                          }}>Salvar Galão</Button>
+                        </DialogFooter>
+                      </DialogContent>
+                    </Dialog>
+                  )}
                 </div>
                 <div className="space-y-2">
                   {selected.galoes.map((g) => (
@@ -0,0 +1,561 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Plus, Trash2, Edit3, Truck, ClipboardList, PackageCheck, Ban } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import {
+    Dialog,
+    DialogContent,
+    DialogDescription,
+    DialogHeader,
+    DialogTitle,
+    DialogFooter,
+} from "@/components/ui/dialog";
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from "@/components/ui/select";
+import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
+import { toast } from "@/components/ui/sonner";
+import { useConfirm } from "@/hooks/use-confirm";
+import type {
+    Product,
+    PurchaseOrderDetails,
+    PurchaseOrderSummary,
+    PurchaseStatus,
+    Supplier,
+} from "@/lib/bindings";
+import { errorMessage } from "@/lib/utils/errors";
+import { formatCurrency } from "@/lib/utils/format";
+
+const STATUS_LABELS: Record<PurchaseStatus, string> = {
+    open: "Em aberto",
+    partial: "Recebido em parte",
+    received: "Recebido",
+    cancelled: "Cancelado",
+};
+
+const STATUS_CLASSES: Record<PurchaseStatus, string> = {
+    open: "bg-primary/10 text-primary",
+    partial: "bg-amber-500/10 text-amber-600",
+    received: "bg-emerald-500/10 text-emerald-600",
+    cancelled: "bg-muted text-muted-foreground",
+};
+
+interface DraftItem {
+    productId: string;
+    quantity: string;
+    unitCost: string;
+}
+
+const emptySupplier = { name: "", document: "", phone: "", email: "", notes: "" };
+
+const currentUser = () => JSON.parse(localStorage.getItem("user") || "{}").username;
+
+const Compras = () => {
+    const [orders, setOrders] = useState<PurchaseOrderSummary[]>([]);
+    const [suppliers, setSuppliers] = useState<Supplier[]>([]);
+    const [products, setProducts] = useState<Product[]>([]);
+
+    // Novo pedido
+    const [isNewOrderOpen, setIsNewOrderOpen] = useState(false);
+    const [orderSupplierId, setOrderSupplierId] = useState("");
+    const [orderNotes, setOrderNotes] = useState("");
+    const [draftItems, setDraftItems] = useState<DraftItem[]>([]);
+
+    // Recebimento
+    const [details, setDetails] = useState<PurchaseOrderDetails | null>(null);
+    const [receiving, setReceiving] = useState<Record<number, { quantity: string; unitCost: string }>>({});
+
+    // Fornecedor
+    const [supplierForm, setSupplierForm] = useState(emptySupplier);
+    const [editingSupplierId, setEditingSupplierId] = useState<number | null>(null);
+    const [isSupplierOpen, setIsSupplierOpen] = useState(false);
+
+    const { ConfirmDialog, confirm: openConfirm } = useConfirm();
+
+    useEffect(() => {
+        loadData();
+    }, []);
+
+    const loadData = async () => {
+        try {
+            const [o, s, p] = await Promise.all([
+                invoke<PurchaseOrderSummary[]>("get_purchase_orders", { status: null }),
+                invoke<Supplier[]>("get_suppliers"),
+                invoke<Product[]>("get_products"),
+            ]);
+            setOrders(o);
+            setSuppliers(s);
+            setProducts(p);
+        } catch (err) {
+            toast.error(errorMessage(err));
+        }
+    };
+
+    const openNewOrder = () => {
+        setOrderSupplierId(suppliers[0]?.id.toString() ?? "");
+        setOrderNotes("");
+        setDraftItems([{ productId: "", quantity: "", unitCost: "" }]);
+        setIsNewOrderOpen(true);
+    };
+
+    const updateDraft = (index: number, changes: Partial<DraftItem>) => {
+        setDraftItems(items => items.map((item, i) => (i === index ? { ...item, ...changes } : item)));
+    };
+
+    const handleCreateOrder = async () => {
+        try {
+            await invoke("create_purchase_order", {
+                supplierId: parseInt(orderSupplierId),
+                items: draftItems.map(item => ({
+                    product_id: parseInt(item.productId),
+                    quantity: parseInt(item.quantity),
+                    unit_cost: parseFloat(item.unitCost),
+                })),
+                notes: orderNotes || null,
+                username: currentUser(),
+            });
+            setIsNewOrderOpen(false);
+            loadData();
+            toast.success("Pedido de compra registrado!");
+        } catch (err) {
+            toast.error("Erro ao registrar pedido: " + errorMessage(err));
+        }
+    };
+
+    const openDetails = async (id: number) => {
+        try {
+            const order = await invoke<PurchaseOrderDetails>("get_purchase_order", { id });
+            setDetails(order);
+            setReceiving(Object.fromEntries(order.items.map(({ item }) => [
+                item.id,
+                { quantity: String(item.quantity - item.received_quantity), unitCost: String(item.unit_cost) },
+            ])));
+        } catch (err) {
+            toast.error(errorMessage(err));
+        }
+    };
+
+    const handleReceive = async () => {
+        if (!details) return;
+        const items = details.items
+            .map(({ item }) => ({ item, entry: receiving[item.id] }))
+            .filter(({ entry }) => entry && parseInt(entry.quantity) > 0)
+            .map(({ item, entry }) => ({
+                item_id: item.id,
+                quantity: parseInt(entry.quantity),
+                unit_cost: parseFloat(entry.unitCost) === item.unit_cost ? null : parseFloat(entry.unitCost),
+            }));
+        try {
+            const order = await invoke<PurchaseOrderDetails>("receive_purchase_order", {
+                id: details.order.id,
+                items,
+                username: currentUser(),
+            });
+            setDetails(null);
+            loadData();
+            toast.success(order.order.status === "received" ? "Pedido recebido!" : "Recebimento parcial registrado!");
+        } catch (err) {
+            toast.error("Erro ao receber pedido: " + errorMessage(err));
+        }
+    };
+
+    const handleCancel = async () => {
+        if (!details) return;
+        const ok = await openConfirm(
+            "Cancelar Pedido",
+            "O que ainda não foi recebido deixa de ser esperado. O estoque já recebido não é alterado."
+        );
+        if (!ok) return;
+        try {
+            await invoke("cancel_purchase_order", { id: details.order.id, username: currentUser() });
+            setDetails(null);
+            loadData();
+            toast.success("Pedido cancelado!");
+        } catch (err) {
+            toast.error(errorMessage(err));
+        }
+    };
+
+    const openSupplier = (supplier: Supplier | null) => {
+        setEditingSupplierId(supplier?.id ?? null);
+        setSupplierForm(supplier ? {
+            name: supplier.name,
+            document: supplier.document ?? "",
+            phone: supplier.phone ?? "",
+            email: supplier.email ?? "",
+            notes: supplier.notes ?? "",
+        } : emptySupplier);
+        setIsSupplierOpen(true);
+    };
+
+    const handleSaveSupplier = async () => {
+        const args = {
+            name: supplierForm.name,
+            document: supplierForm.document || null,
+            phone: supplierForm.phone || null,
+            email: supplierForm.email || null,
+            notes: supplierForm.notes || null,
+        };
+        try {
+            if (editingSupplierId === null) {
+                await invoke("create_supplier", args);
+            } else {
+                await invoke("update_supplier", { id: editingSupplierId, ...args });
+            }
+            setIsSupplierOpen(false);
+            loadData();
+            toast.success("Fornecedor salvo!");
+        } catch (err) {
+            toast.error("Erro ao salvar fornecedor: " + errorMessage(err));
+        }
+    };
+
+    const handleDeleteSupplier = async (id: number) => {
+        const ok = await openConfirm("Remover Fornecedor", "Tem certeza que deseja remover este fornecedor?");
+        if (!ok) return;
+        try {
+            await invoke("delete_supplier", { id });
+            loadData();
+            toast.success("Fornecedor removido!");
+        } catch (err) {
+            toast.error("Erro ao remover fornecedor: " + errorMessage(err));
+        }
+    };
+
+    const canChange = details?.order.status === "open" || details?.order.status === "partial";
+
+    return (
+        <div className="space-y-6">
+            <div className="flex items-center justify-between">
+                <div>
+                    <h1 className="text-2xl font-bold text-foreground">Compras</h1>
+                    <p className="text-sm text-muted-foreground">Pedidos a fornecedores e entrada de estoque</p>
+                </div>
+                <div className="flex items-center gap-2">
+                    <Button variant="outline" className="gap-2" onClick={() => openSupplier(null)}>
+                        <Truck className="h-4 w-4" />
+                        Novo Fornecedor
+                    </Button>
+                    <Button className="gap-2" onClick={openNewOrder} disabled={suppliers.length === 0}>
+                        <Plus className="h-4 w-4" />
+                        Novo Pedido
+                    </Button>
+                </div>
+            </div>
+
+            <Tabs defaultValue="pedidos" className="w-full">
+                <TabsList>
+                    <TabsTrigger value="pedidos" className="gap-2">
+                        <ClipboardList className="h-4 w-4" />
+                        Pedidos
+                    </TabsTrigger>
+                    <TabsTrigger value="fornecedores" className="gap-2">
+                        <Truck className="h-4 w-4" />
+                        Fornecedores
+                    </TabsTrigger>
+                </TabsList>
+
+                <TabsContent value="pedidos">
+                    <Card className="card-shadow border-border/60">
+                        <CardHeader className="pb-3 border-b">
+                            <CardTitle className="text-base font-semibold">Pedidos de Compra</CardTitle>
+                        </CardHeader>
+                        <CardContent className="p-0">
+                            <table className="w-full text-left">
+                                <thead>
+                                    <tr className="border-b bg-muted/30 text-xs font-semibold uppercase text-muted-foreground">
+                                        <th className="px-6 py-3">Pedido</th>
+                                        <th className="px-6 py-3">Fornecedor</th>
+                                        <th className="px-6 py-3">Data</th>
+                                        <th className="px-6 py-3">Situação</th>
+                                        <th className="px-6 py-3 text-right">Total</th>
+                                    </tr>
+                                </thead>
+                                <tbody className="divide-y divide-border">
+                                    {orders.map(({ order, supplier_name, total }) => (
+                                        <tr
+                                            key={order.id}
+                                            className="hover:bg-muted/30 transition-colors cursor-pointer"
+                                            onClick={() => openDetails(order.id)}
+                                        >
+                                            <td className="px-6 py-3 text-sm font-medium">#{order.id}</td>
+                                            <td className="px-6 py-3 text-sm">{supplier_name}</td>
+                                            <td className="px-6 py-3 text-xs text-muted-foreground">
+                                                {new Date(order.created_at).toLocaleDateString("pt-BR")}
+                                            </td>
+                                            <td className="px-6 py-3">
+                                                <span className={`text-xs px-2 py-1 rounded-full ${STATUS_CLASSES[order.status as PurchaseStatus]}`}>
+                                                    {STATUS_LABELS[order.status as PurchaseStatus] ?? order.status}
+                                                </span>
+                                            </td>
+                                            <td className="px-6 py-3 text-sm font-semibold text-right">{formatCurrency(total)}</td>
+                                        </tr>
+                                    ))}
+                                    {orders.length === 0 && (
+                                        <tr>
+                                            <td colSpan={5} className="px-6 py-8 text-center text-sm text-muted-foreground">
+                                                Nenhum pedido de compra registrado.
+                                            </td>
+                                        </tr>
+                                    )}
+                                </tbody>
+                            </table>
+                        </CardContent>
+                    </Card>
+                </TabsContent>
+
+                <TabsContent value="fornecedores">
+                    <Card className="card-shadow border-border/60">
+                        <CardHeader className="pb-3 border-b">
+                            <CardTitle className="text-base font-semibold">Fornecedores</CardTitle>
+                        </CardHeader>
+                        <CardContent className="p-0">
+                            <table className="w-full text-left">
+                                <thead>
+                                    <tr className="border-b bg-muted/30 text-xs font-semibold uppercase text-muted-foreground">
+                                        <th className="px-6 py-3">Nome</th>
+                                        <th className="px-6 py-3">Documento</th>
+                                        <th className="px-6 py-3">Contato</th>
+                                        <th className="px-6 py-3 text-right">Ações</th>
+                                    </tr>
+                                </thead>
+                                <tbody className="divide-y divide-border">
+                                    {suppliers.map((supplier) => (
+                                        <tr key={supplier.id} className="hover:bg-muted/30 transition-colors">
+                                            <td className="px-6 py-3 text-sm font-medium">{supplier.name}</td>
+                                            <td className="px-6 py-3 text-sm">{supplier.document ?? "-"}</td>
+                                            <td className="px-6 py-3 text-xs text-muted-foreground">
+                                                {[supplier.phone, supplier.email].filter(Boolean).join(" • ") || "-"}
+                                            </td>
+                                            <td className="px-6 py-3 text-right">
+                                                <div className="flex justify-end gap-2">
+                                                    <Button variant="ghost" size="icon" className="h-8 w-8" onClick={() => openSupplier(supplier)}>
+                                                        <Edit3 className="h-4 w-4" />
+                                                    </Button>
+                                                    <Button
+                                                        variant="ghost"
+                                                        size="icon"
+                                                        className="h-8 w-8 text-destructive hover:text-destructive"
+                                                        onClick={() => handleDeleteSupplier(supplier.id)}
+                                                    >
+                                                        <Trash2 className="h-4 w-4" />
+                                                    </Button>
+                                                </div>
+                                            </td>
+                                        </tr>
+                                    ))}
+                                </tbody>
+                            </table>
+                        </CardContent>
+                    </Card>
+                </TabsContent>
+            </Tabs>
+
+            {/* Novo pedido */}
+            <Dialog open={isNewOrderOpen} onOpenChange={setIsNewOrderOpen}>
+                <DialogContent className="max-w-2xl">
+                    <DialogHeader>
+                        <DialogTitle>Novo Pedido de Compra</DialogTitle>
+                        <DialogDescription>O estoque só aumenta quando o pedido for recebido.</DialogDescription>
+                    </DialogHeader>
+                    <div className="grid gap-4 py-2">
+                        <div className="grid grid-cols-2 gap-4">
+                            <div className="grid gap-2">
+                                <Label>Fornecedor</Label>
+                                <Select value={orderSupplierId} onValueChange={setOrderSupplierId}>
+                                    <SelectTrigger>
+                                        <SelectValue />
+                                    </SelectTrigger>
+                                    <SelectContent>
+                                        {suppliers.map(s => (
+                                            <SelectItem key={s.id} value={s.id.toString()}>{s.name}</SelectItem>
+                                        ))}
+                                    </SelectContent>
+                                </Select>
+                            </div>
+                            <div className="grid gap-2">
+                                <Label htmlFor="order-notes">Observação</Label>
+                                <Input id="order-notes" value={orderNotes} onChange={(e) => setOrderNotes(e.target.value)} />
+                            </div>
+                        </div>
+                        {draftItems.map((item, index) => (
+                            <div key={index} className="grid grid-cols-[1fr_90px_110px_36px] gap-2 items-end">
+                                <div className="grid gap-1">
+                                    {index === 0 && <Label className="text-xs">Produto</Label>}
+                                    <Select value={item.productId} onValueChange={(value) => updateDraft(index, { productId: value })}>
+                                        <SelectTrigger className="h-9">
+                                            <SelectValue placeholder="Selecione" />
+                                        </SelectTrigger>
+                                        <SelectContent>
+                                            {products.map(p => (
+                                                <SelectItem key={p.id} value={p.id.toString()}>{p.name}</SelectItem>
+                                            ))}
+                                        </SelectContent>
+                                    </Select>
+                                </div>
+                                <div className="grid gap-1">
+                                    {index === 0 && <Label className="text-xs">Quantidade</Label>}
+                                    <Input type="number" min="1" className="h-9" value={item.quantity} onChange={(e) => updateDraft(index, { quantity: e.target.value })} />
+                                </div>
+                                <div className="grid gap-1">
+                                    {index === 0 && <Label className="text-xs">Custo (R$)</Label>}
+                                    <Input type="number" step="0.01" min="0" className="h-9" value={item.unitCost} onChange={(e) => updateDraft(index, { unitCost: e.target.value })} />
+                                </div>
+                                <Button
+                                    variant="ghost"
+                                    size="icon"
+                                    className="h-9 w-9"
+                                    disabled={draftItems.length === 1}
+                                    onClick={() => setDraftItems(items => items.filter((_, i) => i !== index))}
+                                >
+                                    <Trash2 className="h-4 w-4" />
+                                </Button>
+                            </div>
+                        ))}
+                        <Button
+                            variant="outline"
+                            size="sm"
+                            className="gap-2 w-fit"
+                            onClick={() => setDraftItems(items => [...items, { productId: "", quantity: "", unitCost: "" }])}
+                        >
+                            <Plus className="h-4 w-4" />
+                            Adicionar produto
+                        </Button>
+                    </div>
+                    <DialogFooter>
+                        <Button onClick={handleCreateOrder}>Registrar Pedido</Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+
+            {/* Detalhes e recebimento */}
+            <Dialog open={details !== null} onOpenChange={(open) => !open && setDetails(null)}>
+                <DialogContent className="max-w-2xl">
+                    <DialogHeader>
+                        <DialogTitle>Pedido #{details?.order.id} • {details?.supplier.name}</DialogTitle>
+                        <DialogDescription>
+                            {details && STATUS_LABELS[details.order.status as PurchaseStatus]}
+                            {details?.order.notes && ` • ${details.order.notes}`}
+                        </DialogDescription>
+                    </DialogHeader>
+                    <div className="rounded-lg border border-border/60 overflow-hidden">
+                        <table className="w-full">
+                            <thead>
+                                <tr className="bg-muted/50 text-left text-xs font-semibold text-muted-foreground">
+                                    <th className="px-3 py-2">Produto</th>
+                                    <th className="px-3 py-2 text-right">Pedido</th>
+                                    <th className="px-3 py-2 text-right">Recebido</th>
+                                    {canChange && <th className="px-3 py-2 text-right">Receber</th>}
+                                    <th className="px-3 py-2 text-right">Custo (R$)</th>
+                                </tr>
+                            </thead>
+                            <tbody className="divide-y divide-border">
+                                {details?.items.map(({ item, product_name }) => {
+                                    const pending = item.quantity - item.received_quantity;
+                                    const entry = receiving[item.id];
+                                    return (
+                                        <tr key={item.id}>
+                                            <td className="px-3 py-1.5 text-sm">{product_name}</td>
+                                            <td className="px-3 py-1.5 text-sm text-right">{item.quantity}</td>
+                                            <td className="px-3 py-1.5 text-sm text-right">{item.received_quantity}</td>
+                                            {canChange && (
+                                                <td className="px-3 py-1.5 text-right">
+                                                    <Input
+                                                        type="number"
+                                                        min="0"
+                                                        max={pending}
+                                                        className="h-8 w-20 ml-auto text-right"
+                                                        disabled={pending === 0}
+                                                        value={entry?.quantity ?? ""}
+                                                        onChange={(e) => setReceiving(r => ({ ...r, [item.id]: { ...entry, quantity: e.target.value } }))}
+                                                    />
+                                                </td>
+                                            )}
+                                            <td className="px-3 py-1.5 text-right">
+                                                {canChange && pending > 0 && item.received_quantity === 0 ? (
+                                                    <Input
+                                                        type="number"
+                                                        step="0.01"
+                                                        min="0"
+                                                        className="h-8 w-24 ml-auto text-right"
+                                                        value={entry?.unitCost ?? ""}
+                                                        onChange={(e) => setReceiving(r => ({ ...r, [item.id]: { ...entry, unitCost: e.target.value } }))}
+                                                    />
+                                                ) : (
+                                                    <span className="text-sm">{formatCurrency(item.unit_cost)}</span>
+                                                )}
+                                            </td>
+                                        </tr>
+                                    );
+                                })}
+                            </tbody>
+                        </table>
+                    </div>
+                    <p className="text-sm text-right">
+                        Total: <span className="font-semibold">{details && formatCurrency(details.total)}</span>
+                    </p>
+                    {canChange && (
+                        <DialogFooter className="gap-2">
+                            <Button variant="outline" className="gap-2 text-destructive hover:text-destructive" onClick={handleCancel}>
+                                <Ban className="h-4 w-4" />
+                                Cancelar Pedido
+                            </Button>
+                            <Button className="gap-2" onClick={handleReceive}>
+                                <PackageCheck className="h-4 w-4" />
+                                Registrar Recebimento
+                            </Button>
+                        </DialogFooter>
+                    )}
+                </DialogContent>
+            </Dialog>
+
+            {/* Fornecedor */}
+            <Dialog open={isSupplierOpen} onOpenChange={setIsSupplierOpen}>
+                <DialogContent className="sm:max-w-[425px]">
+                    <DialogHeader>
+                        <DialogTitle>{editingSupplierId === null ? "Novo Fornecedor" : "Editar Fornecedor"}</DialogTitle>
+                        <DialogDescription>Dados de contato de quem fornece os produtos.</DialogDescription>
+                    </DialogHeader>
+                    <div className="grid gap-4 py-4">
+                        <div className="grid gap-2">
+                            <Label htmlFor="supplier-name">Nome</Label>
+                            <Input id="supplier-name" value={supplierForm.name} onChange={(e) => setSupplierForm({ ...supplierForm, name: e.target.value })} />
+                        </div>
+                        <div className="grid grid-cols-2 gap-4">
+                            <div className="grid gap-2">
+                                <Label htmlFor="supplier-document">CNPJ/CPF</Label>
+                                <Input id="supplier-document" value={supplierForm.document} onChange={(e) => setSupplierForm({ ...supplierForm, document: e.target.value })} />
+                            </div>
+                            <div className="grid gap-2">
+                                <Label htmlFor="supplier-phone">Telefone</Label>
+                                <Input id="supplier-phone" value={supplierForm.phone} onChange={(e) => setSupplierForm({ ...supplierForm, phone: e.target.value })} />
+                            </div>
+                        </div>
+                        <div className="grid gap-2">
+                            <Label htmlFor="supplier-email">E-mail</Label>
+                            <Input id="supplier-email" value={supplierForm.email} onChange={(e) => setSupplierForm({ ...supplierForm, email: e.target.value })} />
+                        </div>
+                        <div className="grid gap-2">
+                            <Label htmlFor="supplier-notes">Observações</Label>
+                            <Input id="supplier-notes" value={supplierForm.notes} onChange={(e) => setSupplierForm({ ...supplierForm, notes: e.target.value })} />
+                        </div>
+                    </div>
+                    <DialogFooter>
+                        <Button onClick={handleSaveSupplier}>Salvar</Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+
+            <ConfirmDialog />
+        </div>
+    );
+};
+
+export default Compras;
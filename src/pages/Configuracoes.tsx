@@ -19,6 +19,10 @@ import {
     Info,
     Tags,
     Building2,
+    DatabaseBackup,
+    Upload,
+    PackageOpen,
+    Scale,
 } from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
@@ -49,6 +53,10 @@ import {
     loadCompanySettings,
     saveCompanySettings,
 } from "@/lib/companySettings";
+import type { ArchiveManifest, BackupFile, EncryptedDatabase, EncryptionStatus, ExportedFile, RestoredBackup, StockDiscrepancy } from "@/lib/bindings";
+import { downloadExportedFile, fileToBase64 } from "@/lib/utils/download";
+import { errorMessage } from "@/lib/utils/errors";
+import { formatNumber } from "@/lib/utils/format";
 
 interface User {
     id: number;
@@ -68,6 +76,17 @@ interface PaymentMethod {
     name: string;
 }
 
+interface Setting {
+    key: string;
+    value: string;
+}
+
+const BACKUP_DEFAULTS: Record<string, string> = {
+    backup_interval_hours: "24",
+    backup_keep_daily: "7",
+    backup_keep_weekly: "4",
+};
+
 interface Category {
     id: number;
     name: string;
@@ -79,6 +98,22 @@ const Configuracoes = () => {
     const [shippingMethods, setShippingMethods] = useState<ShippingMethod[]>([]);
     const [paymentMethods, setPaymentMethods] = useState<PaymentMethod[]>([]);
     const [categories, setCategories] = useState<Category[]>([]);
+    const [backups, setBackups] = useState<BackupFile[]>([]);
+    const [backupSchedule, setBackupSchedule] = useState<Record<string, string>>(BACKUP_DEFAULTS);
+    const [isBackingUp, setIsBackingUp] = useState(false);
+    const [restoringBackup, setRestoringBackup] = useState<BackupFile | null>(null);
+    const [encryption, setEncryption] = useState<EncryptionStatus | null>(null);
+    const [isEncryptOpen, setIsEncryptOpen] = useState(false);
+    const [isEncrypting, setIsEncrypting] = useState(false);
+    const [adminPassword, setAdminPassword] = useState("");
+    const [dbPassphrase, setDbPassphrase] = useState("");
+    const [dbPassphraseConfirm, setDbPassphraseConfirm] = useState("");
+    const [isArchiveImportOpen, setIsArchiveImportOpen] = useState(false);
+    const [isArchiveExportOpen, setIsArchiveExportOpen] = useState(false);
+    const [archiveFile, setArchiveFile] = useState<File | null>(null);
+    const [isArchiving, setIsArchiving] = useState(false);
+    const [stockDiscrepancies, setStockDiscrepancies] = useState<StockDiscrepancy[] | null>(null);
+    const [stockAdminPassword, setStockAdminPassword] = useState("");
 
     const [isNewShippingOpen, setIsNewShippingOpen] = useState(false);
     const [isNewPaymentOpen, setIsNewPaymentOpen] = useState(false);
@@ -127,21 +162,168 @@ const Configuracoes = () => {
 
     const loadData = async () => {
         try {
-            const [s, pay, u, c] = await Promise.all([
+            const [s, pay, u, c, b, settings, enc] = await Promise.all([
                 invoke<ShippingMethod[]>("get_shipping_methods"),
                 invoke<PaymentMethod[]>("get_payment_methods"),
                 invoke<User[]>("get_users"),
-                invoke<Category[]>("get_categories")
+                invoke<Category[]>("get_categories"),
+                invoke<BackupFile[]>("list_backups"),
+                invoke<Setting[]>("get_settings"),
+                invoke<EncryptionStatus>("get_encryption_status")
             ]);
             setShippingMethods(s);
             setPaymentMethods(pay);
             setUsers(u);
             setCategories(c);
+            setBackups(b);
+            setEncryption(enc);
+            const schedule = { ...BACKUP_DEFAULTS };
+            settings.forEach((setting) => {
+                if (setting.key in schedule) schedule[setting.key] = setting.value;
+            });
+            setBackupSchedule(schedule);
         } catch (err) {
             console.error(err);
         }
     };
 
+    const handleCreateBackup = async () => {
+        setIsBackingUp(true);
+        try {
+            const backup = await invoke<BackupFile>("create_backup_now");
+            setBackups((prev) => [backup, ...prev]);
+            toast.success("Backup criado com sucesso!");
+        } catch (err) {
+            toast.error("Erro ao criar backup: " + errorMessage(err));
+        } finally {
+            setIsBackingUp(false);
+        }
+    };
+
+    const handleRestoreBackup = async () => {
+        if (!restoringBackup) return;
+        const user = JSON.parse(localStorage.getItem("user") || "{}");
+        setIsBackingUp(true);
+        try {
+            const restored = await invoke<RestoredBackup>("restore_backup", {
+                name: restoringBackup.name,
+                username: user.username,
+                password: adminPassword,
+            });
+            setRestoringBackup(null);
+            setAdminPassword("");
+            toast.success(
+                `Backup de ${new Date(restored.created_at).toLocaleString("pt-BR")} restaurado (versão ${restored.schema_version} do banco). Cópia anterior: ${restored.safety_backup.name}`
+            );
+            // Todas as telas passam a ler o banco restaurado
+            setTimeout(() => window.location.reload(), 1500);
+        } catch (err) {
+            toast.error("Erro ao restaurar backup: " + errorMessage(err));
+            setIsBackingUp(false);
+        }
+    };
+
+    const handleEncryptDatabase = async () => {
+        if (dbPassphrase !== dbPassphraseConfirm) {
+            toast.error("As senhas do banco não conferem");
+            return;
+        }
+        const user = JSON.parse(localStorage.getItem("user") || "{}");
+        setIsEncrypting(true);
+        try {
+            const result = await invoke<EncryptedDatabase>("encrypt_database", {
+                username: user.username,
+                password: adminPassword,
+                passphrase: dbPassphrase,
+            });
+            setIsEncryptOpen(false);
+            setAdminPassword(""); setDbPassphrase(""); setDbPassphraseConfirm("");
+            loadData();
+            toast.success(`Banco criptografado! ${result.encrypted_backups.length} backup(s) também foram criptografados.`);
+            if (result.skipped_backups.length > 0) {
+                toast.error(`Não foi possível criptografar: ${result.skipped_backups.join(", ")}`);
+            }
+        } catch (err) {
+            toast.error("Erro ao criptografar o banco: " + errorMessage(err));
+        } finally {
+            setIsEncrypting(false);
+        }
+    };
+
+    const handleExportArchive = async () => {
+        const user = JSON.parse(localStorage.getItem("user") || "{}");
+        setIsArchiving(true);
+        try {
+            const file = await invoke<ExportedFile>("export_archive", {
+                username: user.username,
+                password: adminPassword,
+            });
+            setIsArchiveExportOpen(false);
+            setAdminPassword("");
+            await downloadExportedFile(file);
+            toast.success("Pacote de migração gerado!");
+        } catch (err) {
+            toast.error("Erro ao gerar o pacote: " + errorMessage(err));
+        } finally {
+            setIsArchiving(false);
+        }
+    };
+
+    const handleImportArchive = async () => {
+        if (!archiveFile) return;
+        const user = JSON.parse(localStorage.getItem("user") || "{}");
+        setIsArchiving(true);
+        try {
+            const manifest = await invoke<ArchiveManifest>("import_archive", {
+                content: await fileToBase64(archiveFile),
+                username: user.username,
+                password: adminPassword,
+            });
+            setIsArchiveImportOpen(false);
+            setAdminPassword("");
+            toast.success(`Dados importados (${manifest.counts.clients ?? 0} cliente(s), ${manifest.counts.sales ?? 0} venda(s)). Entre novamente com os usuários do pacote.`);
+            localStorage.removeItem("user");
+            setTimeout(() => window.location.reload(), 1500);
+        } catch (err) {
+            toast.error("Erro ao importar o pacote: " + errorMessage(err));
+            setIsArchiving(false);
+        }
+    };
+
+    const handleCheckStock = async () => {
+        try {
+            setStockDiscrepancies(await invoke<StockDiscrepancy[]>("check_stock_consistency"));
+        } catch (err) {
+            toast.error("Erro ao conferir o estoque: " + errorMessage(err));
+        }
+    };
+
+    const handleRecomputeStock = async () => {
+        const user = JSON.parse(localStorage.getItem("user") || "{}");
+        try {
+            const fixed = await invoke<StockDiscrepancy[]>("recompute_stock", {
+                username: user.username,
+                password: stockAdminPassword,
+            });
+            setStockAdminPassword("");
+            setStockDiscrepancies([]);
+            toast.success(`Estoque de ${fixed.length} produto(s) recalculado.`);
+        } catch (err) {
+            toast.error("Erro ao recalcular o estoque: " + errorMessage(err));
+        }
+    };
+
+    const handleSaveBackupSchedule = async () => {
+        try {
+            for (const [key, value] of Object.entries(backupSchedule)) {
+                await invoke("save_setting", { key, value });
+            }
+            toast.success("Agenda de backups salva!");
+        } catch (err) {
+            toast.error("Erro ao salvar agenda de backups: " + errorMessage(err));
+        }
+    };
+
     const handleCreateUser = async () => {
         try {
             await invoke("create_user", {
@@ -155,7 +337,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Usuário criado com sucesso!");
         } catch (err) {
-            toast.error("Erro ao criar usuário: " + err);
+            toast.error("Erro ao criar usuário: " + errorMessage(err));
         }
     };
 
@@ -170,7 +352,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Usuário removido com sucesso!");
         } catch (err) {
-            toast.error("Erro ao remover usuário: " + err);
+            toast.error("Erro ao remover usuário: " + errorMessage(err));
         }
     };
 
@@ -182,7 +364,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Forma de envio criada!");
         } catch (err) {
-            toast.error("Erro ao criar forma de envio: " + err);
+            toast.error("Erro ao criar forma de envio: " + errorMessage(err));
         }
     };
 
@@ -194,7 +376,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Forma de pagamento criada!");
         } catch (err) {
-            toast.error("Erro ao criar forma de pagamento: " + err);
+            toast.error("Erro ao criar forma de pagamento: " + errorMessage(err));
         }
     };
 
@@ -206,7 +388,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Categoria criada com sucesso!");
         } catch (err) {
-            toast.error("Erro ao criar categoria: " + err);
+            toast.error("Erro ao criar categoria: " + errorMessage(err));
         }
     };
 
@@ -222,7 +404,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Frete atualizado!");
         } catch (err) {
-            toast.error("Erro ao atualizar frete: " + err);
+            toast.error("Erro ao atualizar frete: " + errorMessage(err));
         }
     };
 
@@ -237,7 +419,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Frete removido!");
         } catch (err) {
-            toast.error("Erro ao remover frete: " + err);
+            toast.error("Erro ao remover frete: " + errorMessage(err));
         }
     };
 
@@ -249,7 +431,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Pagamento atualizado!");
         } catch (err) {
-            toast.error("Erro ao atualizar pagamento: " + err);
+            toast.error("Erro ao atualizar pagamento: " + errorMessage(err));
         }
     };
 
@@ -264,7 +446,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Pagamento removido!");
         } catch (err) {
-            toast.error("Erro ao remover pagamento: " + err);
+            toast.error("Erro ao remover pagamento: " + errorMessage(err));
         }
     };
 
@@ -280,7 +462,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Categoria atualizada!");
         } catch (err) {
-            toast.error("Erro ao atualizar categoria: " + err);
+            toast.error("Erro ao atualizar categoria: " + errorMessage(err));
         }
     };
 
@@ -295,7 +477,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Categoria removida!");
         } catch (err) {
-            toast.error("Erro ao remover categoria: " + err);
+            toast.error("Erro ao remover categoria: " + errorMessage(err));
         }
     };
 
@@ -314,7 +496,7 @@ const Configuracoes = () => {
             loadData();
             toast.success("Usuário atualizado com sucesso!");
         } catch (err) {
-            toast.error("Erro ao atualizar usuário: " + err);
+            toast.error("Erro ao atualizar usuário: " + errorMessage(err));
         }
     };
 
@@ -326,7 +508,7 @@ const Configuracoes = () => {
             </div>
 
             <Tabs defaultValue="envio" className="w-full">
-                <TabsList className="grid w-full grid-cols-6 max-w-[1020px]">
+                <TabsList className="grid w-full grid-cols-7 max-w-[1180px]">
                     <TabsTrigger value="empresa" className="gap-2">
                         <Building2 className="h-4 w-4" />
                         Empresa
@@ -347,6 +529,10 @@ const Configuracoes = () => {
                         <Users className="h-4 w-4" />
                         Usuários
                     </TabsTrigger>
+                    <TabsTrigger value="backups" className="gap-2">
+                        <DatabaseBackup className="h-4 w-4" />
+                        Backups
+                    </TabsTrigger>
                     <TabsTrigger value="atualizacoes" className="gap-2">
                         <RefreshCw className="h-4 w-4" />
                         Atualizações
@@ -818,6 +1004,198 @@ const Configuracoes = () => {
                     </Card>
                 </TabsContent>
 
+                {/* Aba Backups */}
+                <TabsContent value="backups" className="mt-6">
+                    <div className="max-w-2xl space-y-6">
+                        <Card className="card-shadow border-border/60">
+                            <CardHeader>
+                                <CardTitle className="text-base flex items-center gap-2">
+                                    <DatabaseBackup className="h-4 w-4 text-primary" />
+                                    Backup Automático
+                                </CardTitle>
+                            </CardHeader>
+                            <CardContent className="space-y-4">
+                                <p className="text-xs text-muted-foreground">
+                                    O banco é copiado e verificado no intervalo abaixo e ao fechar o sistema.
+                                    Backups manuais nunca são apagados pela rotação.
+                                </p>
+                                <div className="grid grid-cols-3 gap-4">
+                                    <div className="space-y-2">
+                                        <Label>Intervalo (horas)</Label>
+                                        <Input
+                                            type="number"
+                                            min={1}
+                                            value={backupSchedule.backup_interval_hours}
+                                            onChange={(e) => setBackupSchedule({ ...backupSchedule, backup_interval_hours: e.target.value })}
+                                        />
+                                    </div>
+                                    <div className="space-y-2">
+                                        <Label>Manter diários</Label>
+                                        <Input
+                                            type="number"
+                                            min={0}
+                                            value={backupSchedule.backup_keep_daily}
+                                            onChange={(e) => setBackupSchedule({ ...backupSchedule, backup_keep_daily: e.target.value })}
+                                        />
+                                    </div>
+                                    <div className="space-y-2">
+                                        <Label>Manter semanais</Label>
+                                        <Input
+                                            type="number"
+                                            min={0}
+                                            value={backupSchedule.backup_keep_weekly}
+                                            onChange={(e) => setBackupSchedule({ ...backupSchedule, backup_keep_weekly: e.target.value })}
+                                        />
+                                    </div>
+                                </div>
+                                <div className="flex gap-2">
+                                    <Button variant="outline" size="sm" onClick={handleSaveBackupSchedule}>
+                                        Salvar Agenda
+                                    </Button>
+                                    <Button size="sm" className="gap-2" onClick={handleCreateBackup} disabled={isBackingUp}>
+                                        <DatabaseBackup className={`h-4 w-4 ${isBackingUp ? "animate-pulse" : ""}`} />
+                                        Fazer Backup Agora
+                                    </Button>
+                                </div>
+                            </CardContent>
+                        </Card>
+
+                        <Card className="card-shadow border-border/60">
+                            <CardHeader>
+                                <CardTitle className="text-base">Backups Salvos</CardTitle>
+                            </CardHeader>
+                            <CardContent>
+                                {backups.length === 0 ? (
+                                    <p className="text-sm text-muted-foreground">Nenhum backup encontrado.</p>
+                                ) : (
+                                    <ul className="divide-y rounded-lg border">
+                                        {backups.map((backup) => (
+                                            <li key={backup.name} className="flex items-center justify-between gap-3 p-3 text-sm">
+                                                <span className="truncate font-medium">{backup.name}</span>
+                                                <div className="flex shrink-0 items-center gap-3">
+                                                    <span className="text-xs text-muted-foreground">
+                                                        {new Date(backup.created_at).toLocaleString("pt-BR")} • {formatNumber(backup.size / 1024, 0)} KB
+                                                    </span>
+                                                    <Button
+                                                        variant="ghost"
+                                                        size="sm"
+                                                        onClick={() => setRestoringBackup(backup)}
+                                                        disabled={isBackingUp}
+                                                    >
+                                                        Restaurar
+                                                    </Button>
+                                                </div>
+                                            </li>
+                                        ))}
+                                    </ul>
+                                )}
+                            </CardContent>
+                        </Card>
+
+                        {encryption?.supported && (
+                            <Card className="card-shadow border-border/60">
+                                <CardHeader>
+                                    <CardTitle className="text-base flex items-center gap-2">
+                                        <Lock className="h-4 w-4 text-primary" />
+                                        Criptografia do Banco
+                                    </CardTitle>
+                                </CardHeader>
+                                <CardContent className="space-y-4">
+                                    {encryption.encrypted ? (
+                                        <div className="flex items-center gap-3 text-sm text-muted-foreground">
+                                            <CheckCircle2 className="h-4 w-4 text-green-500" />
+                                            O banco e os backups estão criptografados. A senha é pedida ao abrir o sistema.
+                                        </div>
+                                    ) : (
+                                        <>
+                                            <p className="text-xs text-muted-foreground">
+                                                Protege os dados dos clientes (LGPD) caso o computador seja compartilhado.
+                                                Sem a senha do banco não é possível abrir o sistema nem os backups: guarde-a em local seguro.
+                                            </p>
+                                            <Button size="sm" className="gap-2" onClick={() => setIsEncryptOpen(true)}>
+                                                <Lock className="h-4 w-4" />
+                                                Criptografar Banco
+                                            </Button>
+                                        </>
+                                    )}
+                                </CardContent>
+                            </Card>
+                        )}
+
+                        <Card className="card-shadow border-border/60">
+                            <CardHeader>
+                                <CardTitle className="text-base flex items-center gap-2">
+                                    <PackageOpen className="h-4 w-4 text-primary" />
+                                    Migração para Outro Computador
+                                </CardTitle>
+                            </CardHeader>
+                            <CardContent className="space-y-4">
+                                <p className="text-xs text-muted-foreground">
+                                    Gera um pacote com todos os dados (clientes, produtos, vendas, usuários e configurações).
+                                    A importação só pode ser feita em uma instalação nova, ainda sem cadastros.
+                                </p>
+                                <div className="flex items-center gap-2">
+                                    <Button size="sm" variant="outline" className="gap-2" onClick={() => setIsArchiveExportOpen(true)} disabled={isArchiving}>
+                                        <Download className="h-4 w-4" />
+                                        Exportar Pacote
+                                    </Button>
+                                    <Button size="sm" variant="outline" className="gap-2" onClick={() => setIsArchiveImportOpen(true)} disabled={isArchiving}>
+                                        <Upload className="h-4 w-4" />
+                                        Importar Pacote
+                                    </Button>
+                                </div>
+                            </CardContent>
+                        </Card>
+
+                        <Card className="card-shadow border-border/60">
+                            <CardHeader>
+                                <CardTitle className="text-base flex items-center gap-2">
+                                    <Scale className="h-4 w-4 text-primary" />
+                                    Conferência de Estoque
+                                </CardTitle>
+                            </CardHeader>
+                            <CardContent className="space-y-4">
+                                <p className="text-xs text-muted-foreground">
+                                    Compara o estoque de cada produto com a soma das suas movimentações (vendas, compras e ajustes).
+                                </p>
+                                <Button size="sm" variant="outline" className="gap-2" onClick={handleCheckStock}>
+                                    <Scale className="h-4 w-4" />
+                                    Conferir Estoque
+                                </Button>
+                                {stockDiscrepancies && stockDiscrepancies.length === 0 && (
+                                    <div className="flex items-center gap-3 text-sm text-muted-foreground">
+                                        <CheckCircle2 className="h-4 w-4 text-green-500" />
+                                        O estoque confere com o histórico de movimentações.
+                                    </div>
+                                )}
+                                {stockDiscrepancies && stockDiscrepancies.length > 0 && (
+                                    <>
+                                        <ul className="divide-y divide-border rounded-lg border border-border/60">
+                                            {stockDiscrepancies.map((d) => (
+                                                <li key={d.product_id} className="flex items-center justify-between px-3 py-2 text-sm">
+                                                    <span>{d.name}</span>
+                                                    <span className="text-muted-foreground">
+                                                        Estoque {d.stock_quantity} • Movimentações {d.movements_total}
+                                                    </span>
+                                                </li>
+                                            ))}
+                                        </ul>
+                                        <div className="flex items-end gap-2">
+                                            <div className="space-y-1 flex-1">
+                                                <Label className="text-xs">Sua senha de administrador</Label>
+                                                <Input type="password" value={stockAdminPassword} onChange={(e) => setStockAdminPassword(e.target.value)} />
+                                            </div>
+                                            <Button size="sm" onClick={handleRecomputeStock} disabled={!stockAdminPassword}>
+                                                Recalcular pelo Histórico
+                                            </Button>
+                                        </div>
+                                    </>
+                                )}
+                            </CardContent>
+                        </Card>
+                    </div>
+                </TabsContent>
+
                 {/* Aba Atualizações */}
                 <TabsContent value="atualizacoes" className="mt-6">
                     <div className="max-w-2xl space-y-6">
@@ -916,6 +1294,122 @@ const Configuracoes = () => {
                 </TabsContent>
             </Tabs>
 
+            {/* Encrypt Database Dialog */}
+            <Dialog open={isEncryptOpen} onOpenChange={setIsEncryptOpen}>
+                <DialogContent>
+                    <DialogHeader>
+                        <DialogTitle>Criptografar Banco</DialogTitle>
+                        <DialogDescription>
+                            Se a senha do banco for perdida, os dados não poderão ser recuperados.
+                        </DialogDescription>
+                    </DialogHeader>
+                    <div className="space-y-4 py-4">
+                        <div className="space-y-2">
+                            <Label>Sua senha de administrador</Label>
+                            <Input type="password" value={adminPassword} onChange={(e) => setAdminPassword(e.target.value)} />
+                        </div>
+                        <div className="space-y-2">
+                            <Label>Senha do banco (mínimo 8 caracteres)</Label>
+                            <Input type="password" value={dbPassphrase} onChange={(e) => setDbPassphrase(e.target.value)} />
+                        </div>
+                        <div className="space-y-2">
+                            <Label>Confirme a senha do banco</Label>
+                            <Input type="password" value={dbPassphraseConfirm} onChange={(e) => setDbPassphraseConfirm(e.target.value)} />
+                        </div>
+                    </div>
+                    <DialogFooter>
+                        <Button variant="outline" onClick={() => setIsEncryptOpen(false)}>Cancelar</Button>
+                        <Button onClick={handleEncryptDatabase} disabled={isEncrypting || !adminPassword || !dbPassphrase}>
+                            {isEncrypting ? "Criptografando..." : "Criptografar"}
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+
+            {/* Restore Backup Dialog */}
+            <Dialog open={restoringBackup !== null} onOpenChange={(open) => {
+                if (!open) { setRestoringBackup(null); setAdminPassword(""); }
+            }}>
+                <DialogContent>
+                    <DialogHeader>
+                        <DialogTitle>Restaurar Backup</DialogTitle>
+                        <DialogDescription>
+                            {restoringBackup && `Os dados atuais serão substituídos pelo backup de ${new Date(restoringBackup.created_at).toLocaleString("pt-BR")}. Uma cópia do banco atual é salva antes da troca.`}
+                        </DialogDescription>
+                    </DialogHeader>
+                    <div className="space-y-4 py-4">
+                        <div className="space-y-2">
+                            <Label>Sua senha de administrador</Label>
+                            <Input type="password" value={adminPassword} onChange={(e) => setAdminPassword(e.target.value)} />
+                        </div>
+                    </div>
+                    <DialogFooter>
+                        <Button variant="outline" onClick={() => setRestoringBackup(null)}>Cancelar</Button>
+                        <Button variant="destructive" onClick={handleRestoreBackup} disabled={isBackingUp || !adminPassword}>
+                            {isBackingUp ? "Restaurando..." : "Restaurar"}
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+
+            {/* Export Archive Dialog */}
+            <Dialog open={isArchiveExportOpen} onOpenChange={(open) => {
+                setIsArchiveExportOpen(open);
+                if (!open) setAdminPassword("");
+            }}>
+                <DialogContent>
+                    <DialogHeader>
+                        <DialogTitle>Exportar Pacote de Migração</DialogTitle>
+                        <DialogDescription>
+                            O pacote contém todos os dados dos clientes. A exportação fica registrada na auditoria.
+                        </DialogDescription>
+                    </DialogHeader>
+                    <div className="space-y-4 py-4">
+                        <div className="space-y-2">
+                            <Label>Sua senha de administrador</Label>
+                            <Input type="password" value={adminPassword} onChange={(e) => setAdminPassword(e.target.value)} />
+                        </div>
+                    </div>
+                    <DialogFooter>
+                        <Button variant="outline" onClick={() => setIsArchiveExportOpen(false)}>Cancelar</Button>
+                        <Button onClick={handleExportArchive} disabled={isArchiving || !adminPassword}>
+                            {isArchiving ? "Exportando..." : "Exportar"}
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+
+            {/* Import Archive Dialog */}
+            <Dialog open={isArchiveImportOpen} onOpenChange={(open) => {
+                setIsArchiveImportOpen(open);
+                if (!open) { setArchiveFile(null); setAdminPassword(""); }
+            }}>
+                <DialogContent>
+                    <DialogHeader>
+                        <DialogTitle>Importar Pacote de Migração</DialogTitle>
+                        <DialogDescription>
+                            Os usuários, categorias e formas de pagamento desta instalação serão substituídos pelos do pacote.
+                        </DialogDescription>
+                    </DialogHeader>
+                    <div className="space-y-4 py-4">
+                        <div className="space-y-2">
+                            <Label>Pacote (.zip)</Label>
+                            <Input type="file" accept=".zip" onChange={(e) => setArchiveFile(e.target.files?.[0] ?? null)} />
+                        </div>
+                        <div className="space-y-2">
+                            <Label>Sua senha de administrador</Label>
+                            <Input type="password" value={adminPassword} onChange={(e) => setAdminPassword(e.target.value)} />
+                        </div>
+                    </div>
+                    <DialogFooter>
+                        <Button variant="outline" onClick={() => setIsArchiveImportOpen(false)}>Cancelar</Button>
+                        <Button onClick={handleImportArchive} disabled={isArchiving || !archiveFile || !adminPassword}>
+                            {isArchiving ? "Importando..." : "Importar"}
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+
             {/* Edit Shipping Dialog */}
             <Dialog open={isEditShippingOpen} onOpenChange={setIsEditShippingOpen}>
                 <DialogContent>
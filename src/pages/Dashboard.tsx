@@ -14,53 +14,21 @@ import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { StatusBadge } from "@/components/StatusBadge";
 import { Link } from "react-router-dom";
-
-interface DashboardData {
-  revenue: string;
-  revenue_change: string;
-  sales_count: number;
-  sales_change: string;
-  client_count: number;
-  client_change: string;
-  alerts: number;
-}
-
-interface Product {
-  id: number;
-  name: string;
-  price: number;
-  stock_quantity: number;
-  category: string;
-}
-
-interface ExpiringGallon {
-  id: number;
-  client_name: string;
-  brand: string;
-  expiration_date: string;
-}
-
-interface Sale {
-  id: number;
-  client_id: number;
-  items: string;
-  total: number;
-  created_at: string;
-  // Mock client name since we don't have the JOIN yet
-  client_name?: string;
-}
+import type { DashboardStats, ExpiringGallon, Product, RecentSale } from "@/lib/bindings";
+import { formatChange, formatCurrency, formatDelta } from "@/lib/utils/format";
 
 const Dashboard = () => {
-  const [stats, setStats] = useState<DashboardData>({
-    revenue: "R$ 0,00",
-    revenue_change: "+0%",
+  const [stats, setStats] = useState<DashboardStats>({
+    revenue: 0,
+    revenue_yesterday: 0,
+    revenue_change: null,
     sales_count: 0,
-    sales_change: "+0",
+    sales_yesterday: 0,
+    sales_change: 0,
     client_count: 0,
-    client_change: "+0",
     alerts: 0,
   });
-  const [recentSales, setRecentSales] = useState<Sale[]>([]);
+  const [recentSales, setRecentSales] = useState<RecentSale[]>([]);
   const [popularProducts, setPopularProducts] = useState<Product[]>([]);
   const [expiringGallons, setExpiringGallons] = useState<ExpiringGallon[]>([]);
   const [isLoading, setIsLoading] = useState(true);
@@ -72,8 +40,8 @@ const Dashboard = () => {
   const loadDashboard = async () => {
     try {
       const [statsData, salesData, productsData, gallonsData] = await Promise.all([
-        invoke<DashboardData>("get_dashboard_stats"),
-        invoke<Sale[]>("get_recent_sales"),
+        invoke<DashboardStats>("get_dashboard_stats"),
+        invoke<RecentSale[]>("get_recent_sales"),
         invoke<Product[]>("get_popular_products"),
         invoke<ExpiringGallon[]>("get_expiring_gallons")
       ]);
@@ -91,20 +59,20 @@ const Dashboard = () => {
   const statsCards = [
     {
       label: "Vendas Hoje",
-      value: stats.revenue,
-      change: stats.revenue_change,
+      value: formatCurrency(stats.revenue),
+      change: formatChange(stats.revenue_change),
       icon: DollarSign,
     },
     {
       label: "Pedidos Hoje",
       value: stats.sales_count.toString(),
-      change: stats.sales_change,
+      change: formatDelta(stats.sales_change),
       icon: ShoppingCart,
     },
     {
       label: "Total de Clientes",
       value: stats.client_count.toString(),
-      change: stats.client_change,
+      change: `+${stats.client_count}`,
       icon: Users,
     },
     {
@@ -215,7 +183,7 @@ const Dashboard = () => {
                 >
                   <div className="flex-1 min-w-0">
                     <p className="text-sm font-medium text-foreground">
-                      {sale.client_name || `Cliente #${sale.client_id}`}
+                      {sale.client_name}
                     </p>
                     <p className="text-xs text-muted-foreground truncate">
                       {formatItems(sale.items)}
@@ -8,6 +8,7 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "@/components/ui/card";
 import { Label } from "@/components/ui/label";
+import { errorMessage } from "@/lib/utils/errors";
 
 const Login = () => {
     const [username, setUsername] = useState("");
@@ -31,7 +32,7 @@ const Login = () => {
             localStorage.setItem("user", JSON.stringify(user));
             navigate("/dashboard");
         } catch (err: any) {
-            setError(err.toString());
+            setError(errorMessage(err));
         } finally {
             setIsLoading(false);
         }
@@ -9,7 +9,8 @@ import {
     LayoutGrid,
     List,
     Trash2,
-    Edit3
+    Edit3,
+    History
 } from "lucide-react";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
@@ -33,6 +34,10 @@ import {
 } from "@/components/ui/select";
 import { toast } from "@/components/ui/sonner";
 import { useConfirm } from "@/hooks/use-confirm";
+import { ImportDialog } from "@/components/ImportDialog";
+import { ReorderSuggestionsDialog } from "@/components/ReorderSuggestionsDialog";
+import { StockDialog } from "@/components/StockDialog";
+import { errorMessage } from "@/lib/utils/errors";
 
 interface Category {
     id: number;
@@ -46,6 +51,9 @@ interface Product {
     stock_quantity: number;
     category: string;
     category_id: number | null;
+    min_stock: number;
+    reorder_qty: number;
+    cost_price: number;
 }
 
 const Produtos = () => {
@@ -57,11 +65,15 @@ const Produtos = () => {
     const [prodName, setProdName] = useState("");
     const [prodPrice, setProdPrice] = useState("");
     const [prodStock, setProdStock] = useState("");
+    const [prodMinStock, setProdMinStock] = useState("");
+    const [prodReorderQty, setProdReorderQty] = useState("");
+    const [prodCost, setProdCost] = useState("");
     const [prodCategoryId, setProdCategoryId] = useState<string>("");
 
     // Edit states
     const [editingProduct, setEditingProduct] = useState<Product | null>(null);
     const [isEditProductOpen, setIsEditProductOpen] = useState(false);
+    const [stockProduct, setStockProduct] = useState<Product | null>(null);
 
     const { ConfirmDialog, confirm: openConfirm } = useConfirm();
 
@@ -93,14 +105,17 @@ const Produtos = () => {
                 price: parseFloat(prodPrice),
                 stockQuantity: parseInt(prodStock),
                 category: selectedCat?.name || "Geral",
-                categoryId: parseInt(prodCategoryId)
+                categoryId: parseInt(prodCategoryId),
+                minStock: prodMinStock ? parseInt(prodMinStock) : null,
+                reorderQty: prodReorderQty ? parseInt(prodReorderQty) : null,
+                costPrice: prodCost ? parseFloat(prodCost) : null
             });
             setIsNewProductOpen(false);
-            setProdName(""); setProdPrice(""); setProdStock("");
+            setProdName(""); setProdPrice(""); setProdStock(""); setProdMinStock(""); setProdReorderQty(""); setProdCost("");
             loadData();
             toast.success("Produto cadastrado com sucesso!");
         } catch (err) {
-            toast.error("Erro ao criar produto: " + err);
+            toast.error("Erro ao criar produto: " + errorMessage(err));
         }
     };
 
@@ -112,15 +127,17 @@ const Produtos = () => {
                 id: editingProduct.id,
                 name: editingProduct.name,
                 price: parseFloat(editingProduct.price.toString()),
-                stockQuantity: parseInt(editingProduct.stock_quantity.toString()),
                 category: selectedCat?.name || editingProduct.category,
-                categoryId: editingProduct.category_id
+                categoryId: editingProduct.category_id,
+                minStock: editingProduct.min_stock,
+                reorderQty: editingProduct.reorder_qty,
+                costPrice: editingProduct.cost_price
             });
             setIsEditProductOpen(false);
             loadData();
             toast.success("Produto atualizado com sucesso!");
         } catch (err) {
-            toast.error("Erro ao atualizar produto: " + err);
+            toast.error("Erro ao atualizar produto: " + errorMessage(err));
         }
     };
 
@@ -135,7 +152,7 @@ const Produtos = () => {
             loadData();
             toast.success("Produto removido!");
         } catch (err) {
-            toast.error("Erro ao remover produto: " + err);
+            toast.error("Erro ao remover produto: " + errorMessage(err));
         }
     };
 
@@ -146,54 +163,72 @@ const Produtos = () => {
                     <h1 className="text-2xl font-bold text-foreground">Produtos</h1>
                     <p className="text-sm text-muted-foreground">Catálogo e controle de estoque</p>
                 </div>
-                <Dialog open={isNewProductOpen} onOpenChange={setIsNewProductOpen}>
-                    <DialogTrigger asChild>
-                        <Button className="gap-2">
-                            <Plus className="h-4 w-4" />
-                            Novo Produto
-                        </Button>
-                    </DialogTrigger>
-                    <DialogContent className="sm:max-w-[425px]">
-                        <DialogHeader>
-                            <DialogTitle>Novo Produto</DialogTitle>
-                            <DialogDescription>
-                                Adicione um novo item ao catálogo de vendas.
-                            </DialogDescription>
-                        </DialogHeader>
-                        <div className="grid gap-4 py-4">
-                            <div className="grid gap-2">
-                                <Label htmlFor="name">Nome do Produto</Label>
-                                <Input id="name" value={prodName} onChange={(e) => setProdName(e.target.value)} placeholder="Ex: Galão 20L Premium" />
-                            </div>
-                            <div className="grid grid-cols-2 gap-4">
+                <div className="flex items-center gap-2">
+                    <ReorderSuggestionsDialog />
+                    <ImportDialog kind="products" onImported={loadData} />
+                    <Dialog open={isNewProductOpen} onOpenChange={setIsNewProductOpen}>
+                        <DialogTrigger asChild>
+                            <Button className="gap-2">
+                                <Plus className="h-4 w-4" />
+                                Novo Produto
+                            </Button>
+                        </DialogTrigger>
+                        <DialogContent className="sm:max-w-[425px]">
+                            <DialogHeader>
+                                <DialogTitle>Novo Produto</DialogTitle>
+                                <DialogDescription>
+                                    Adicione um novo item ao catálogo de vendas.
+                                </DialogDescription>
+                            </DialogHeader>
+                            <div className="grid gap-4 py-4">
                                 <div className="grid gap-2">
-                                    <Label htmlFor="price">Preço (R$)</Label>
-                                    <Input id="price" type="number" step="0.01" value={prodPrice} onChange={(e) => setProdPrice(e.target.value)} placeholder="18.00" />
+                                    <Label htmlFor="name">Nome do Produto</Label>
+                                    <Input id="name" value={prodName} onChange={(e) => setProdName(e.target.value)} placeholder="Ex: Galão 20L Premium" />
+                                </div>
+                                <div className="grid grid-cols-3 gap-4">
+                                    <div className="grid gap-2">
+                                        <Label htmlFor="price">Preço (R$)</Label>
+                                        <Input id="price" type="number" step="0.01" value={prodPrice} onChange={(e) => setProdPrice(e.target.value)} placeholder="18.00" />
+                                    </div>
+                                    <div className="grid gap-2">
+                                        <Label htmlFor="cost">Custo (R$)</Label>
+                                        <Input id="cost" type="number" step="0.01" min="0" value={prodCost} onChange={(e) => setProdCost(e.target.value)} placeholder="0.00" />
+                                    </div>
+                                    <div className="grid gap-2">
+                                        <Label htmlFor="stock">Estoque Inicial</Label>
+                                        <Input id="stock" type="number" value={prodStock} onChange={(e) => setProdStock(e.target.value)} placeholder="100" />
+                                    </div>
+                                </div>
+                                <div className="grid grid-cols-2 gap-4">
+                                    <div className="grid gap-2">
+                                        <Label htmlFor="min-stock">Estoque Mínimo</Label>
+                                        <Input id="min-stock" type="number" min="0" value={prodMinStock} onChange={(e) => setProdMinStock(e.target.value)} placeholder="10" />
+                                    </div>
+                                    <div className="grid gap-2">
+                                        <Label htmlFor="reorder-qty">Lote de Compra</Label>
+                                        <Input id="reorder-qty" type="number" min="0" value={prodReorderQty} onChange={(e) => setProdReorderQty(e.target.value)} placeholder="0" />
+                                    </div>
                                 </div>
                                 <div className="grid gap-2">
-                                    <Label htmlFor="stock">Estoque Inicial</Label>
-                                    <Input id="stock" type="number" value={prodStock} onChange={(e) => setProdStock(e.target.value)} placeholder="100" />
+                                    <Label htmlFor="category">Categoria</Label>
+                                    <Select value={prodCategoryId} onValueChange={setProdCategoryId}>
+                                        <SelectTrigger>
+                                            <SelectValue />
+                                        </SelectTrigger>
+                                        <SelectContent>
+                                            {categories.map(cat => (
+                                                <SelectItem key={cat.id} value={cat.id.toString()}>{cat.name}</SelectItem>
+                                            ))}
+                                        </SelectContent>
+                                    </Select>
                                 </div>
                             </div>
-                            <div className="grid gap-2">
-                                <Label htmlFor="category">Categoria</Label>
-                                <Select value={prodCategoryId} onValueChange={setProdCategoryId}>
-                                    <SelectTrigger>
-                                        <SelectValue />
-                                    </SelectTrigger>
-                                    <SelectContent>
-                                        {categories.map(cat => (
-                                            <SelectItem key={cat.id} value={cat.id.toString()}>{cat.name}</SelectItem>
-                                        ))}
-                                    </SelectContent>
-                                </Select>
-                            </div>
-                        </div>
-                        <DialogFooter>
-                            <Button onClick={handleCreateProduct}>Cadastrar Produto</Button>
-                        </DialogFooter>
-                    </DialogContent>
-                </Dialog>
+                            <DialogFooter>
+                                <Button onClick={handleCreateProduct}>Cadastrar Produto</Button>
+                            </DialogFooter>
+                        </DialogContent>
+                    </Dialog>
+                </div>
             </div>
 
             <Card className="card-shadow border-border/60">
@@ -242,12 +277,21 @@ const Produtos = () => {
                                             R$ {prod.price.toFixed(2)}
                                         </td>
                                         <td className="px-6 py-4 text-right">
-                                            <span className={`text-sm font-bold ${prod.stock_quantity < 10 ? 'text-destructive' : 'text-foreground'}`}>
+                                            <span className={`text-sm font-bold ${prod.stock_quantity < prod.min_stock ? 'text-destructive' : 'text-foreground'}`}>
                                                 {prod.stock_quantity}
                                             </span>
                                         </td>
                                         <td className="px-6 py-4 text-right">
                                             <div className="flex justify-end gap-2">
+                                                <Button
+                                                    variant="ghost"
+                                                    size="icon"
+                                                    className="h-8 w-8"
+                                                    title="Movimentações de estoque"
+                                                    onClick={() => setStockProduct(prod)}
+                                                >
+                                                    <History className="h-4 w-4" />
+                                                </Button>
                                                 <Button
                                                     variant="ghost"
                                                     size="icon"
@@ -296,7 +340,7 @@ const Produtos = () => {
                                     onChange={(e) => setEditingProduct({ ...editingProduct, name: e.target.value })}
                                 />
                             </div>
-                            <div className="grid grid-cols-2 gap-4">
+                            <div className="grid grid-cols-3 gap-4">
                                 <div className="grid gap-2">
                                     <Label htmlFor="edit-price">Preço (R$)</Label>
                                     <Input
@@ -307,13 +351,60 @@ const Produtos = () => {
                                         onChange={(e) => setEditingProduct({ ...editingProduct, price: parseFloat(e.target.value) || 0 })}
                                     />
                                 </div>
+                                <div className="grid gap-2">
+                                    <Label htmlFor="edit-cost">Custo Médio (R$)</Label>
+                                    <Input
+                                        id="edit-cost"
+                                        type="number"
+                                        step="0.01"
+                                        min="0"
+                                        value={editingProduct.cost_price}
+                                        onChange={(e) => setEditingProduct({ ...editingProduct, cost_price: parseFloat(e.target.value) || 0 })}
+                                        title="Recalculado a cada compra recebida"
+                                    />
+                                </div>
                                 <div className="grid gap-2">
                                     <Label htmlFor="edit-stock">Estoque</Label>
                                     <Input
                                         id="edit-stock"
                                         type="number"
                                         value={editingProduct.stock_quantity}
-                                        onChange={(e) => setEditingProduct({ ...editingProduct, stock_quantity: parseInt(e.target.value) || 0 })}
+                                        disabled
+                                        title="Use as movimentações de estoque para ajustar"
+                                    />
+                                    <Button
+                                        type="button"
+                                        variant="link"
+                                        size="sm"
+                                        className="h-auto p-0 justify-start"
+                                        onClick={() => {
+                                            setIsEditProductOpen(false);
+                                            setStockProduct(editingProduct);
+                                        }}
+                                    >
+                                        Ajustar estoque
+                                    </Button>
+                                </div>
+                            </div>
+                            <div className="grid grid-cols-2 gap-4">
+                                <div className="grid gap-2">
+                                    <Label htmlFor="edit-min-stock">Estoque Mínimo</Label>
+                                    <Input
+                                        id="edit-min-stock"
+                                        type="number"
+                                        min="0"
+                                        value={editingProduct.min_stock}
+                                        onChange={(e) => setEditingProduct({ ...editingProduct, min_stock: parseInt(e.target.value) || 0 })}
+                                    />
+                                </div>
+                                <div className="grid gap-2">
+                                    <Label htmlFor="edit-reorder-qty">Lote de Compra</Label>
+                                    <Input
+                                        id="edit-reorder-qty"
+                                        type="number"
+                                        min="0"
+                                        value={editingProduct.reorder_qty}
+                                        onChange={(e) => setEditingProduct({ ...editingProduct, reorder_qty: parseInt(e.target.value) || 0 })}
                                     />
                                 </div>
                             </div>
@@ -341,6 +432,12 @@ const Produtos = () => {
                 </DialogContent>
             </Dialog>
 
+            <StockDialog
+                product={stockProduct}
+                onOpenChange={(open) => !open && setStockProduct(null)}
+                onAdjusted={loadData}
+            />
+
             <ConfirmDialog />
         </div>
     );
@@ -38,31 +38,36 @@ import {
   Tooltip,
   ResponsiveContainer,
 } from "recharts";
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuLabel,
+  DropdownMenuSeparator,
+  DropdownMenuTrigger,
+} from "@/components/ui/dropdown-menu";
+import type { ExportedFile, PaymentMethod, ProfitabilityReport, ProfitLine, SaleDetails, SalesReport } from "@/lib/bindings";
+import { formatChange, formatCurrency, formatNumber } from "@/lib/utils/format";
+import { downloadExportedFile } from "@/lib/utils/download";
+import { errorMessage } from "@/lib/utils/errors";
 
-interface ReportData {
-  summary: {
-    revenue: { value: string; change: string };
-    sales_count: { value: string; change: string };
-    average_ticket: { value: string; change: string };
-    unique_clients: { value: string; change: string };
-  };
-  chart_data: { date: string; revenue: number }[];
-  sales_list: {
-    id: number;
-    client_name: string;
-    items: string;
-    payment_method: string;
-    total: number;
-    created_at: string;
-  }[];
-}
+type ExportTarget = "sales" | "receivables" | "products" | "clients";
+type ExportFormat = "csv" | "xlsx";
+
+const EXPORT_TARGETS: { target: ExportTarget; label: string }[] = [
+  { target: "sales", label: "Vendas" },
+  { target: "receivables", label: "Contas a receber" },
+  { target: "products", label: "Produtos" },
+  { target: "clients", label: "Clientes" },
+];
 
 const Relatorios = () => {
   const [dateFrom, setDateFrom] = useState(new Date(new Date().setDate(new Date().getDate() - 30)).toISOString().split('T')[0]);
   const [dateTo, setDateTo] = useState(new Date().toISOString().split('T')[0]);
   const [paymentFilter, setPaymentFilter] = useState("todos");
-  const [paymentMethods, setPaymentMethods] = useState<{ id: number, name: string }[]>([]);
-  const [reportData, setReportData] = useState<ReportData | null>(null);
+  const [paymentMethods, setPaymentMethods] = useState<PaymentMethod[]>([]);
+  const [reportData, setReportData] = useState<SalesReport | null>(null);
+  const [profitability, setProfitability] = useState<ProfitabilityReport | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [isFiltersOpen, setIsFiltersOpen] = useState(false);
 
@@ -72,7 +77,7 @@ const Relatorios = () => {
 
   const loadInitialData = async () => {
     try {
-      const methods = await invoke<{ id: number, name: string }[]>("get_payment_methods");
+      const methods = await invoke<PaymentMethod[]>("get_payment_methods");
       setPaymentMethods(methods);
     } catch (e) {
       console.error(e);
@@ -91,12 +96,17 @@ const Relatorios = () => {
       const start = new Date(dateFrom + 'T00:00:00');
       const end = new Date(dateTo + 'T23:59:59');
 
-      const data = await invoke<ReportData>("get_sales_report", {
+      const args = {
         startIso: start.toISOString(),
         endIso: end.toISOString(),
         paymentMethod: paymentFilter
-      });
+      };
+      const [data, profit] = await Promise.all([
+        invoke<SalesReport>("get_sales_report", args),
+        invoke<ProfitabilityReport>("get_profitability_report", args),
+      ]);
       setReportData(data);
+      setProfitability(profit);
     } catch (error) {
       console.error("Erro ao carregar relatório:", error);
     } finally {
@@ -104,9 +114,39 @@ const Relatorios = () => {
     }
   }
 
+  // Mesmo período do relatório (dia local -> ISO UTC)
+  const periodArgs = () => ({
+    startIso: new Date(dateFrom + 'T00:00:00').toISOString(),
+    endIso: new Date(dateTo + 'T23:59:59').toISOString(),
+  });
+
+  const handleExport = async (target: ExportTarget, format: ExportFormat) => {
+    try {
+      let file: ExportedFile;
+      switch (target) {
+        case "sales":
+          file = await invoke<ExportedFile>("export_sales", { ...periodArgs(), paymentMethod: paymentFilter, format });
+          break;
+        case "receivables":
+          file = await invoke<ExportedFile>("export_receivables", { ...periodArgs(), format });
+          break;
+        case "products":
+          file = await invoke<ExportedFile>("export_products", { format });
+          break;
+        case "clients":
+          file = await invoke<ExportedFile>("export_clients", { format });
+          break;
+      }
+      await downloadExportedFile(file);
+      toast.success(`Planilha ${file.file_name} gerada`);
+    } catch (error) {
+      toast.error("Erro ao exportar: " + errorMessage(error));
+    }
+  };
+
   const handlePrint = async (saleId: number) => {
     try {
-      const sale = await invoke<any>("get_sale_details", { id: saleId });
+      const sale = await invoke<SaleDetails>("get_sale_details", { id: saleId });
 
       const pdfBase64 = await invoke<string>("generate_sale_pdf", {
         clientName: sale.client_name,
@@ -141,11 +181,12 @@ const Relatorios = () => {
 
   if (!reportData) return <div className="p-8 text-center">Carregando relatório...</div>;
 
+  const { summary } = reportData;
   const stats = [
-    { label: "Faturamento", ...reportData.summary.revenue, icon: DollarSign },
-    { label: "Total de Vendas", ...reportData.summary.sales_count, icon: ShoppingCart },
-    { label: "Ticket Médio", ...reportData.summary.average_ticket, icon: TrendingUp },
-    { label: "Clientes Únicos", ...reportData.summary.unique_clients, icon: Users },
+    { label: "Faturamento", value: formatCurrency(summary.revenue.value), change: summary.revenue.change, icon: DollarSign },
+    { label: "Total de Vendas", value: formatNumber(summary.sales_count.value), change: summary.sales_count.change, icon: ShoppingCart },
+    { label: "Ticket Médio", value: formatCurrency(summary.average_ticket.value), change: summary.average_ticket.change, icon: TrendingUp },
+    { label: "Clientes Únicos", value: formatNumber(summary.unique_clients.value), change: summary.unique_clients.change, icon: Users },
   ];
 
   return (
@@ -155,10 +196,24 @@ const Relatorios = () => {
           <h1 className="text-2xl font-bold text-foreground">Relatórios</h1>
           <p className="text-sm text-muted-foreground">Análise inteligente de performance</p>
         </div>
-        <Button variant="outline" className="gap-1.5">
-          <Download className="h-4 w-4" />
-          Exportar PDF
-        </Button>
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button variant="outline" className="gap-1.5">
+              <Download className="h-4 w-4" />
+              Exportar
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align="end">
+            {EXPORT_TARGETS.map(({ target, label }, i) => (
+              <div key={target}>
+                {i > 0 && <DropdownMenuSeparator />}
+                <DropdownMenuLabel className="text-xs text-muted-foreground">{label}</DropdownMenuLabel>
+                <DropdownMenuItem onClick={() => handleExport(target, "xlsx")}>Excel (XLSX)</DropdownMenuItem>
+                <DropdownMenuItem onClick={() => handleExport(target, "csv")}>CSV</DropdownMenuItem>
+              </div>
+            ))}
+          </DropdownMenuContent>
+        </DropdownMenu>
       </div>
 
       {/* Filters */}
@@ -223,9 +278,9 @@ const Relatorios = () => {
               <div className="mt-3 flex items-center gap-1.5">
                 <span className={cn(
                   "text-xs font-bold px-1.5 py-0.5 rounded",
-                  s.change.startsWith('+') ? "bg-status-ok/10 text-status-ok" : "bg-destructive/10 text-destructive"
+                  (s.change ?? 0) >= 0 ? "bg-status-ok/10 text-status-ok" : "bg-destructive/10 text-destructive"
                 )}>
-                  {s.change}
+                  {formatChange(s.change)}
                 </span>
                 <span className="text-[10px] text-muted-foreground">vs período anterior</span>
               </div>
@@ -267,6 +322,38 @@ const Relatorios = () => {
         </CardContent>
       </Card>
 
+      {/* Profitability */}
+      {profitability && (
+        <Card className="card-shadow border-border/60">
+          <CardHeader>
+            <CardTitle className="text-base font-semibold">Lucratividade</CardTitle>
+            <p className="text-xs text-muted-foreground">
+              Receita dos itens vendidos, custo das mercadorias e margem bruta
+              {profitability.items_without_cost > 0 && ` • ${profitability.items_without_cost} item(ns) sem custo cadastrado`}
+            </p>
+          </CardHeader>
+          <CardContent className="space-y-6">
+            <div className="grid grid-cols-2 md:grid-cols-4 gap-4">
+              {[
+                { label: "Receita", value: formatCurrency(profitability.summary.revenue) },
+                { label: "Custo (CMV)", value: formatCurrency(profitability.summary.cost) },
+                { label: "Lucro Bruto", value: formatCurrency(profitability.summary.gross_profit) },
+                { label: "Margem", value: formatMargin(profitability.summary.margin) },
+              ].map((m) => (
+                <div key={m.label} className="rounded-lg bg-muted/30 p-3">
+                  <p className="text-xs text-muted-foreground">{m.label}</p>
+                  <p className="text-lg font-bold">{m.value}</p>
+                </div>
+              ))}
+            </div>
+            <div className="grid gap-6 lg:grid-cols-2">
+              <ProfitTable title="Por Produto" lines={profitability.by_product} />
+              <ProfitTable title="Por Categoria" lines={profitability.by_category} />
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
       {/* Sales Table */}
       <Card className="card-shadow border-border/60 overflow-hidden">
         <CardHeader className="bg-muted/30">
@@ -328,4 +415,41 @@ const Relatorios = () => {
   );
 };
 
+const formatMargin = (margin: number | null) =>
+  margin === null ? "-" : `${formatNumber(margin * 100, 1)}%`;
+
+const ProfitTable = ({ title, lines }: { title: string; lines: ProfitLine[] }) => (
+  <div className="rounded-lg border border-border/60 overflow-hidden">
+    <table className="w-full">
+      <thead>
+        <tr className="bg-muted/50 text-left text-xs font-semibold text-muted-foreground">
+          <th className="px-3 py-2">{title}</th>
+          <th className="px-3 py-2 text-right">Receita</th>
+          <th className="px-3 py-2 text-right">Custo</th>
+          <th className="px-3 py-2 text-right">Lucro</th>
+          <th className="px-3 py-2 text-right">Margem</th>
+        </tr>
+      </thead>
+      <tbody className="divide-y divide-border">
+        {lines.map((line) => (
+          <tr key={line.label}>
+            <td className="px-3 py-1.5 text-sm">{line.label}</td>
+            <td className="px-3 py-1.5 text-sm text-right">{formatCurrency(line.revenue)}</td>
+            <td className="px-3 py-1.5 text-sm text-right">{formatCurrency(line.cost)}</td>
+            <td className={cn("px-3 py-1.5 text-sm text-right font-semibold", line.gross_profit < 0 && "text-destructive")}>
+              {formatCurrency(line.gross_profit)}
+            </td>
+            <td className="px-3 py-1.5 text-sm text-right">{formatMargin(line.margin)}</td>
+          </tr>
+        ))}
+        {lines.length === 0 && (
+          <tr>
+            <td colSpan={5} className="px-3 py-6 text-center text-sm text-muted-foreground">Nenhuma venda no período.</td>
+          </tr>
+        )}
+      </tbody>
+    </table>
+  </div>
+);
+
 export default Relatorios;
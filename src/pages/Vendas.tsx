@@ -45,16 +45,7 @@ import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { NovaVendaForm } from "@/components/NovaVendaForm";
 import { generateAndPrintBlankPDF } from "@/components/SaleReceipt";
 import { PrintPreviewModal } from "@/components/PrintPreviewModal";
-
-interface Sale {
-    id: number;
-    client_id: number;
-    client_name: string;
-    items: string;
-    total: number;
-    payment_method: string;
-    created_at: string;
-}
+import type { ReportSale as Sale, SalesReport } from "@/lib/bindings";
 
 const Vendas = () => {
     const [sales, setSales] = useState<Sale[]>([]);
@@ -93,7 +84,7 @@ const Vendas = () => {
             const start = new Date();
             start.setFullYear(start.getFullYear() - 1);
 
-            const data = await invoke<any>("get_sales_report", {
+            const data = await invoke<SalesReport>("get_sales_report", {
                 startIso: start.toISOString(),
                 endIso: new Date().toISOString(),
                 paymentMethod: "todos"